@@ -8,20 +8,68 @@ use world::player;
 use world::WorldPlugin;
 
 use crate::game_state::{change_game_phase, GamePhaseState, toggle_game};
+use crate::network::client::ClientPlugin;
+use crate::network::game::ServerGame;
+use crate::network::NetworkMode;
+use crate::network::protocol::GameSetup;
 use crate::ui::action_panel::plugin::ActionPanelPlugin;
+use crate::ui::diplomacy::plugin::DiplomacyScreenPlugin;
+use crate::ui::galactic_event::plugin::GalacticEventPlugin;
 use crate::ui::game_result::plugin::GameResultPlugin;
+use crate::ui::handover::plugin::HandoverPlugin;
 use crate::ui::hud::HudPlugin;
 use crate::ui::stats::plugin::StatsPlugin;
+use crate::ui::tech_tree::plugin::TechTreePlugin;
+use crate::ui::tutorial::plugin::TutorialPanelPlugin;
+use crate::world::campaign::CampaignRun;
+use crate::world::factions::Factions;
 use crate::world::ownership::OwnershipPlugin;
-use crate::world::player::TurnPlugin;
+use crate::world::player::{TurnOrder, TurnPlugin};
+use crate::world::scenario::Scenario;
+use crate::world::terrain::GameMap;
+use crate::world::turn_timer::TimeControl;
+use crate::world::tutorial::Tutorial;
+use crate::world::victory::VictoryConditions;
 
 mod game_state;
+mod network;
 mod space_ships;
 mod ui;
 mod world;
 
 pub fn main() {
-    App::new()
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let network_mode = NetworkMode::from_args(args.iter().cloned());
+    let tutorial = Tutorial::from_args(args.iter().cloned());
+    let (campaign, scenario) = match CampaignRun::from_args(args.iter().cloned()) {
+        Some((campaign, scenario)) => (Some(campaign), Some(scenario)),
+        None => (None, Scenario::from_args(args.iter().cloned())),
+    };
+    let turn_order = scenario.as_ref()
+        .map_or_else(|| TurnOrder::from_args(args.iter().cloned()), Scenario::turn_order);
+    let factions = scenario.as_ref()
+        .map_or_else(|| Factions::from_args(args.iter().cloned(), &turn_order.players), Scenario::factions);
+    let mut victory_conditions = VictoryConditions::from_args(args.iter().cloned());
+    if let Some(scenario) = scenario.as_ref() {
+        scenario.apply_victory_conditions(&mut victory_conditions);
+    }
+    let mut game_map = match (tutorial.as_ref(), scenario.as_ref()) {
+        (Some(tutorial), _) => GameMap::named(&tutorial.map),
+        (None, Some(scenario)) => GameMap::named(&scenario.map),
+        (None, None) => GameMap::from_args(args.iter().cloned()),
+    };
+    if let Some(scenario) = scenario.as_ref() {
+        scenario.apply_homes(&mut game_map);
+    }
+    let setup = GameSetup::new(&turn_order, &factions, &game_map.name);
+    if let NetworkMode::Server { address, spectator_delay } = network_mode {
+        let game = ServerGame::new(turn_order, factions, victory_conditions, &game_map);
+        network::server::run_server(address, spectator_delay, game);
+        return;
+    }
+
+    let mut app = App::new();
+    app
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 resolution: (1_100.0, 1_100.0).into(),
@@ -32,12 +80,22 @@ pub fn main() {
         }))
         .add_state::<AppState>()
         .add_state::<GamePhaseState>()
+        .insert_resource(victory_conditions)
+        .insert_resource(factions)
+        .insert_resource(game_map)
+        .insert_resource(TimeControl::from_args(args.iter().cloned()))
+        .insert_resource(turn_order)
         // .add_plugins(bevy_editor_pls::EditorPlugin::default())
         .add_plugins(OwnershipPlugin)
         .add_plugins(DefaultPickingPlugins)
         .add_plugins(StatsPlugin)
         .add_plugins(HudPlugin)
         .add_plugins(GameResultPlugin)
+        .add_plugins(HandoverPlugin)
+        .add_plugins(GalacticEventPlugin)
+        .add_plugins(TechTreePlugin)
+        .add_plugins(DiplomacyScreenPlugin)
+        .add_plugins(TutorialPanelPlugin)
         .add_plugins(ActionPanelPlugin)
         .add_plugins(WorldPlugin)
         .add_plugins(GameStatePlugin)
@@ -56,6 +114,18 @@ pub fn main() {
                 world::navigations_systems::zoom_system,
                 world::navigations_systems::move_system,
             ),
-        )
-        .run()
+        );
+    if let Some(tutorial) = tutorial {
+        app.insert_resource(tutorial);
+    }
+    if let Some(scenario) = scenario {
+        app.insert_resource(scenario.ai_players()).insert_resource(scenario);
+    }
+    if let Some(campaign) = campaign {
+        app.insert_resource(campaign);
+    }
+    if let NetworkMode::Client { address, token, spectate } = network_mode {
+        app.add_plugins(ClientPlugin { address, token, spectate, setup });
+    }
+    app.run()
 }
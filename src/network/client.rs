@@ -0,0 +1,303 @@
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game_state::UpdateUI;
+use crate::network::connection::Connection;
+use crate::network::protocol::{ClientMessage, GameSetup, ServerMessage};
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::galactic_event::plugin::GalacticEventState;
+use crate::world::factions::Factions;
+use crate::world::fog_of_war::Viewer;
+use crate::world::fonts_and_styles::fonts::get_info_text_style;
+use crate::world::galactic_events::GalacticEvents;
+use crate::world::game_log::{GameEvent, LogEntry};
+use crate::world::player::{Movable, Player};
+use crate::world::snapshot::WorldState;
+use crate::world::turn_actions::TurnAction;
+
+const RECONNECT_INTERVAL: f32 = 2.0;
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+pub(crate) struct ClientPlugin {
+    pub address: String,
+    pub token: Option<String>,
+    /// Watch the game read-only instead of taking a seat.
+    pub spectate: bool,
+    pub setup: GameSetup,
+}
+
+impl Plugin for ClientPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(GameClient::new(self.address.clone(), self.token.clone(), self.spectate, self.setup.clone()))
+            .configure_sets(Update, (
+                UpdateUI::UserInput.run_if(is_local_turn),
+                UpdateUI::FieldSelect.run_if(is_local_turn),
+            ))
+            .add_systems(Startup, spawn_network_status)
+            .add_systems(Update, (connect_to_server, receive_server_messages, finish_turn_switch).chain().in_set(UpdateUI::NewRound))
+            .add_systems(Update, (announce_server_events, update_network_status).in_set(UpdateUI::RenderStats))
+            .add_systems(Update, forward_turn_actions.in_set(UpdateUI::FlipTurn));
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct NetworkStatusText;
+
+/// Connection of this process to the game server and the seat it was given.
+#[derive(Resource)]
+pub(crate) struct GameClient {
+    address: String,
+    connection: Option<Connection>,
+    reconnect_timer: Timer,
+    token: Option<String>,
+    spectator: bool,
+    setup: GameSetup,
+    /// How many turns behind the players a spectator is shown the game.
+    spectator_delay: u32,
+    pub seat: Option<Player>,
+    /// Last turn accepted by the server, 0 until the game has started.
+    pub turn: u32,
+    /// Round and player to move of the last state received from the server.
+    synced: Option<(i32, Option<Player>)>,
+    awaiting_reply: bool,
+    /// Length of the log of the last state received, `None` before the first one.
+    known_log: Option<usize>,
+    /// Galactic events and eliminations the server played out, waiting to be announced.
+    news: Vec<LogEntry>,
+}
+
+impl GameClient {
+    fn new(address: String, token: Option<String>, spectator: bool, setup: GameSetup) -> Self {
+        let mut reconnect_timer = Timer::from_seconds(RECONNECT_INTERVAL, TimerMode::Repeating);
+        // The first attempt is made right away
+        reconnect_timer.tick(Duration::from_secs_f32(RECONNECT_INTERVAL));
+        Self {
+            address,
+            connection: None,
+            reconnect_timer,
+            token,
+            spectator,
+            setup,
+            spectator_delay: 0,
+            seat: None,
+            turn: 0,
+            synced: None,
+            awaiting_reply: false,
+            known_log: None,
+            news: vec![],
+        }
+    }
+
+    pub(crate) fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    pub(crate) fn is_spectator(&self) -> bool {
+        self.spectator
+    }
+
+    fn send(&mut self, message: &ClientMessage) {
+        let Some(connection) = self.connection.as_mut() else { return; };
+        if let Err(error) = connection.send(message) {
+            warn!("Lost connection to {}: {}", self.address, error);
+            self.connection = None;
+        }
+    }
+}
+
+/// Hotseat game, nothing is synchronised over the network.
+pub(crate) fn is_local_game(client: Option<Res<GameClient>>) -> bool {
+    client.is_none()
+}
+
+pub(crate) fn is_spectator(client: Option<Res<GameClient>>) -> bool {
+    client.map_or(false, |client| client.is_spectator())
+}
+
+/// Input is only accepted from the seat whose turn the server says it is.
+pub(crate) fn is_local_turn(
+    client: Option<Res<GameClient>>,
+    current_player_query: Query<&Player, With<Movable>>,
+) -> bool {
+    let Some(client) = client else { return true; };
+    client.is_connected()
+        && !client.awaiting_reply
+        && client.synced.is_some()
+        && client.seat.is_some()
+        && client.seat == current_player_query.get_single().ok().copied()
+}
+
+fn connect_to_server(mut client: ResMut<GameClient>, time: Res<Time>) {
+    if client.is_connected() { return; }
+    client.reconnect_timer.tick(time.delta());
+    if !client.reconnect_timer.just_finished() { return; }
+
+    let Some(address) = client.address.to_socket_addrs().ok().and_then(|mut addresses| addresses.next()) else {
+        warn!("Cannot resolve {}", client.address);
+        return;
+    };
+    match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).and_then(Connection::new) {
+        Ok(connection) => {
+            info!("Connected to {}", client.address);
+            client.connection = Some(connection);
+            client.awaiting_reply = false;
+            let setup = client.setup.clone();
+            let message = if client.spectator {
+                ClientMessage::Spectate { setup }
+            } else {
+                ClientMessage::Join { token: client.token.clone(), setup }
+            };
+            client.send(&message);
+        }
+        Err(error) => warn!("Cannot connect to {}: {}", client.address, error),
+    }
+}
+
+fn receive_server_messages(
+    mut client: ResMut<GameClient>,
+    mut world_state: WorldState,
+    mut viewer: ResMut<Viewer>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    mut next_turn_switched_state: ResMut<NextState<TurnSwitchedState>>,
+) {
+    let Some(connection) = client.connection.as_mut() else { return; };
+    let messages = match connection.flush().and_then(|_| connection.receive::<ServerMessage>()) {
+        Ok(messages) => messages,
+        Err(error) => {
+            warn!("Lost connection to {}: {}, reconnecting", client.address, error);
+            client.connection = None;
+            return;
+        }
+    };
+
+    for message in messages {
+        match message {
+            ServerMessage::Welcome { seat, token, turn } => {
+                info!("Playing as {}, rejoin with --token {}", seat, token);
+                client.seat = Some(seat);
+                client.token = Some(token);
+                client.turn = turn;
+                client.synced = None;
+                *viewer = Viewer::Seat(seat);
+            }
+            ServerMessage::Spectating { delay } => {
+                info!("Spectating, {} turns behind the players", delay);
+                client.spectator_delay = delay;
+                *viewer = Viewer::FullMap;
+            }
+            ServerMessage::State { turn, snapshot } => {
+                world_state.apply(&snapshot);
+                let position = Some((snapshot.round, snapshot.current_player));
+                // The turn moved on without a local action, it switches the same way a local one does
+                if client.synced != position && *turn_switched_state.get() == TurnSwitchedState::OnDefaultState {
+                    next_turn_switched_state.set(TurnSwitchedState::OnTurnSwitched);
+                }
+                if let Some(known) = client.known_log {
+                    let news = snapshot.log.iter().skip(known)
+                        .filter(|entry| matches!(entry.event, GameEvent::GalacticEvent { .. } | GameEvent::PlayerEliminated))
+                        .cloned();
+                    client.news.extend(news);
+                }
+                client.known_log = Some(snapshot.log.len());
+                client.turn = turn;
+                client.synced = position;
+                client.awaiting_reply = false;
+            }
+            ServerMessage::Rejected { reason } => {
+                warn!("Server rejected the request: {}", reason);
+                client.awaiting_reply = false;
+            }
+        }
+    }
+}
+
+/// What the local player did goes to the server, which plays it out and answers with the resulting state.
+fn forward_turn_actions(mut client: ResMut<GameClient>, mut turn_actions: EventReader<TurnAction>) {
+    for action in turn_actions.read() {
+        if !client.is_connected() || client.seat.is_none() { continue; }
+        let base_turn = client.turn;
+        client.send(&ClientMessage::Act { base_turn, action: action.clone() });
+        // Every accepted action is a turn of the server, the next one builds on it
+        client.turn += 1;
+        client.awaiting_reply = true;
+    }
+}
+
+/// A turn switch ends once the server sent the state that follows it,
+/// so that the start of the next turn is shown on the real board.
+fn finish_turn_switch(
+    client: Res<GameClient>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    mut next_turn_switched_state: ResMut<NextState<TurnSwitchedState>>,
+) {
+    if *turn_switched_state.get() != TurnSwitchedState::OnTurnSwitched || client.awaiting_reply || client.synced.is_none() { return; }
+    next_turn_switched_state.set(TurnSwitchedState::OnDefaultState);
+}
+
+/// Galactic events and eliminations happen on the server, they are announced the way a local game does.
+fn announce_server_events(
+    mut client: ResMut<GameClient>,
+    factions: Res<Factions>,
+    mut galactic_events: ResMut<GalacticEvents>,
+    mut event_state: ResMut<NextState<GalacticEventState>>,
+) {
+    if client.news.is_empty() { return; }
+    for entry in client.news.drain(..) {
+        match entry.event {
+            GameEvent::GalacticEvent { name, outcome } => {
+                let description = galactic_events.deck.iter().find(|event| event.name == name).map(|event| event.description.clone()).unwrap_or_default();
+                galactic_events.announce(name, format!("{}\n{}", description, outcome));
+            }
+            GameEvent::PlayerEliminated => {
+                let name = factions.name(&entry.player);
+                galactic_events.announce(format!("{} eliminated", name), format!("{} has no planets and no fleets left", name));
+            }
+            _ => {}
+        }
+    }
+    event_state.set(GalacticEventState::Shown);
+}
+
+fn spawn_network_status(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section("", get_info_text_style(&asset_server)).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Percent(40.0),
+            ..default()
+        }),
+        NetworkStatusText,
+    ));
+}
+
+fn update_network_status(
+    client: Res<GameClient>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut text_query: Query<&mut Text, With<NetworkStatusText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    let status = match (client.is_connected(), client.seat) {
+        (false, _) => format!("Connecting to {}...", client.address),
+        (true, None) if client.spectator => {
+            let perspective = match *viewer {
+                Viewer::Seat(player) => player.to_string(),
+                _ => "full map".to_string(),
+            };
+            format!("Spectating ({}, V to switch), {} turns behind", perspective, client.spectator_delay)
+        }
+        (true, None) => "Waiting for a seat...".to_string(),
+        (true, Some(seat)) if client.synced.is_none() => format!("{}, waiting for every seat to be taken", seat),
+        (true, Some(seat)) => match current_player_query.get_single() {
+            Ok(player) if *player == seat => format!("{}, your turn", seat),
+            Ok(player) => format!("{}, waiting for {}", seat, player),
+            Err(_) => format!("{}", seat),
+        },
+    };
+    if text.sections[0].value != status {
+        text.sections[0].value = status;
+    }
+}
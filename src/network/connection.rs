@@ -0,0 +1,168 @@
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+
+use bevy::log::warn;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Longest message taken from a peer, snapshots of long games stay well below it.
+/// A peer sending a longer line is dropped rather than buffered without end.
+const MAX_LINE_LENGTH: usize = 4 * 1024 * 1024;
+
+/// Non-blocking TCP stream carrying newline delimited JSON messages, polled from systems every frame.
+pub(crate) struct Connection {
+    stream: TcpStream,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+}
+
+impl Connection {
+    pub(crate) fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self { stream, incoming: vec![], outgoing: vec![] })
+    }
+
+    pub(crate) fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.outgoing, message)?;
+        self.outgoing.push(b'\n');
+        self.flush()
+    }
+
+    /// Writes as much of the queued data as the socket accepts right now.
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(written) => { self.outgoing.drain(..written); }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
+    /// All complete messages received since the last call, an error means the peer is gone
+    /// or sent a line longer than `MAX_LINE_LENGTH` and has to be dropped.
+    pub(crate) fn receive<T: DeserializeOwned>(&mut self) -> io::Result<Vec<T>> {
+        let mut buffer = [0u8; 4096];
+        let mut messages = vec![];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => return Err(ErrorKind::ConnectionAborted.into()),
+                Ok(read) => {
+                    self.incoming.extend_from_slice(&buffer[..read]);
+                    if buffer[..read].contains(&b'\n') {
+                        self.take_messages(&mut messages);
+                    }
+                    // Only the start of a line is left, it may not grow past the limit
+                    if self.incoming.len() > MAX_LINE_LENGTH {
+                        return Err(io::Error::new(ErrorKind::InvalidData, "message too long"));
+                    }
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(messages)
+    }
+
+    fn take_messages<T: DeserializeOwned>(&mut self, messages: &mut Vec<T>) {
+        while let Some(end) = self.incoming.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.incoming.drain(..=end).collect();
+            match serde_json::from_slice(&line[..line.len() - 1]) {
+                Ok(message) => messages.push(message),
+                Err(error) => warn!("Dropping malformed message: {}", error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    const DEADLINE: Duration = Duration::from_secs(5);
+
+    fn connected_pair() -> (Connection, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (Connection::new(client).unwrap(), server)
+    }
+
+    /// Polls until `count` messages arrived or the deadline passed.
+    fn receive_messages(connection: &mut Connection, count: usize) -> Vec<Vec<u32>> {
+        let deadline = Instant::now() + DEADLINE;
+        let mut messages = vec![];
+        while messages.len() < count && Instant::now() < deadline {
+            messages.extend(connection.receive::<Vec<u32>>().unwrap());
+        }
+        messages
+    }
+
+    /// Polls until receiving fails or the deadline passed, returns whether it failed.
+    fn receive_fails(connection: &mut Connection) -> bool {
+        let deadline = Instant::now() + DEADLINE;
+        while Instant::now() < deadline {
+            if connection.receive::<Vec<u32>>().is_err() { return true; }
+        }
+        false
+    }
+
+    #[test]
+    fn messages_are_split_at_newlines() {
+        let (mut connection, mut peer) = connected_pair();
+        peer.write_all(b"[1,2]\n[3]\n").unwrap();
+        assert_eq!(receive_messages(&mut connection, 2), vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn partial_messages_wait_for_the_rest() {
+        let (mut connection, mut peer) = connected_pair();
+        peer.write_all(b"[1,").unwrap();
+        assert!(connection.receive::<Vec<u32>>().unwrap().is_empty());
+        peer.write_all(b"2]\n[4").unwrap();
+        assert_eq!(receive_messages(&mut connection, 1), vec![vec![1, 2]]);
+        peer.write_all(b"]\n").unwrap();
+        assert_eq!(receive_messages(&mut connection, 1), vec![vec![4]]);
+    }
+
+    #[test]
+    fn malformed_messages_are_dropped() {
+        let (mut connection, mut peer) = connected_pair();
+        peer.write_all(b"not json\n[5]\n").unwrap();
+        assert_eq!(receive_messages(&mut connection, 1), vec![vec![5]]);
+    }
+
+    #[test]
+    fn sent_messages_arrive_as_one_line_each() {
+        let (mut sender, peer) = connected_pair();
+        let mut receiver = Connection::new(peer).unwrap();
+        sender.send(&vec![7u32]).unwrap();
+        sender.send(&vec![8u32, 9]).unwrap();
+        assert_eq!(receive_messages(&mut receiver, 2), vec![vec![7], vec![8, 9]]);
+    }
+
+    #[test]
+    fn closed_peer_is_an_error() {
+        let (mut connection, peer) = connected_pair();
+        drop(peer);
+        assert!(receive_fails(&mut connection));
+    }
+
+    #[test]
+    fn overlong_lines_are_an_error() {
+        let (mut connection, mut peer) = connected_pair();
+        // Blocks once the socket buffers are full, so it writes from its own thread until the connection is dropped
+        thread::spawn(move || { let _ = peer.write_all(&vec![b'1'; MAX_LINE_LENGTH + 1]); });
+        assert!(receive_fails(&mut connection));
+    }
+}
@@ -0,0 +1,308 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::{Hex, HexLayout};
+
+use crate::space_ships::ShipIds;
+use crate::world::buildings::{advance_buildings, start_building};
+use crate::world::defense::{can_fortify, fortify};
+use crate::world::diplomacy::{answer_proposal, break_treaty, can_attack, Diplomacy, make_proposal};
+use crate::world::elimination::{eliminate_players, surrender_planets};
+use crate::world::factions::Factions;
+use crate::world::fleets::{Fleet, move_fleets, plan_fleets};
+use crate::world::galactic_events::{draw_event, GalacticEvents, is_event_due};
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::objectives::Objectives;
+use crate::world::pathfinding::{can_fly_path, find_path};
+use crate::world::player::{INITIAL_MOVES, next_turn, NextTurn, Player, start_round, Stats, TurnOrder};
+use crate::world::production::{change_production, deliver_ships, ProductionChange, ProductionDraft};
+use crate::world::random::GameRng;
+use crate::world::research::{Research, start_research};
+use crate::world::resources::{GameResources, setup_resources};
+use crate::world::setup_world_grid::{create_objectives, create_planets, HexGrid};
+use crate::world::snapshot::{capture_planets, capture_player, capture_storms, GameSnapshot};
+use crate::world::supply::compute_supply;
+use crate::world::terrain::GameMap;
+use crate::world::turn_actions::TurnAction;
+use crate::world::victory::{end_round, GameOutcome, VictoryConditions};
+
+/// The game as the server runs it, without entities or rendering.
+/// Clients only send what they did, the rules are played out here and the resulting state is sent back.
+pub(crate) struct ServerGame {
+    pub turn_order: TurnOrder,
+    pub factions: Factions,
+    pub map: String,
+    grid: HexGrid,
+    resources: GameResources,
+    round: i32,
+    players: Vec<(Player, Stats)>,
+    current_player: Option<Player>,
+    fleets: Vec<Fleet>,
+    game_log: GameLog,
+    objectives: Objectives,
+    research: Research,
+    galactic_events: GalacticEvents,
+    diplomacy: Diplomacy,
+    ship_ids: ShipIds,
+    rng: GameRng,
+    victory_conditions: VictoryConditions,
+    outcome: Option<GameOutcome>,
+}
+
+impl ServerGame {
+    /// Sets the board up the way `setup_grid` does and starts the turn of the first player.
+    pub(crate) fn new(turn_order: TurnOrder, factions: Factions, victory_conditions: VictoryConditions, game_map: &GameMap) -> Self {
+        let mut ship_ids = ShipIds::default();
+        let mut research = Research::load();
+        let mut planets = create_planets(game_map, &mut ship_ids);
+        let resources = setup_resources(&mut planets, &turn_order, &factions, game_map, &mut research, &mut ship_ids);
+        let players = turn_order.players.iter()
+            .map(|player| (*player, Stats { moves_left: INITIAL_MOVES, win_points: 0, eliminated: false }))
+            .collect();
+        let mut game = Self {
+            current_player: Some(turn_order.first()),
+            map: game_map.name.clone(),
+            grid: HexGrid { entities: HashMap::new(), layout: HexLayout::default(), planets },
+            resources,
+            round: 1,
+            players,
+            fleets: vec![],
+            game_log: GameLog::default(),
+            objectives: create_objectives(game_map, &victory_conditions),
+            research,
+            galactic_events: GalacticEvents::load(),
+            diplomacy: Diplomacy::default(),
+            ship_ids,
+            rng: GameRng::default(),
+            victory_conditions,
+            outcome: None,
+            turn_order,
+            factions,
+        };
+        game.start_turn();
+        game
+    }
+
+    pub(crate) fn snapshot(&self) -> GameSnapshot {
+        let mut players: Vec<_> = self.players.iter()
+            .map(|(player, stats)| capture_player(player, stats, &self.resources, &self.objectives, &self.research))
+            .collect();
+        players.sort_by_key(|snapshot| snapshot.player.id);
+        GameSnapshot {
+            round: self.round,
+            current_player: self.current_player,
+            players,
+            planets: capture_planets(&self.grid),
+            fleets: self.fleets.clone(),
+            log: self.game_log.entries.clone(),
+            storms: capture_storms(&self.galactic_events),
+            diplomacy: self.diplomacy.clone(),
+            outcome: self.outcome.clone(),
+        }
+    }
+
+    /// Checks `action` against the rules and plays it out for `player`, the state is unchanged on an error.
+    pub(crate) fn perform(&mut self, player: Player, action: TurnAction) -> Result<(), String> {
+        if self.outcome.is_some() {
+            return Err("The game is over".to_string());
+        }
+        if self.current_player != Some(player) {
+            return Err("It is not your turn".to_string());
+        }
+        let ends_turn = action.ends_turn();
+        match action {
+            TurnAction::ChangeProduction { hex, changes } => self.change_production(&player, hex, changes)?,
+            TurnAction::Build { hex, building } => {
+                let planet = self.grid.planets.get_mut(&hex).ok_or("There is no planet there")?;
+                if !start_building(planet, &player, &mut self.resources, building, &mut self.game_log, self.round) {
+                    return Err(format!("Cannot build a {} there", building));
+                }
+            }
+            TurnAction::MoveShips { ships, destination } => self.move_ships(&player, ships, destination)?,
+            TurnAction::Fortify { hex } => {
+                if !can_fortify(&player, &self.grid, &hex) {
+                    return Err("Cannot fortify there".to_string());
+                }
+                fortify(&player, &mut self.grid, &hex, &mut self.game_log, self.round);
+            }
+            TurnAction::StartResearch { tech } => {
+                if !start_research(&mut self.research, &mut self.resources, &player, &tech, &mut self.game_log, self.round) {
+                    return Err(format!("Cannot research {}", tech));
+                }
+            }
+            TurnAction::Propose { proposal } => {
+                if proposal.from != player || !make_proposal(&mut self.diplomacy, &mut self.resources, proposal, &mut self.game_log, self.round) {
+                    return Err("Cannot make that proposal".to_string());
+                }
+            }
+            TurnAction::Answer { proposal, accept } => {
+                if proposal.to != player || !answer_proposal(&mut self.diplomacy, &mut self.resources, proposal, accept, &mut self.game_log, self.round) {
+                    return Err("There is no such proposal".to_string());
+                }
+            }
+            TurnAction::SkipRound => {
+                self.stats_mut(&player).moves_left = 1;
+                self.game_log.record(self.round, player, GameEvent::RoundSkipped);
+            }
+            TurnAction::Surrender => {
+                self.fleets.retain(|fleet| fleet.owner != player);
+                surrender_planets(&player, &mut self.grid, &mut self.game_log, self.round);
+            }
+            TurnAction::OutOfTime { skip_round } => {
+                if skip_round {
+                    self.stats_mut(&player).moves_left = 1;
+                }
+                self.game_log.record(self.round, player, GameEvent::OutOfTime);
+            }
+        }
+        self.game_log.note_witnesses(&self.turn_order.players, &self.grid, self.fleets.iter());
+        let is_out = self.check_eliminations();
+        if (ends_turn || is_out) && self.outcome.is_none() {
+            self.end_turn();
+        }
+        Ok(())
+    }
+
+    fn stats_mut(&mut self, player: &Player) -> &mut Stats {
+        self.players.iter_mut().find(|(other, _)| other == player).map(|(_, stats)| stats).expect("Every player has stats")
+    }
+
+    /// Applies the edits to a copy of the planet, so a rejected one leaves the queue as it was.
+    fn change_production(&mut self, player: &Player, hex: Hex, changes: Vec<ProductionChange>) -> Result<(), String> {
+        let supply = compute_supply(&self.grid.planets, self.fleets.iter(), &self.diplomacy);
+        let planet = self.grid.planets.get(&hex).ok_or("There is no planet there")?;
+        if planet.owner != *player || !planet.has_shipyard() || supply.is_cut_off(&hex) {
+            return Err("There is no working shipyard of yours there".to_string());
+        }
+        let draft = ProductionDraft::new(planet, &self.resources);
+        let mut planet = planet.clone();
+        let mut resources = self.resources.clone();
+        for change in changes {
+            if !change_production(&mut planet, player, &mut resources, &self.research, change) {
+                return Err(format!("Cannot change the production: {:?}", change));
+            }
+        }
+        let ordered = draft.ordered(&planet);
+        self.grid.planets.insert(hex, planet);
+        self.resources = resources;
+        if !ordered.is_empty() {
+            self.game_log.record(self.round, *player, GameEvent::ShipsBought { hex, ships: ordered });
+        }
+        Ok(())
+    }
+
+    /// Every ship has to be a garrison of `player` able to fly the whole way to `destination`.
+    fn move_ships(&mut self, player: &Player, ship_ids: Vec<u32>, destination: Hex) -> Result<(), String> {
+        let defender = self.grid.planets.get(&destination).ok_or("There is no planet there")?.owner;
+        if !can_attack(player, &defender, &self.diplomacy, &self.resources) {
+            return Err(format!("Cannot attack {}", defender));
+        }
+        let mut unique_ids = ship_ids.clone();
+        unique_ids.sort();
+        unique_ids.dedup();
+        if unique_ids.is_empty() || unique_ids.len() != ship_ids.len() {
+            return Err("Select every ship once".to_string());
+        }
+
+        let mut army = vec![];
+        for id in ship_ids.iter() {
+            let ship = self.grid.planets.values()
+                .filter(|planet| planet.owner == *player)
+                .flat_map(|planet| planet.owner_army.iter())
+                .find(|ship| ship.id == *id)
+                .ok_or_else(|| format!("Ship {} is not on a planet of yours", id))?;
+            if ship.ship_hex == destination {
+                return Err(format!("Ship {} is already there", id));
+            }
+            let path = find_path(player, ship.ship_hex, destination, &self.grid.planets, &self.diplomacy)
+                .ok_or_else(|| format!("There is no route for ship {}", id))?;
+            if !can_fly_path(ship, &path, &self.grid.planets, &self.research) {
+                return Err(format!("The {} cannot fly that far", ship.ship_type));
+            }
+            army.push(ship.clone());
+        }
+
+        if self.diplomacy.is_at_peace(player, &defender) {
+            break_treaty(player, &defender, &mut self.diplomacy, &mut self.resources, &mut self.game_log, self.round);
+        }
+        for planet in self.grid.planets.values_mut() {
+            planet.owner_army.retain(|ship| !ship_ids.contains(&ship.id));
+        }
+        let fleets = plan_fleets(player, army, destination, &mut self.grid, &self.diplomacy, &mut self.game_log, self.round);
+        self.fleets.extend(fleets);
+        Ok(())
+    }
+
+    /// Same as `determine_turn`, the round ends once nobody has moves left.
+    fn end_turn(&mut self) {
+        let Some(current) = self.current_player else { return; };
+        self.stats_mut(&current).moves_left -= 1;
+        match next_turn(&current, &self.turn_order, self.players.iter().map(|(player, stats)| (player, stats))) {
+            NextTurn::Player(next) => {
+                self.current_player = Some(next);
+                self.start_turn();
+            }
+            NextTurn::Again => self.start_turn(),
+            NextTurn::RoundOver => {
+                self.current_player = None;
+                self.end_round();
+            }
+        }
+    }
+
+    /// Same as `update_win_points_number` followed by `update_round_number_text`.
+    fn end_round(&mut self) {
+        let outcome = end_round(self.players.iter_mut().map(|(player, stats)| (&*player, stats)), &mut self.objectives, &self.victory_conditions,
+                                self.round, &self.grid, &self.resources, self.fleets.iter(), &self.diplomacy);
+        if let Some(outcome) = outcome {
+            info!("Game over: {:?}", outcome);
+            self.outcome = Some(outcome);
+            return;
+        }
+
+        self.round += 1;
+        self.current_player = start_round(&self.turn_order, self.players.iter_mut().map(|(player, stats)| (&*player, stats)));
+        self.start_turn();
+    }
+
+    /// Everything the `OnEnter(OnDefaultState)` systems do when a turn starts.
+    fn start_turn(&mut self) {
+        let Some(player) = self.current_player else { return; };
+        self.diplomacy.expire(self.round);
+        if is_event_due(&player, self.round, &self.turn_order, self.players.iter().map(|(player, stats)| (player, stats))) {
+            draw_event(&mut self.galactic_events, &mut self.grid, &mut self.resources, &mut self.rng, &mut self.ship_ids,
+                       &self.research, &self.factions, &mut self.game_log, self.round);
+        }
+
+        // Keyed by position, so the fleets keep their order
+        let mut fleets: HashMap<usize, Fleet> = self.fleets.drain(..).enumerate().collect();
+        move_fleets(&player, &mut fleets, &mut self.grid, &mut self.game_log, self.round, &mut self.rng,
+                    &self.research, &self.factions, &mut self.diplomacy, &mut self.resources);
+        let mut fleets: Vec<(usize, Fleet)> = fleets.into_iter().collect();
+        fleets.sort_by_key(|(index, _)| *index);
+        self.fleets = fleets.into_iter().map(|(_, fleet)| fleet).collect();
+
+        let supply = compute_supply(&self.grid.planets, self.fleets.iter(), &self.diplomacy);
+        deliver_ships(&player, &mut self.grid, &mut self.ship_ids, &supply, &mut self.game_log, self.round);
+        advance_buildings(&player, &mut self.grid, &mut self.game_log, self.round);
+        self.research.advance(&player, &mut self.game_log, self.round);
+        let stormy_hexes = self.galactic_events.stormy_hexes(self.round);
+        self.resources.update(&self.grid, &player, self.factions.planet_bonus(&player), &stormy_hexes, &supply);
+        self.game_log.note_witnesses(&self.turn_order.players, &self.grid, self.fleets.iter());
+
+        if self.check_eliminations() && self.outcome.is_none() {
+            self.end_turn();
+        }
+    }
+
+    /// Same as `detect_eliminations`, returns whether the player to move is out.
+    fn check_eliminations(&mut self) -> bool {
+        let fleets: Vec<&Fleet> = self.fleets.iter().collect();
+        let eliminations = eliminate_players(self.players.iter_mut().map(|(player, stats)| (&*player, stats)), self.current_player, &self.turn_order,
+                                             &self.grid, &fleets, &mut self.diplomacy, &mut self.resources, &mut self.game_log, self.round);
+        if let Some(outcome) = eliminations.outcome {
+            info!("Game over: {:?}", outcome);
+            self.outcome = Some(outcome);
+        }
+        eliminations.is_current_out
+    }
+}
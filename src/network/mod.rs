@@ -0,0 +1,39 @@
+pub(crate) mod client;
+pub(crate) mod connection;
+pub(crate) mod game;
+pub(crate) mod protocol;
+pub(crate) mod server;
+
+/// How the game was started, read from the command line:
+/// `--server [address] [--spectator-delay <turns>]` hosts a headless game,
+/// `--connect <address> [--token <token>] [--spectate]` joins one.
+/// Players, factions and map are given to both, clients with a different setup are turned away.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum NetworkMode {
+    Local,
+    Server { address: String, spectator_delay: u32 },
+    Client { address: String, token: Option<String>, spectate: bool },
+}
+
+impl NetworkMode {
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Self {
+        let args: Vec<String> = args.collect();
+        let value_after = |flag: &str| args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+            .filter(|value| !value.starts_with("--"))
+            .cloned();
+
+        if args.iter().any(|arg| arg == "--server") {
+            let address = value_after("--server").unwrap_or_else(|| protocol::default_address());
+            let spectator_delay = value_after("--spectator-delay").and_then(|value| value.parse().ok()).unwrap_or(0);
+            NetworkMode::Server { address, spectator_delay }
+        } else if args.iter().any(|arg| arg == "--connect") {
+            let address = value_after("--connect").unwrap_or_else(|| protocol::default_address());
+            let spectate = args.iter().any(|arg| arg == "--spectate");
+            NetworkMode::Client { address, token: value_after("--token"), spectate }
+        } else {
+            NetworkMode::Local
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::world::factions::Factions;
+use crate::world::player::{Player, TurnOrder};
+use crate::world::snapshot::GameSnapshot;
+use crate::world::terrain::GameMap;
+use crate::world::turn_actions::TurnAction;
+
+pub(crate) const DEFAULT_PORT: u16 = 7777;
+
+pub(crate) fn default_address() -> String {
+    format!("127.0.0.1:{}", DEFAULT_PORT)
+}
+
+/// What a client was started with, it has to match the game of the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct GameSetup {
+    /// Seats of the game in turn order, handed out to clients in the order they join.
+    pub players: Vec<Player>,
+    /// Faction of every seat, in the same order.
+    pub factions: Vec<String>,
+    pub map: String,
+}
+
+impl GameSetup {
+    pub(crate) fn new(turn_order: &TurnOrder, factions: &Factions, map: &str) -> Self {
+        Self {
+            players: turn_order.players.clone(),
+            factions: turn_order.players.iter().map(|player| factions.players.get(player).cloned().unwrap_or_default()).collect(),
+            map: map.to_string(),
+        }
+    }
+}
+
+/// Messages are sent as one JSON document per line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum ClientMessage {
+    /// `token` is the one received in `Welcome`, it gives the same seat back after a reconnect.
+    Join { token: Option<String>, setup: GameSetup },
+    /// Watch the game without a seat.
+    Spectate { setup: GameSetup },
+    /// Something the sender did on their turn, `base_turn` is the turn they saw when doing it.
+    Act { base_turn: u32, action: TurnAction },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum ServerMessage {
+    Welcome { seat: Player, token: String, turn: u32 },
+    /// Spectators see every state `delay` turns after the players do.
+    Spectating { delay: u32 },
+    /// Canonical state of the game, sent once every seat is taken, after every accepted action and on join.
+    State { turn: u32, snapshot: GameSnapshot },
+    Rejected { reason: String },
+}
@@ -0,0 +1,266 @@
+use std::net::TcpListener;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use crate::network::connection::Connection;
+use crate::network::game::ServerGame;
+use crate::network::protocol::{ClientMessage, GameSetup, ServerMessage};
+use crate::world::player::Player;
+use crate::world::snapshot::GameSnapshot;
+use crate::world::turn_actions::TurnAction;
+
+/// Runs the authoritative server without a window until the process is killed.
+pub(crate) fn run_server(address: String, spectator_delay: u32, game: ServerGame) {
+    App::new()
+        .add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_millis(16))))
+        .add_plugins(LogPlugin::default())
+        .add_plugins(ServerPlugin { address, spectator_delay, game: Mutex::new(Some(game)) })
+        .run();
+}
+
+pub(crate) struct ServerPlugin {
+    pub address: String,
+    /// How many turns spectators lag behind, so they cannot pass live information to a player.
+    pub spectator_delay: u32,
+    /// Taken out when the plugin is built, plugins only get shared access to themselves.
+    pub game: Mutex<Option<ServerGame>>,
+}
+
+impl Plugin for ServerPlugin {
+    fn build(&self, app: &mut App) {
+        let listener = TcpListener::bind(&self.address)
+            .unwrap_or_else(|error| panic!("Cannot listen on {}: {}", self.address, error));
+        listener.set_nonblocking(true).expect("Cannot make the listener non-blocking");
+        info!("Server is listening on {}", self.address);
+        let game = self.game.lock().unwrap().take().expect("The server plugin is only built once");
+        app
+            .insert_resource(GameServer::new(listener, self.spectator_delay, game))
+            .add_systems(Update, (accept_connections, handle_client_messages).chain());
+    }
+}
+
+/// A seat stays reserved for its token when the client drops, so the same player can come back.
+struct Seat {
+    player: Player,
+    token: String,
+    connection: Option<u32>,
+}
+
+/// The server decides who sits where and runs the game, clients only tell it what they did.
+#[derive(Resource)]
+pub(crate) struct GameServer {
+    listener: TcpListener,
+    connections: HashMap<u32, Connection>,
+    next_connection_id: u32,
+    seats: Vec<Seat>,
+    spectators: HashSet<u32>,
+    spectator_delay: u32,
+    game: ServerGame,
+    /// Number of accepted actions.
+    turn: u32,
+    /// State after every accepted action, the one of turn `n` is at index `n`.
+    history: Vec<GameSnapshot>,
+}
+
+impl GameServer {
+    fn new(listener: TcpListener, spectator_delay: u32, game: ServerGame) -> Self {
+        Self {
+            listener,
+            connections: HashMap::new(),
+            next_connection_id: 0,
+            seats: vec![],
+            spectators: HashSet::new(),
+            spectator_delay,
+            history: vec![game.snapshot()],
+            game,
+            turn: 0,
+        }
+    }
+
+    fn setup(&self) -> GameSetup {
+        GameSetup::new(&self.game.turn_order, &self.game.factions, &self.game.map)
+    }
+
+    fn check_setup(&self, setup: &GameSetup) -> Result<(), String> {
+        let expected = self.setup();
+        if *setup == expected { return Ok(()); }
+        Err(format!("The server plays {} players with factions {:?} on map '{}'", expected.players.len(), expected.factions, expected.map))
+    }
+
+    /// The game only starts once every seat of the turn order is taken.
+    fn is_full(&self) -> bool {
+        self.seats.len() == self.game.turn_order.players.len()
+    }
+
+    fn seat_of(&self, connection_id: u32) -> Option<Player> {
+        self.seats.iter().find(|seat| seat.connection == Some(connection_id)).map(|seat| seat.player)
+    }
+
+    fn send(&mut self, connection_id: u32, message: &ServerMessage) {
+        let Some(connection) = self.connections.get_mut(&connection_id) else { return; };
+        if let Err(error) = connection.send(message) {
+            warn!("Cannot send to connection {}: {}", connection_id, error);
+            self.disconnect(connection_id);
+        }
+    }
+
+    fn broadcast_state(&mut self) {
+        let ids: Vec<u32> = self.connections.keys().copied().collect();
+        for id in ids {
+            if let Some(state) = self.state_message_for(id) {
+                self.send(id, &state);
+            }
+        }
+    }
+
+    fn disconnect(&mut self, connection_id: u32) {
+        self.connections.remove(&connection_id);
+        self.spectators.remove(&connection_id);
+        for seat in self.seats.iter_mut().filter(|seat| seat.connection == Some(connection_id)) {
+            info!("{} disconnected, the seat is kept for a reconnect", seat.player);
+            seat.connection = None;
+        }
+    }
+
+    fn join(&mut self, connection_id: u32, token: Option<String>, setup: &GameSetup) -> Result<(Player, String), String> {
+        self.check_setup(setup)?;
+        if let Some(seat) = self.seats.iter_mut().find(|seat| Some(&seat.token) == token.as_ref()) {
+            let previous = seat.connection.replace(connection_id);
+            let joined = (seat.player, seat.token.clone());
+            if let Some(previous) = previous.filter(|previous| *previous != connection_id) {
+                self.connections.remove(&previous);
+            }
+            info!("{} reconnected", joined.0);
+            return Ok(joined);
+        }
+        if self.seat_of(connection_id).is_some() || self.spectators.contains(&connection_id) {
+            return Err("Already in the game".to_string());
+        }
+        let Some(player) = self.game.turn_order.players.iter().find(|player| self.seats.iter().all(|seat| seat.player != **player)) else {
+            return Err("The game is full".to_string());
+        };
+        let token = format!("{:016x}", rand::random::<u64>());
+        self.seats.push(Seat { player: *player, token: token.clone(), connection: Some(connection_id) });
+        info!("{} joined", player);
+        Ok((*player, token))
+    }
+
+    fn act(&mut self, connection_id: u32, base_turn: u32, action: TurnAction) -> Result<(), String> {
+        let Some(seat) = self.seat_of(connection_id) else {
+            return Err(if self.spectators.contains(&connection_id) { "Spectators cannot play" } else { "Join the game first" }.to_string());
+        };
+        if !self.is_full() {
+            return Err("The game starts once every seat is taken".to_string());
+        }
+        if base_turn != self.turn {
+            return Err(format!("Turn {} is already over", base_turn));
+        }
+        self.game.perform(seat, action)?;
+
+        self.turn += 1;
+        self.history.push(self.game.snapshot());
+        info!("Turn {} played by {}", self.turn, seat);
+        Ok(())
+    }
+
+    /// Latest state for players, the delayed one for spectators, nothing before the game started.
+    fn state_message_for(&self, connection_id: u32) -> Option<ServerMessage> {
+        if !self.is_full() { return None; }
+        let turn = if self.spectators.contains(&connection_id) {
+            self.turn.saturating_sub(self.spectator_delay)
+        } else {
+            self.turn
+        };
+        let snapshot = self.history[turn as usize].clone();
+        Some(ServerMessage::State { turn, snapshot })
+    }
+}
+
+fn accept_connections(mut server: ResMut<GameServer>) {
+    loop {
+        match server.listener.accept() {
+            Ok((stream, address)) => match Connection::new(stream) {
+                Ok(connection) => {
+                    let id = server.next_connection_id;
+                    server.next_connection_id += 1;
+                    server.connections.insert(id, connection);
+                    info!("Connection {} from {}", id, address);
+                }
+                Err(error) => warn!("Cannot set up connection from {}: {}", address, error),
+            },
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => break,
+            Err(error) => {
+                warn!("Cannot accept a connection: {}", error);
+                break;
+            }
+        }
+    }
+}
+
+fn handle_client_messages(mut server: ResMut<GameServer>) {
+    let mut received: Vec<(u32, ClientMessage)> = vec![];
+    let mut lost: Vec<u32> = vec![];
+    for (id, connection) in server.connections.iter_mut() {
+        match connection.flush().and_then(|_| connection.receive::<ClientMessage>()) {
+            Ok(messages) => received.extend(messages.into_iter().map(|message| (*id, message))),
+            Err(_) => lost.push(*id),
+        }
+    }
+    for id in lost {
+        server.disconnect(id);
+    }
+
+    for (id, message) in received {
+        match message {
+            ClientMessage::Join { token, setup } => {
+                let was_full = server.is_full();
+                match server.join(id, token, &setup) {
+                    Ok((seat, token)) => {
+                        let turn = server.turn;
+                        server.send(id, &ServerMessage::Welcome { seat, token, turn });
+                        if !was_full && server.is_full() {
+                            info!("Every seat is taken, the game starts");
+                            server.broadcast_state();
+                        } else if let Some(state) = server.state_message_for(id) {
+                            server.send(id, &state);
+                        }
+                    }
+                    Err(reason) => server.send(id, &ServerMessage::Rejected { reason }),
+                }
+            }
+            ClientMessage::Spectate { setup } => {
+                if server.seat_of(id).is_some() {
+                    server.send(id, &ServerMessage::Rejected { reason: "Players cannot spectate".to_string() });
+                    continue;
+                }
+                if let Err(reason) = server.check_setup(&setup) {
+                    server.send(id, &ServerMessage::Rejected { reason });
+                    continue;
+                }
+                server.spectators.insert(id);
+                info!("Connection {} is spectating", id);
+                let delay = server.spectator_delay;
+                server.send(id, &ServerMessage::Spectating { delay });
+                if let Some(state) = server.state_message_for(id) {
+                    server.send(id, &state);
+                }
+            }
+            ClientMessage::Act { base_turn, action } => match server.act(id, base_turn, action) {
+                Ok(()) => server.broadcast_state(),
+                Err(reason) => {
+                    warn!("Rejected an action from connection {}: {}", id, reason);
+                    server.send(id, &ServerMessage::Rejected { reason });
+                    // Puts the client back onto the canonical state
+                    if let Some(state) = server.state_message_for(id) {
+                        server.send(id, &state);
+                    }
+                }
+            },
+        }
+    }
+}
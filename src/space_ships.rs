@@ -6,12 +6,13 @@ use bevy::sprite::Anchor;
 use glam::vec2;
 use hexx::Hex;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::space_ships::SpaceShipType::{Battleship, Carrier, Destroyer, Fighter, Frigate};
 use crate::world::player::Player;
 use crate::world::resources::PlayerResources;
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum SpaceShipType {
     Carrier,
     Destroyer,
@@ -34,6 +35,8 @@ impl fmt::Display for SpaceShipType {
     }
 }
 
+pub(crate) const ALL_SPACE_SHIPS: [SpaceShipType; 5] = [Carrier, Destroyer, Frigate, Battleship, Fighter];
+
 pub(crate) fn get_random_spaceship() -> SpaceShipType {
     let n = rand::thread_rng().gen_range(0..5);
     let all_space_ships = vec![Carrier, Destroyer, Frigate, Battleship, Fighter];
@@ -47,6 +50,8 @@ pub(crate) fn get_count_spaceship_dict(spaceship_list: Vec<SpaceShip>) -> HashMa
             (Carrier, 0),
             (Destroyer, 0),
             (Frigate, 0),
+            (Battleship, 0),
+            (Fighter, 0),
         ]);
 
     spaceship_list.iter().for_each(|spaceship| {
@@ -61,24 +66,71 @@ pub(crate) fn get_count_spaceship_dict(spaceship_list: Vec<SpaceShip>) -> HashMa
 pub struct SpaceShipCharacteristics {
     pub id: usize,
     pub power: u32,
-    pub price: PlayerResources
+    pub price: PlayerResources,
+    /// How many hexes a ship of this type can fly in a single move.
+    pub range: u32,
+    /// How many hexes a ship of this type covers per turn while in transit.
+    pub speed: u32,
+    /// How far around its hex a ship of this type reveals the map.
+    pub sensor_range: u32,
+    /// Turns of the owner a shipyard needs to deliver a ship of this type.
+    pub build_time: u32,
 }
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone, Component)]
+/// Duels a ship has to win to reach every rank past the first, each rank adds a point of power.
+const RANK_EXPERIENCE: [u32; 3] = [1, 3, 6];
+pub(crate) const MAX_RANK: u32 = RANK_EXPERIENCE.len() as u32;
+/// Drawn once per rank over the ship.
+pub(crate) const CHEVRON_ICON: &str = "kenney - Simpe Icons/direction_n.png";
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Component, Serialize, Deserialize)]
 pub(crate) struct SpaceShip {
+    /// Stays the same for the whole life of the ship, see `ShipIds`.
+    pub id: u32,
     pub ship_type: SpaceShipType,
     pub ship_owner: Player,
     pub ship_hex: Hex,
     pub is_selected_for_move: bool,
-    pub is_selected_for_buy: bool,
+    /// Duels won so far.
+    pub experience: u32,
 }
 
 impl SpaceShip {
+    pub(crate) fn new(id: u32, ship_type: SpaceShipType, ship_owner: Player, ship_hex: Hex) -> Self {
+        Self { id, ship_type, ship_owner, ship_hex, is_selected_for_move: false, experience: 0 }
+    }
+
+    pub(crate) fn rank(&self) -> u32 {
+        RANK_EXPERIENCE.iter().filter(|experience| self.experience >= **experience).count() as u32
+    }
+
+    pub(crate) fn is_veteran(&self) -> bool {
+        self.rank() > 0
+    }
+
     fn format(&self) -> String {
         format!("{:?}", self.ship_type)
     }
 }
 
+/// Hands out the ids of new ships.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct ShipIds {
+    next: u32,
+}
+
+impl ShipIds {
+    pub(crate) fn next(&mut self) -> u32 {
+        self.next += 1;
+        self.next
+    }
+
+    /// Ships restored from elsewhere (network, undo) keep their ids, new ones have to come after them.
+    pub(crate) fn skip_past(&mut self, id: u32) {
+        self.next = self.next.max(id);
+    }
+}
+
 impl fmt::Debug for SpaceShip {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.format())
@@ -88,11 +140,11 @@ impl fmt::Debug for SpaceShip {
 impl SpaceShipCharacteristics {
     pub fn get_by_spaceship_type(ship_type: SpaceShipType) -> Self {
         match ship_type {
-            Carrier => SpaceShipCharacteristics { id: 6, power: 2, price: PlayerResources { influence: 0, resources: 3 }},
-            Destroyer => SpaceShipCharacteristics { id: 18, power: 6, price: PlayerResources { influence: 0, resources: 8 } },
-            Frigate => SpaceShipCharacteristics { id: 11, power: 9, price: PlayerResources { influence: 0, resources: 12 } },
-            Fighter => SpaceShipCharacteristics { id: 1, power: 8, price: PlayerResources { influence: 0, resources: 5 }},
-            Battleship => SpaceShipCharacteristics { id: 22, power: 5, price: PlayerResources { influence: 0, resources: 5 }},
+            Carrier => SpaceShipCharacteristics { id: 6, power: 2, price: PlayerResources { influence: 0, resources: 3 }, range: 2, speed: 1, sensor_range: 2, build_time: 1 },
+            Destroyer => SpaceShipCharacteristics { id: 18, power: 6, price: PlayerResources { influence: 0, resources: 8 }, range: 3, speed: 2, sensor_range: 1, build_time: 2 },
+            Frigate => SpaceShipCharacteristics { id: 11, power: 9, price: PlayerResources { influence: 0, resources: 12 }, range: 1, speed: 1, sensor_range: 1, build_time: 2 },
+            Fighter => SpaceShipCharacteristics { id: 1, power: 8, price: PlayerResources { influence: 0, resources: 5 }, range: 4, speed: 3, sensor_range: 2, build_time: 1 },
+            Battleship => SpaceShipCharacteristics { id: 22, power: 5, price: PlayerResources { influence: 0, resources: 5 }, range: 1, speed: 1, sensor_range: 1, build_time: 3 },
         }
     }
 }
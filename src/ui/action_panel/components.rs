@@ -9,6 +9,18 @@ pub struct HireArmyButton;
 #[derive(Component)]
 pub struct OpenMovePanelButton;
 
+#[derive(Component)]
+pub struct OpenBuildPanelButton;
+
+#[derive(Component)]
+pub struct FortifyButton;
+
+/// Armed by the first click, the second one gives the game up.
+#[derive(Component, Default)]
+pub struct SurrenderButton {
+    pub armed: bool,
+}
+
 #[derive(Component)]
 pub struct ActionPanel;
 
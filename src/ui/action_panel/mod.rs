@@ -1,4 +1,4 @@
 pub mod plugin;
-mod components;
+pub(crate) mod components;
 mod styles;
 pub mod systems;
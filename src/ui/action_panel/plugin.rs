@@ -17,11 +17,17 @@ impl Plugin for ActionPanelPlugin {
             .add_systems(Update, (
                 interaction::handle_move_button_click,
                 interaction::spawn_menu_button_click,
+                interaction::handle_build_button_click,
+                interaction::handle_fortify_button_click,
                 handle_finish_moves_in_round_button_click,
+                interaction::handle_surrender_button_click,
             ).in_set(UpdateUI::UserInput))
             .add_systems(Update, (
                 interaction::update_spawn_button_disabled,
                 interaction::update_move_button_disabled,
+                interaction::update_build_button_disabled,
+                interaction::update_fortify_button_disabled,
+                interaction::update_action_panel_visibility,
             ).in_set(UpdateUI::RenderStats))
             .add_systems(OnEnter(TurnSwitchedState::OnTurnSwitched), (clear_selected, crate::world::actions::clear_action_state).in_set(FlipTurn),
             )
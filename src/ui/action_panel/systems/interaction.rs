@@ -1,16 +1,29 @@
 use std::fmt::Debug;
 
-use bevy::prelude::{BackgroundColor, Button, Changed, Interaction, NextState, Query, Res, ResMut, State, With};
+use bevy::prelude::{BackgroundColor, Button, Changed, Children, Commands, Entity, EventWriter, Interaction, NextState, Query, Res, ResMut, State, Text, Visibility, With};
 use bevy::utils::HashMap;
 use hexx::Hex;
 
-use crate::ui::action_panel::components::{HireArmyButton, OpenMovePanelButton, SkipRoundButton};
+use crate::network::client::GameClient;
+use crate::ui::action_panel::components::{ActionPanel, FortifyButton, HireArmyButton, OpenBuildPanelButton, OpenMovePanelButton, SkipRoundButton, SurrenderButton};
+use crate::ui::action_panel::systems::layout::{CONFIRM_SURRENDER_TEXT, SURRENDER_TEXT};
 use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::stats::resources::Round;
 use crate::world::actions::ActionsState;
-use crate::world::actions::ActionsState::{MovingSpaceShips, NoActionRunning, SpawningSpaceShips};
+use crate::world::actions::ActionsState::{ConstructingBuildings, MovingSpaceShips, NoActionRunning, SpawningSpaceShips};
+use crate::world::ai::AiPlayers;
 use crate::world::fonts_and_styles::colors::{DISABLED_BUTTON, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
-use crate::world::player::{Movable, Player, Stats};
+use crate::world::defense::{can_fortify, fortify};
+use crate::world::elimination::surrender;
+use crate::world::fleets::Fleet;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::pathfinding::is_hex_reachable;
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+use crate::world::diplomacy::Diplomacy;
+use crate::world::research::Research;
 use crate::world::setup_world_grid::{HexGrid, Planet, SelectedHex};
+use crate::world::supply::Supply;
+use crate::world::turn_actions::TurnAction;
 
 pub fn spawn_menu_button_click(
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>, With<HireArmyButton>)>,
@@ -18,6 +31,7 @@ pub fn spawn_menu_button_click(
     mut mut_current_state: ResMut<NextState<ActionsState>>,
     selected_hex: Res<SelectedHex>,
     grid: Res<HexGrid>,
+    supply: Res<Supply>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
 ) {
     if let Err(_) = interaction_query.get_single() {
@@ -31,7 +45,7 @@ pub fn spawn_menu_button_click(
     }
 
     let current_player = current_player_query.single();
-    if !selected_hex.is_selected || !is_selected_hex_belongs_to_player(current_player, &grid, &selected_hex.hex) {
+    if !selected_hex.is_selected || !is_selected_hex_shipyard_of_player(current_player, &grid, &selected_hex.hex, &supply) {
         return;
     }
 
@@ -55,12 +69,13 @@ pub fn update_spawn_button_disabled(
     mut interaction_query: Query<&mut BackgroundColor, (With<Button>, With<HireArmyButton>, )>,
     selected_hex: Res<SelectedHex>,
     grid: Res<HexGrid>,
+    supply: Res<Supply>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
 ) {
     let mut binding = interaction_query.get_single_mut().unwrap();
     let current_player = current_player_query.single();
     if binding.clone().0 == HOVERED_BUTTON || binding.clone().0 == PRESSED_BUTTON { return; }
-    if !selected_hex.is_selected || !is_selected_hex_belongs_to_player(current_player, &grid, &selected_hex.hex) {
+    if !selected_hex.is_selected || !is_selected_hex_shipyard_of_player(current_player, &grid, &selected_hex.hex, &supply) {
         binding.0 = DISABLED_BUTTON.into();
         return;
     } else {
@@ -73,12 +88,14 @@ pub fn update_move_button_disabled(
     mut interaction_query: Query<&mut BackgroundColor, (With<Button>, With<OpenMovePanelButton>, )>,
     selected_hex: Res<SelectedHex>,
     grid: Res<HexGrid>,
+    research: Res<Research>,
+    diplomacy: Res<Diplomacy>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
 ) {
     let player = current_player_query.single();
     let mut color = interaction_query.get_single_mut().unwrap();
     if color.0 == HOVERED_BUTTON || color.clone().0 == PRESSED_BUTTON { return; }
-    if !selected_hex.is_selected || !(is_selected_hex_belongs_to_player(player, &grid, &selected_hex.hex) || is_selected_hex_has_neighbours(player, &grid, &selected_hex.hex)) {
+    if !selected_hex.is_selected || !(is_selected_hex_belongs_to_player(player, &grid, &selected_hex.hex) || is_selected_hex_reachable(player, &grid, &selected_hex.hex, &research, &diplomacy)) {
         color.0 = DISABLED_BUTTON.into();
         return;
     } else {
@@ -92,6 +109,8 @@ pub fn handle_move_button_click(
     current_state: Res<State<ActionsState>>,
     selected_hex: Res<SelectedHex>,
     grid: Res<HexGrid>,
+    research: Res<Research>,
+    diplomacy: Res<Diplomacy>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
     mut mut_current_state: ResMut<NextState<ActionsState>>) {
     if let Err(_) = interaction_query.get_single() {
@@ -99,7 +118,7 @@ pub fn handle_move_button_click(
     }
 
     let player = current_player_query.single();
-    if !selected_hex.is_selected || !(is_selected_hex_belongs_to_player(player, &grid, &selected_hex.hex) || is_selected_hex_has_neighbours(player, &grid, &selected_hex.hex)) {
+    if !selected_hex.is_selected || !(is_selected_hex_belongs_to_player(player, &grid, &selected_hex.hex) || is_selected_hex_reachable(player, &grid, &selected_hex.hex, &research, &diplomacy)) {
         return;
     }
 
@@ -123,12 +142,126 @@ pub fn handle_move_button_click(
     }
 }
 
+pub fn update_build_button_disabled(
+    mut interaction_query: Query<&mut BackgroundColor, (With<Button>, With<OpenBuildPanelButton>, )>,
+    selected_hex: Res<SelectedHex>,
+    grid: Res<HexGrid>,
+    current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+) {
+    let player = current_player_query.single();
+    let mut color = interaction_query.get_single_mut().unwrap();
+    if color.0 == HOVERED_BUTTON || color.0 == PRESSED_BUTTON { return; }
+    if !selected_hex.is_selected || !is_selected_hex_belongs_to_player(player, &grid, &selected_hex.hex) {
+        color.0 = DISABLED_BUTTON.into();
+    } else {
+        color.0 = NORMAL_BUTTON.into();
+    }
+}
+
+pub fn handle_build_button_click(
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>, With<OpenBuildPanelButton>)>,
+    current_state: Res<State<ActionsState>>,
+    selected_hex: Res<SelectedHex>,
+    grid: Res<HexGrid>,
+    current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+    mut mut_current_state: ResMut<NextState<ActionsState>>) {
+    if let Err(_) = interaction_query.get_single() {
+        return;
+    }
+
+    let player = current_player_query.single();
+    if !selected_hex.is_selected || !is_selected_hex_belongs_to_player(player, &grid, &selected_hex.hex) {
+        return;
+    }
+
+    let (interaction, mut color) = interaction_query.single_mut();
+    if *current_state.get() != NoActionRunning {
+        *color = NORMAL_BUTTON.into();
+        return;
+    }
+
+    match interaction {
+        Interaction::Pressed => {
+            *color = PRESSED_BUTTON.into();
+            mut_current_state.set(ConstructingBuildings);
+        }
+        Interaction::Hovered => {
+            *color = HOVERED_BUTTON.into();
+        }
+        Interaction::None => {
+            *color = NORMAL_BUTTON.into()
+        }
+    }
+}
+
+pub fn update_fortify_button_disabled(
+    mut interaction_query: Query<&mut BackgroundColor, (With<Button>, With<FortifyButton>, )>,
+    selected_hex: Res<SelectedHex>,
+    grid: Res<HexGrid>,
+    current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+) {
+    let player = current_player_query.single();
+    let mut color = interaction_query.get_single_mut().unwrap();
+    if color.0 == HOVERED_BUTTON || color.0 == PRESSED_BUTTON { return; }
+    if !selected_hex.is_selected || !can_fortify(player, &grid, &selected_hex.hex) {
+        color.0 = DISABLED_BUTTON.into();
+    } else {
+        color.0 = NORMAL_BUTTON.into();
+    }
+}
+
+/// Fortifying takes the whole turn.
+pub fn handle_fortify_button_click(
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>, With<FortifyButton>)>,
+    current_state: Res<State<ActionsState>>,
+    selected_hex: Res<SelectedHex>,
+    mut grid: ResMut<HexGrid>,
+    current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+    mut move_done_state: ResMut<NextState<TurnSwitchedState>>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    if let Err(_) = interaction_query.get_single() {
+        return;
+    }
+
+    let player = current_player_query.single();
+    if !selected_hex.is_selected || !can_fortify(player, &grid, &selected_hex.hex) {
+        return;
+    }
+
+    let (interaction, mut color) = interaction_query.single_mut();
+    if *current_state.get() != NoActionRunning {
+        *color = NORMAL_BUTTON.into();
+        return;
+    }
+
+    match interaction {
+        Interaction::Pressed => {
+            *color = PRESSED_BUTTON.into();
+            fortify(player, &mut grid, &selected_hex.hex, &mut game_log, round.number);
+            turn_actions.send(TurnAction::Fortify { hex: selected_hex.hex });
+            move_done_state.set(TurnSwitchedState::OnTurnSwitched);
+        }
+        Interaction::Hovered => {
+            *color = HOVERED_BUTTON.into();
+        }
+        Interaction::None => {
+            *color = NORMAL_BUTTON.into()
+        }
+    }
+}
+
 pub fn handle_finish_moves_in_round_button_click(
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<Button>, With<SkipRoundButton>)>,
-    mut current_player_query: Query<&mut Stats, (With<Player>, With<Movable>)>,
+    mut current_player_query: Query<(&Player, &mut Stats), With<Movable>>,
     mut move_done_state: ResMut<NextState<TurnSwitchedState>>,
     current_state: Res<State<ActionsState>>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut turn_actions: EventWriter<TurnAction>,
 ) {
     if let Err(_) = interaction_query.get_single() {
         return;
@@ -140,11 +273,13 @@ pub fn handle_finish_moves_in_round_button_click(
         return;
     }
 
-    let mut current_stats = current_player_query.single_mut();
+    let (player, mut current_stats) = current_player_query.single_mut();
     match *interaction {
         Interaction::Pressed => {
             *color = PRESSED_BUTTON.into();
             current_stats.moves_left = 1;
+            game_log.record(round.number, *player, GameEvent::RoundSkipped);
+            turn_actions.send(TurnAction::SkipRound);
             move_done_state.set(TurnSwitchedState::OnTurnSwitched)
         }
         Interaction::Hovered => {
@@ -156,18 +291,94 @@ pub fn handle_finish_moves_in_round_button_click(
     }
 }
 
+/// Surrendering hands every planet to the neutrals, the player is eliminated right after.
+pub fn handle_surrender_button_click(
+    mut commands: Commands,
+    mut interaction_query: Query<(&Interaction, &mut BackgroundColor, &mut SurrenderButton, &Children), (Changed<Interaction>, With<Button>)>,
+    mut text_query: Query<&mut Text>,
+    current_state: Res<State<ActionsState>>,
+    current_player_query: Query<&Player, With<Movable>>,
+    fleets_query: Query<(Entity, &Fleet)>,
+    mut grid: ResMut<HexGrid>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    let Ok((interaction, mut color, mut button, children)) = interaction_query.get_single_mut() else { return; };
+    if *current_state.get() != NoActionRunning {
+        *color = NORMAL_BUTTON.into();
+        return;
+    }
+
+    match *interaction {
+        Interaction::Pressed => {
+            *color = PRESSED_BUTTON.into();
+            if button.armed {
+                let Ok(player) = current_player_query.get_single() else { return; };
+                surrender(player, &mut grid, fleets_query.iter(), &mut commands, &mut game_log, round.number);
+                turn_actions.send(TurnAction::Surrender);
+            }
+            button.armed = !button.armed;
+        }
+        Interaction::Hovered => {
+            *color = HOVERED_BUTTON.into();
+        }
+        Interaction::None => {
+            *color = NORMAL_BUTTON.into();
+            button.armed = false;
+        }
+    }
+    let label = if button.armed { CONFIRM_SURRENDER_TEXT } else { SURRENDER_TEXT };
+    for child in children.iter() {
+        if let Ok(mut text) = text_query.get_mut(*child) {
+            text.sections[0].value = label.to_string();
+        }
+    }
+}
+
+/// Spectators and those watching the computer play against itself only watch, so they don't get the panel at all.
+pub fn update_action_panel_visibility(
+    client: Option<Res<GameClient>>,
+    ai_players: Res<AiPlayers>,
+    turn_order: Res<TurnOrder>,
+    mut panel_query: Query<&mut Visibility, With<ActionPanel>>,
+) {
+    let is_watching = client.map_or(false, |client| client.is_spectator()) || ai_players.plays_all(&turn_order.players);
+    let visibility = if is_watching { Visibility::Hidden } else { Visibility::Inherited };
+    for mut panel_visibility in panel_query.iter_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+    }
+}
+
 pub(crate) fn is_selected_hex_belongs_to_player(player: &Player, grid: &HexGrid, selected_hex: &Hex) -> bool {
     grid.planets.get(selected_hex).unwrap().owner == *player
 }
 
+/// Ships can only be bought on own supplied planets with a finished shipyard.
+pub(crate) fn is_selected_hex_shipyard_of_player(player: &Player, grid: &HexGrid, selected_hex: &Hex, supply: &Supply) -> bool {
+    is_selected_hex_belongs_to_player(player, grid, selected_hex) && grid.planets[selected_hex].has_shipyard() && !supply.is_cut_off(selected_hex)
+}
+
 pub(crate) fn is_selected_hex_has_neighbours(player: &Player, grid: &HexGrid, selected_hex: &Hex) -> bool {
     let planets = &grid.planets;
     has_neighbour_in_planets(player, selected_hex, planets)
 }
 
+pub(crate) fn is_selected_hex_reachable(player: &Player, grid: &HexGrid, selected_hex: &Hex, research: &Research, diplomacy: &Diplomacy) -> bool {
+    is_hex_reachable(player, selected_hex, &grid.planets, research, diplomacy)
+}
+
 pub fn has_neighbour_in_planets(player: &Player, selected_hex: &Hex, planets: &HashMap<Hex, Planet>) -> bool {
-    Hex::NEIGHBORS_COORDS.iter().any(|delta| {
-        let neighbour = *delta + *selected_hex;
-        planets.contains_key(&neighbour) && planets.get(&neighbour).unwrap().owner == *player
+    neighbours_in_planets(selected_hex, planets).any(|(_, planet)| planet.owner == *player)
+}
+
+/// Planets right next to `hex`, those outside of the map are skipped.
+pub fn neighbours_in_planets<'a>(hex: &Hex, planets: &'a HashMap<Hex, Planet>) -> impl Iterator<Item=(Hex, &'a Planet)> {
+    let hex = *hex;
+    Hex::NEIGHBORS_COORDS.iter().filter_map(move |delta| {
+        let neighbour = *delta + hex;
+        planets.get(&neighbour).map(|planet| (neighbour, planet))
     })
 }
\ No newline at end of file
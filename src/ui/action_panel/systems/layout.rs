@@ -3,7 +3,7 @@ use bevy::hierarchy::ChildBuilder;
 use bevy::prelude::{BuildChildren, ButtonBundle, Commands, default, NodeBundle, Res, TextBundle};
 use bevy::ui::Interaction;
 
-use crate::ui::action_panel::components::{ActionPanel, HireArmyButton, SkipRoundButton, OpenMovePanelButton};
+use crate::ui::action_panel::components::{ActionPanel, FortifyButton, HireArmyButton, SkipRoundButton, OpenMovePanelButton, OpenBuildPanelButton, SurrenderButton};
 use crate::ui::action_panel::styles::{get_actions_menu_container_style, get_actions_menu_style, get_button_style};
 use crate::world::fonts_and_styles::colors::*;
 use crate::world::fonts_and_styles::fonts::*;
@@ -40,6 +40,38 @@ fn add_open_move_panel_button(parent: &mut ChildBuilder, asset_server: &Res<Asse
         });
 }
 
+fn add_open_build_panel_button(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent
+        .spawn(ButtonBundle {
+            style: get_button_style(),
+            background_color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(OpenBuildPanelButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Build menu",
+                get_button_text_style(asset_server),
+            ));
+        });
+}
+
+fn add_fortify_button(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent
+        .spawn(ButtonBundle {
+            style: get_button_style(),
+            background_color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(FortifyButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Fortify",
+                get_button_text_style(asset_server),
+            ));
+        });
+}
+
 fn add_title(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
     parent.spawn(TextBundle::from_section(
         "Actions",
@@ -63,6 +95,25 @@ fn add_finish_moves_in_round_button(parent: &mut ChildBuilder, asset_server: &Re
         });
 }
 
+pub(in crate::ui::action_panel) const SURRENDER_TEXT: &str = "Surrender";
+pub(in crate::ui::action_panel) const CONFIRM_SURRENDER_TEXT: &str = "Really surrender?";
+
+fn add_surrender_button(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent
+        .spawn(ButtonBundle {
+            style: get_button_style(),
+            background_color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(SurrenderButton::default())
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                SURRENDER_TEXT,
+                get_button_text_style(asset_server),
+            ));
+        });
+}
+
 pub fn setup_buttons(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn((NodeBundle {
@@ -79,7 +130,10 @@ pub fn setup_buttons(mut commands: Commands, asset_server: Res<AssetServer>) {
                 add_title(parent, &asset_server);
                 add_hire_army_button(parent, &asset_server);
                 add_open_move_panel_button(parent, &asset_server);
+                add_open_build_panel_button(parent, &asset_server);
+                add_fortify_button(parent, &asset_server);
                 add_finish_moves_in_round_button(parent, &asset_server);
+                add_surrender_button(parent, &asset_server);
             });
         });
 }
\ No newline at end of file
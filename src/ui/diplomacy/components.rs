@@ -0,0 +1,20 @@
+use bevy::prelude::Component;
+
+use crate::world::diplomacy::Proposal;
+
+#[derive(Component)]
+pub struct DiplomacyScreen;
+
+#[derive(Component)]
+pub struct ProposeButton {
+    pub proposal: Proposal,
+}
+
+#[derive(Component)]
+pub struct AnswerButton {
+    pub proposal: Proposal,
+    pub accept: bool,
+}
+
+#[derive(Component)]
+pub struct CloseDiplomacyButton;
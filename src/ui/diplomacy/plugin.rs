@@ -0,0 +1,35 @@
+use bevy::app::{App, Update};
+use bevy::prelude::{in_state, IntoSystemConfigs, OnEnter, OnExit, Plugin, States};
+
+use crate::game_state::UpdateUI;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::diplomacy::systems::interaction::{close_diplomacy, interact_with_answer_button, interact_with_close_button, interact_with_propose_button, toggle_diplomacy, update_button_colors};
+use crate::ui::diplomacy::systems::layout::{despawn_diplomacy_screen, refresh_diplomacy_screen, spawn_diplomacy_screen};
+use crate::ui::handover::plugin::HandoverState;
+use crate::ui::tech_tree::plugin::TechTreeState;
+
+pub struct DiplomacyScreenPlugin;
+
+impl Plugin for DiplomacyScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<DiplomacyState>()
+            .add_systems(Update, toggle_diplomacy.run_if(in_state(HandoverState::Hidden)).run_if(in_state(TechTreeState::Hidden)).in_set(UpdateUI::UserInput))
+            .add_systems(Update, (
+                interact_with_propose_button,
+                interact_with_answer_button,
+                interact_with_close_button,
+            ).run_if(in_state(DiplomacyState::Shown)).in_set(UpdateUI::UserInput))
+            .add_systems(Update, (refresh_diplomacy_screen, update_button_colors).chain().run_if(in_state(DiplomacyState::Shown)).in_set(UpdateUI::RenderStats))
+            .add_systems(OnEnter(TurnSwitchedState::OnTurnSwitched), close_diplomacy)
+            .add_systems(OnEnter(DiplomacyState::Shown), spawn_diplomacy_screen)
+            .add_systems(OnExit(DiplomacyState::Shown), despawn_diplomacy_screen);
+    }
+}
+
+/// Trades and treaties are proposed and answered on their own screen.
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub(crate) enum DiplomacyState {
+    #[default]
+    Hidden,
+    Shown,
+}
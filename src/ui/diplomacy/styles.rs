@@ -0,0 +1,63 @@
+use bevy::asset::AssetServer;
+use bevy::prelude::{AlignItems, Color, Display, FlexDirection, JustifyContent, PositionType, Res, Style, TextStyle, UiRect, Val};
+
+pub(in crate::ui::diplomacy) const SCREEN_COLOR: Color = Color::rgba(0.02, 0.02, 0.05, 0.95);
+
+pub(in crate::ui::diplomacy) fn get_screen_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Percent(100.),
+        height: Val::Percent(100.),
+        row_gap: Val::Px(12.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::diplomacy) fn get_row_style() -> Style {
+    Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Row,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(8.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::diplomacy) fn get_row_label_style() -> Style {
+    Style {
+        width: Val::Px(420.0),
+        padding: UiRect::all(Val::Px(6.0)),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::diplomacy) fn get_button_style() -> Style {
+    Style {
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Px(160.0),
+        height: Val::Px(50.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::diplomacy) fn get_diplomacy_title_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: Color::rgb(1.0, 1.0, 1.0),
+    }
+}
+
+pub(in crate::ui::diplomacy) fn get_diplomacy_description_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 18.0,
+        color: Color::rgb(0.8, 0.8, 0.8),
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use crate::ui::diplomacy::components::{AnswerButton, CloseDiplomacyButton, ProposeButton};
+use crate::ui::diplomacy::plugin::DiplomacyState;
+use crate::ui::stats::resources::Round;
+use crate::world::actions::ActionsState;
+use crate::world::diplomacy::{answer_proposal, Diplomacy, make_proposal};
+use crate::world::fonts_and_styles::colors::{DISABLED_BUTTON, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
+use crate::world::game_log::GameLog;
+use crate::world::player::{Movable, Player};
+use crate::world::resources::GameResources;
+use crate::world::turn_actions::TurnAction;
+
+pub(in crate::ui::diplomacy) fn toggle_diplomacy(
+    keyboard_input: Res<Input<KeyCode>>,
+    state: Res<State<DiplomacyState>>,
+    actions_state: Res<State<ActionsState>>,
+    mut next_state: ResMut<NextState<DiplomacyState>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::D) { return; }
+    match state.get() {
+        DiplomacyState::Hidden if *actions_state.get() == ActionsState::NoActionRunning => next_state.set(DiplomacyState::Shown),
+        DiplomacyState::Shown => next_state.set(DiplomacyState::Hidden),
+        _ => {}
+    }
+}
+
+pub(in crate::ui::diplomacy) fn close_diplomacy(mut next_state: ResMut<NextState<DiplomacyState>>) {
+    next_state.set(DiplomacyState::Hidden);
+}
+
+pub(in crate::ui::diplomacy) fn interact_with_propose_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &ProposeButton), Changed<Interaction>>,
+    mut diplomacy: ResMut<Diplomacy>,
+    mut resources: ResMut<GameResources>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    for (interaction, mut color, propose_button) in button_query.iter_mut() {
+        let proposal = propose_button.proposal;
+        let player_resources = resources.resources.get(&proposal.from).copied().unwrap_or_default();
+        let can_propose = diplomacy.can_propose(&proposal, &player_resources);
+        match *interaction {
+            Interaction::Pressed => {
+                if !can_propose { continue; }
+                *color = PRESSED_BUTTON.into();
+                if !make_proposal(&mut diplomacy, &mut resources, proposal, &mut game_log, round.number) { continue; }
+                turn_actions.send(TurnAction::Propose { proposal });
+            }
+            Interaction::Hovered if can_propose => {
+                *color = HOVERED_BUTTON.into();
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(in crate::ui::diplomacy) fn interact_with_answer_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &AnswerButton), Changed<Interaction>>,
+    mut diplomacy: ResMut<Diplomacy>,
+    mut resources: ResMut<GameResources>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    for (interaction, mut color, answer_button) in button_query.iter_mut() {
+        let proposal = answer_button.proposal;
+        let recipient_resources = resources.resources.get(&proposal.to).copied().unwrap_or_default();
+        let can_answer = !answer_button.accept || diplomacy.can_accept(&proposal, &recipient_resources);
+        match *interaction {
+            Interaction::Pressed => {
+                if !can_answer { continue; }
+                *color = PRESSED_BUTTON.into();
+                if !answer_proposal(&mut diplomacy, &mut resources, proposal, answer_button.accept, &mut game_log, round.number) { continue; }
+                turn_actions.send(TurnAction::Answer { proposal, accept: answer_button.accept });
+            }
+            Interaction::Hovered if can_answer => {
+                *color = HOVERED_BUTTON.into();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Proposals and trades the current player cannot afford, or that make no sense right now, are greyed out.
+pub(in crate::ui::diplomacy) fn update_button_colors(
+    diplomacy: Res<Diplomacy>,
+    resources: Res<GameResources>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &ProposeButton)>,
+    mut answer_query: Query<(&Interaction, &mut BackgroundColor, &AnswerButton), Without<ProposeButton>>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let player_resources = resources.resources.get(player).copied().unwrap_or_default();
+    for (interaction, mut color, propose_button) in button_query.iter_mut() {
+        if *interaction != Interaction::None { continue; }
+        let new_color: BackgroundColor = if diplomacy.can_propose(&propose_button.proposal, &player_resources) { NORMAL_BUTTON.into() } else { DISABLED_BUTTON.into() };
+        if color.0 != new_color.0 {
+            *color = new_color;
+        }
+    }
+    for (interaction, mut color, answer_button) in answer_query.iter_mut() {
+        if *interaction != Interaction::None { continue; }
+        let can_answer = !answer_button.accept || diplomacy.can_accept(&answer_button.proposal, &player_resources);
+        let new_color: BackgroundColor = if can_answer { NORMAL_BUTTON.into() } else { DISABLED_BUTTON.into() };
+        if color.0 != new_color.0 {
+            *color = new_color;
+        }
+    }
+}
+
+pub(in crate::ui::diplomacy) fn interact_with_close_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CloseDiplomacyButton>)>,
+    mut next_state: ResMut<NextState<DiplomacyState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                next_state.set(DiplomacyState::Hidden);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
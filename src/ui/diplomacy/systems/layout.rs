@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::ui::diplomacy::components::{AnswerButton, CloseDiplomacyButton, DiplomacyScreen, ProposeButton};
+use crate::ui::diplomacy::styles::{get_button_style, get_diplomacy_description_style, get_diplomacy_title_style, get_row_label_style, get_row_style, get_screen_style, SCREEN_COLOR};
+use crate::world::diplomacy::{Diplomacy, GIFT, PACT_ROUNDS, Proposal, ProposalKind, TRADE_INFLUENCE, TRADE_RESOURCES};
+use crate::world::factions::Factions;
+use crate::world::fonts_and_styles::colors::NORMAL_BUTTON;
+use crate::world::fonts_and_styles::fonts::get_button_text_style;
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+
+pub(in crate::ui::diplomacy) fn spawn_diplomacy_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    diplomacy: Res<Diplomacy>,
+    factions: Res<Factions>,
+    turn_order: Res<TurnOrder>,
+    current_player_query: Query<&Player, With<Movable>>,
+    stats_query: Query<(&Player, &Stats)>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let players = turn_order.active(stats_query.iter());
+    build_diplomacy_screen(&mut commands, &asset_server, &diplomacy, &factions, &players, player);
+}
+
+/// Proposals and treaties change while the screen is open, so it is built again.
+pub(in crate::ui::diplomacy) fn refresh_diplomacy_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    diplomacy: Res<Diplomacy>,
+    factions: Res<Factions>,
+    turn_order: Res<TurnOrder>,
+    current_player_query: Query<&Player, With<Movable>>,
+    stats_query: Query<(&Player, &Stats)>,
+    screen_query: Query<Entity, With<DiplomacyScreen>>,
+) {
+    if !diplomacy.is_changed() { return; }
+    let Ok(player) = current_player_query.get_single() else { return; };
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let players = turn_order.active(stats_query.iter());
+    build_diplomacy_screen(&mut commands, &asset_server, &diplomacy, &factions, &players, player);
+}
+
+fn build_diplomacy_screen(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    diplomacy: &Diplomacy,
+    factions: &Factions,
+    players: &[Player],
+    player: &Player,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_screen_style(),
+                background_color: SCREEN_COLOR.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            DiplomacyScreen,
+            Interaction::None,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Diplomacy", get_diplomacy_title_style(asset_server)));
+            for proposal in diplomacy.incoming(player).iter() {
+                let advice = match proposal.kind {
+                    ProposalKind::Trade { .. } if proposal.kind.is_favourable() => " (fair price)",
+                    ProposalKind::Trade { .. } => " (overpriced)",
+                    _ => "",
+                };
+                spawn_row(parent, asset_server, format!("{} offers {}{}", factions.name(&proposal.from), proposal.kind, advice), |parent| {
+                    spawn_button(parent, asset_server, "Accept", AnswerButton { proposal: *proposal, accept: true });
+                    spawn_button(parent, asset_server, "Decline", AnswerButton { proposal: *proposal, accept: false });
+                });
+            }
+            // Eliminated players are out of diplomacy too
+            for other in players.iter().filter(|other| *other != player) {
+                let relation = diplomacy.treaty(player, other).map_or("at war".to_string(), |treaty| treaty.to_string());
+                spawn_row(parent, asset_server, format!("{} - {}", factions.name(other), relation), |parent| {
+                    let mut kinds = vec![
+                        ("Sell resources", ProposalKind::Trade { give: TRADE_RESOURCES, take: TRADE_INFLUENCE }),
+                        ("Sell influence", ProposalKind::Trade { give: TRADE_INFLUENCE, take: TRADE_RESOURCES }),
+                    ];
+                    // Treaties only make sense with someone else left to fight
+                    if players.len() > 2 {
+                        kinds.push(("Pact", ProposalKind::Pact { rounds: PACT_ROUNDS }));
+                        kinds.push(("Alliance", ProposalKind::Alliance));
+                        kinds.push(("Gift", ProposalKind::Gift { gift: GIFT }));
+                    }
+                    for (label, kind) in kinds {
+                        spawn_button(parent, asset_server, label, ProposeButton { proposal: Proposal { from: *player, to: *other, kind } });
+                    }
+                });
+            }
+            spawn_button(parent, asset_server, "Close", CloseDiplomacyButton);
+        });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, label: String, spawn_buttons: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(NodeBundle { style: get_row_style(), ..default() })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle { style: get_row_label_style(), ..default() })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(label, get_diplomacy_description_style(asset_server)));
+                });
+            spawn_buttons(parent);
+        });
+}
+
+fn spawn_button(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: get_button_style(),
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(label, get_button_text_style(asset_server)));
+        });
+}
+
+pub(in crate::ui::diplomacy) fn despawn_diplomacy_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<DiplomacyScreen>>,
+) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct GalacticEventScreen;
+
+#[derive(Component)]
+pub struct CloseGalacticEventButton;
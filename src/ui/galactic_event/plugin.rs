@@ -0,0 +1,25 @@
+use bevy::app::{App, Update};
+use bevy::prelude::{in_state, IntoSystemConfigs, OnEnter, OnExit, Plugin, States};
+
+use crate::game_state::UpdateUI;
+use crate::ui::galactic_event::systems::interaction::interact_with_close_button;
+use crate::ui::galactic_event::systems::layout::{despawn_galactic_event_screen, spawn_galactic_event_screen};
+
+pub struct GalacticEventPlugin;
+
+impl Plugin for GalacticEventPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<GalacticEventState>()
+            .add_systems(OnEnter(GalacticEventState::Shown), spawn_galactic_event_screen)
+            .add_systems(Update, interact_with_close_button.run_if(in_state(GalacticEventState::Shown)).in_set(UpdateUI::UserInput))
+            .add_systems(OnExit(GalacticEventState::Shown), despawn_galactic_event_screen);
+    }
+}
+
+/// Announces the event drawn at the start of the round, on top of the handover screen.
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub(crate) enum GalacticEventState {
+    #[default]
+    Hidden,
+    Shown,
+}
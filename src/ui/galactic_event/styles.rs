@@ -0,0 +1,44 @@
+use bevy::asset::AssetServer;
+use bevy::prelude::{AlignItems, Color, Display, FlexDirection, JustifyContent, PositionType, Res, Style, TextStyle, Val};
+
+pub(in crate::ui::galactic_event) const SCREEN_COLOR: Color = Color::rgba(0.02, 0.02, 0.05, 0.9);
+
+pub(in crate::ui::galactic_event) fn get_screen_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Percent(100.),
+        height: Val::Percent(100.),
+        row_gap: Val::Px(24.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::galactic_event) fn get_button_style() -> Style {
+    Style {
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Px(200.0),
+        height: Val::Px(60.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::galactic_event) fn get_event_title_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 48.0,
+        color: Color::rgb(1.0, 0.85, 0.4),
+    }
+}
+
+pub(in crate::ui::galactic_event) fn get_event_description_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 24.0,
+        color: Color::rgb(0.8, 0.8, 0.8),
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::ui::galactic_event::components::CloseGalacticEventButton;
+use crate::ui::galactic_event::plugin::GalacticEventState;
+use crate::world::fonts_and_styles::colors::{HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
+
+pub(in crate::ui::galactic_event) fn interact_with_close_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CloseGalacticEventButton>)>,
+    mut galactic_event_state: ResMut<NextState<GalacticEventState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                galactic_event_state.set(GalacticEventState::Hidden);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
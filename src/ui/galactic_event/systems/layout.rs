@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::ui::galactic_event::components::{CloseGalacticEventButton, GalacticEventScreen};
+use crate::ui::galactic_event::styles::{get_button_style, get_event_description_style, get_event_title_style, get_screen_style, SCREEN_COLOR};
+use crate::world::fonts_and_styles::colors::NORMAL_BUTTON;
+use crate::world::fonts_and_styles::fonts::get_button_text_style;
+use crate::world::galactic_events::GalacticEvents;
+
+pub(in crate::ui::galactic_event) fn spawn_galactic_event_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    galactic_events: Res<GalacticEvents>,
+) {
+    let (title, description) = galactic_events.announcement.clone().unwrap_or_default();
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_screen_style(),
+                background_color: SCREEN_COLOR.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            GalacticEventScreen,
+            Interaction::None,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(title, get_event_title_style(&asset_server)));
+            parent.spawn(
+                TextBundle::from_section(description, get_event_description_style(&asset_server))
+                    .with_text_alignment(TextAlignment::Center),
+            );
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: get_button_style(),
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    CloseGalacticEventButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Close",
+                        get_button_text_style(&asset_server),
+                    ));
+                });
+        });
+}
+
+pub(in crate::ui::galactic_event) fn despawn_galactic_event_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<GalacticEventScreen>>,
+    mut galactic_events: ResMut<GalacticEvents>,
+) {
+    galactic_events.announcement = None;
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
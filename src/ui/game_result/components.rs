@@ -4,4 +4,10 @@ use bevy::prelude::Component;
 pub struct ResultPanel;
 
 #[derive(Component)]
-pub struct WinnerText;
\ No newline at end of file
+pub struct WinnerText;
+
+#[derive(Component)]
+pub struct ConditionText;
+
+#[derive(Component)]
+pub struct CampaignText;
@@ -1,18 +1,32 @@
-use bevy::prelude::{Query, Text, Visibility, With};
-use crate::ui::game_result::components::{ResultPanel, WinnerText};
-use crate::world::player::{Player, Stats};
-use crate::ui::stats::systems::interaction::MAX_WIN_POINTS;
+use bevy::prelude::{Query, Res, Text, Visibility, With, Without};
+use crate::ui::game_result::components::{CampaignText, ConditionText, ResultPanel, WinnerText};
+use crate::world::campaign::CampaignRun;
+use crate::world::factions::Factions;
+use crate::world::victory::GameOutcome;
 
 pub(in crate::ui::game_result) fn update_game_result(
     mut panel_query: Query<&mut Visibility, With<ResultPanel>>,
-    mut winner_text_query: Query<&mut Text, With<WinnerText>>,
-    player_query: Query<(&Player, &Stats)>,
+    mut winner_text_query: Query<&mut Text, (With<WinnerText>, Without<ConditionText>, Without<CampaignText>)>,
+    mut condition_text_query: Query<&mut Text, (With<ConditionText>, Without<CampaignText>)>,
+    mut campaign_text_query: Query<&mut Text, With<CampaignText>>,
+    outcome: Option<Res<GameOutcome>>,
+    campaign: Option<Res<CampaignRun>>,
+    factions: Res<Factions>,
 ) {
-    for (player, stats) in player_query.iter() {
-        if (stats.win_points == MAX_WIN_POINTS) {
-            let mut text = winner_text_query.single_mut();
-            text.sections[1].value = player.to_string();
-            *panel_query.single_mut() = Visibility::Visible;
+    let Some(outcome) = outcome else { return; };
+    let mut text = winner_text_query.single_mut();
+    text.sections[1].value = match outcome.winner {
+        Some(player) if outcome.allies.is_empty() => factions.name(&player),
+        Some(player) => {
+            let allies: Vec<String> = outcome.allies.iter().map(|ally| factions.name(ally)).collect();
+            format!("{} together with {}", factions.name(&player), allies.join(", "))
         }
+        None => "nobody, it's a draw".to_string(),
+    };
+    let mut text = condition_text_query.single_mut();
+    text.sections[1].value = outcome.condition.to_string();
+    if let Some(campaign) = campaign {
+        campaign_text_query.single_mut().sections[0].value = campaign.summary(&outcome);
     }
-}
\ No newline at end of file
+    *panel_query.single_mut() = Visibility::Visible;
+}
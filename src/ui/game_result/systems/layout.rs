@@ -4,7 +4,7 @@ use bevy::prelude::{ChildBuilder, Commands, NodeBundle, Res, TextBundle};
 use bevy::prelude::Visibility::Hidden;
 use bevy::text::TextSection;
 use bevy::utils::default;
-use crate::ui::game_result::components::{ResultPanel, WinnerText};
+use crate::ui::game_result::components::{CampaignText, ConditionText, ResultPanel, WinnerText};
 use crate::ui::game_result::styles::{get_panel_style, get_result_text_style};
 
 
@@ -31,6 +31,30 @@ fn setup_winner_text(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>)
         .insert(WinnerText);
 }
 
+fn setup_condition_text(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent.spawn(TextBundle::from_sections([
+        TextSection {
+            value: "By: ".to_string(),
+            style: get_result_text_style(asset_server),
+        },
+        TextSection {
+            value: "None".to_string(),
+            style: get_result_text_style(asset_server),
+        }
+    ]
+    ))
+        .insert(ConditionText);
+}
+
+/// Stays empty outside of campaigns.
+fn setup_campaign_text(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent.spawn(TextBundle::from_section(
+        String::new(),
+        get_result_text_style(asset_server),
+    ))
+        .insert(CampaignText);
+}
+
 pub(in crate::ui::game_result) fn setup_result_panel(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -43,5 +67,7 @@ pub(in crate::ui::game_result) fn setup_result_panel(
         .with_children(|parent| {
             setup_game_over_text(parent, &asset_server);
             setup_winner_text(parent, &asset_server);
+            setup_condition_text(parent, &asset_server);
+            setup_campaign_text(parent, &asset_server);
         });
 }
\ No newline at end of file
@@ -0,0 +1,13 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct HandoverScreen;
+
+#[derive(Component)]
+pub struct HandoverTitleText;
+
+#[derive(Component)]
+pub struct HandoverSummaryText;
+
+#[derive(Component)]
+pub struct ContinueButton;
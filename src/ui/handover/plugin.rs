@@ -0,0 +1,27 @@
+use bevy::app::{App, Update};
+use bevy::prelude::{in_state, IntoSystemConfigs, OnEnter, OnExit, Plugin, States};
+
+use crate::game_state::UpdateUI;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::handover::systems::interaction::{interact_with_continue_button, show_handover_screen};
+use crate::ui::handover::systems::layout::{despawn_handover_screen, spawn_handover_screen};
+
+pub struct HandoverPlugin;
+
+impl Plugin for HandoverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<HandoverState>()
+            .add_systems(OnEnter(TurnSwitchedState::OnDefaultState), show_handover_screen)
+            .add_systems(OnEnter(HandoverState::Shown), spawn_handover_screen)
+            .add_systems(Update, interact_with_continue_button.run_if(in_state(HandoverState::Shown)).in_set(UpdateUI::UserInput))
+            .add_systems(OnExit(HandoverState::Shown), despawn_handover_screen);
+    }
+}
+
+/// Hides the board between hotseat turns, so the next player doesn't see the previous one's view.
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub(crate) enum HandoverState {
+    #[default]
+    Hidden,
+    Shown,
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::network::client::GameClient;
+use crate::ui::handover::components::ContinueButton;
+use crate::ui::handover::plugin::HandoverState;
+use crate::world::fonts_and_styles::colors::{HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
+use crate::world::ai::AiPlayers;
+use crate::world::player::{Movable, Player};
+use crate::world::tutorial::Tutorial;
+
+pub(in crate::ui::handover) fn show_handover_screen(
+    mut handover_state: ResMut<NextState<HandoverState>>,
+    client: Option<Res<GameClient>>,
+    tutorial: Option<Res<Tutorial>>,
+    ai_players: Res<AiPlayers>,
+    current_player_query: Query<&Player, With<Movable>>,
+) {
+    // Networked players sit at their own screens, the tutorial is played alone
+    if client.is_some() || tutorial.is_some() { return; }
+    // Nothing to hide from the computer
+    if current_player_query.get_single().map_or(false, |player| ai_players.contains(player)) { return; }
+    handover_state.set(HandoverState::Shown);
+}
+
+pub(in crate::ui::handover) fn interact_with_continue_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<ContinueButton>)>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut handover_state: ResMut<NextState<HandoverState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        handover_state.set(HandoverState::Hidden);
+        return;
+    }
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                handover_state.set(HandoverState::Hidden);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::ui::handover::components::{ContinueButton, HandoverScreen, HandoverSummaryText, HandoverTitleText};
+use crate::ui::handover::styles::{get_button_style, get_handover_summary_style, get_handover_title_style, get_screen_style, SCREEN_COLOR};
+use crate::world::factions::Factions;
+use crate::world::fonts_and_styles::colors::NORMAL_BUTTON;
+use crate::world::fonts_and_styles::fonts::get_button_text_style;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Movable, Player};
+
+const MAX_SUMMARY_LINES: usize = 12;
+const UNDO_HINT: &str = "<Ctrl> + <Z> takes the last turn back before the board is shown";
+
+/// What `player` saw the opponents do since they last looked at the board,
+/// plus the battles their own fleets fought when the turn began.
+fn get_summary_text(player: &Player, game_log: &GameLog) -> String {
+    let lines: Vec<String> = game_log.unseen_by(player).iter()
+        .filter(|entry| entry.player != *player || matches!(entry.event, GameEvent::Battle { .. } | GameEvent::PlanetCaptured { .. }))
+        .filter(|entry| entry.is_witnessed_by(player))
+        .map(|entry| entry.to_string())
+        .collect();
+    if lines.is_empty() {
+        return "Nothing happened since your last turn".to_string();
+    }
+    let skipped = lines.len().saturating_sub(MAX_SUMMARY_LINES);
+    let mut text = String::from("Since your last turn:\n");
+    if skipped > 0 {
+        text.push_str(&format!("... and {} earlier events\n", skipped));
+    }
+    text.push_str(&lines[skipped..].join("\n"));
+    text
+}
+
+pub(in crate::ui::handover) fn spawn_handover_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    game_log: Res<GameLog>,
+    factions: Res<Factions>,
+) {
+    let (title, summary) = match current_player_query.get_single() {
+        Ok(player) => (format!("{}, press to continue", factions.name(player)), get_summary_text(player, &game_log)),
+        Err(_) => ("Press to continue".to_string(), String::new()),
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_screen_style(),
+                background_color: SCREEN_COLOR.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            HandoverScreen,
+            Interaction::None,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(title, get_handover_title_style(&asset_server)),
+                HandoverTitleText,
+            ));
+            parent.spawn((
+                TextBundle::from_section(summary, get_handover_summary_style(&asset_server))
+                    .with_text_alignment(TextAlignment::Center),
+                HandoverSummaryText,
+            ));
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: get_button_style(),
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    ContinueButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Continue",
+                        get_button_text_style(&asset_server),
+                    ));
+                });
+            parent.spawn(TextBundle::from_section(UNDO_HINT, get_handover_summary_style(&asset_server)));
+        });
+}
+
+pub(in crate::ui::handover) fn despawn_handover_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<HandoverScreen>>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut game_log: ResMut<GameLog>,
+) {
+    if let Ok(player) = current_player_query.get_single() {
+        game_log.mark_seen(player);
+    }
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,14 +1,19 @@
 pub(in crate::ui::hud) const NO_ACTION_RUNNING_UNSELECTED_HEX_HUD_TEXT: &str = "Select hex from grid";
 pub(in crate::ui::hud) const NO_ACTION_RUNNING_SELECTED_HEX_HUD_TEXT: &str = "Choose action from left menu";
-pub(in crate::ui::hud) const SPAWN_MENU_HUD_TEXT: &str = "Click on buttons with ships which you want to buy";
-pub(in crate::ui::hud) const MOVE_MENU_UNSELECTED_HEX_HUD_TEXT: &str = "Select the planet from which you want to move ships";
+/// Shown during the first round, followed by the ids of the factions.
+pub(in crate::ui::hud) const FACTIONS_HUD_TEXT: &str = "Factions are picked when starting the game, e.g. --factions";
+pub(in crate::ui::hud) const SPAWN_MENU_HUD_TEXT: &str = "Queue ships at the shipyard, they are delivered after their build time";
+pub(in crate::ui::hud) const BUILD_MENU_HUD_TEXT: &str = "Click on the building you want to construct on the planet";
+pub(in crate::ui::hud) const MOVE_MENU_UNSELECTED_HEX_HUD_TEXT: &str = "Select the planet from which you want to move ships (it has to be within ships range)";
 pub(in crate::ui::hud) const MOVE_MENU_SELECTED_HEX_HUD_TEXT: &str = "Select which type of ship you want to move from right menu";
 
 
-pub(in crate::ui::hud) const NO_ACTION_RUNNING_UNSELECTED_HEX_SHORTCUT_TEXT: &str = "";
-pub(in crate::ui::hud) const NO_ACTION_RUNNING_SELECTED_HEX_SHORTCUT_TEXT: &str = "<S> to start spawn action\n<M> to start move action";
+pub(in crate::ui::hud) const NO_ACTION_RUNNING_UNSELECTED_HEX_SHORTCUT_TEXT: &str = "<T> to open research\n<D> to trade and open diplomacy\n<Ctrl> + <Shift> + <Z> to redo a turn taken back";
+pub(in crate::ui::hud) const NO_ACTION_RUNNING_SELECTED_HEX_SHORTCUT_TEXT: &str = "<S> to start spawn action\n<M> to start move action\n<B> to start build action\n<F> to fortify the planet\n<T> to open research\n<D> to trade and open diplomacy\n<Ctrl> + <Shift> + <Z> to redo a turn taken back";
 
-pub(in crate::ui::hud) const SPAWN_MENU_SHORTCUT_TEXT: &str = "<Space> + <Click> to spawn ships using all remaining resources\n<C> to cancel spawn";
+pub(in crate::ui::hud) const SPAWN_MENU_SHORTCUT_TEXT: &str = "<Space> + <Click> to queue ships using all remaining resources\n<C> to cancel changes to the queue";
+
+pub(in crate::ui::hud) const BUILD_MENU_SHORTCUT_TEXT: &str = "<C> to cancel build";
 
 pub(in crate::ui::hud) const MOVE_MENU_UNSELECTED_HEX_SHORTCUT_TEXT: &str = "<C> to cancel move";
 pub(in crate::ui::hud) const MOVE_MENU_SELECTED_HEX_SHORTCUT_TEXT: &str = "<Space> + <Click> to move all ships of some type\n<C> to cancel move";
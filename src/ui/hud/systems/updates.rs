@@ -2,50 +2,74 @@ use bevy::prelude::*;
 use bevy::reflect::List;
 
 use crate::ui::hud::components::{HudShortcutTipsText, HudTipsText, ScoreText};
-use crate::ui::hud::resources::{MOVE_MENU_SELECTED_HEX_HUD_TEXT, MOVE_MENU_SELECTED_HEX_SHORTCUT_TEXT, MOVE_MENU_UNSELECTED_HEX_HUD_TEXT, MOVE_MENU_UNSELECTED_HEX_SHORTCUT_TEXT, NO_ACTION_RUNNING_SELECTED_HEX_HUD_TEXT, NO_ACTION_RUNNING_SELECTED_HEX_SHORTCUT_TEXT, NO_ACTION_RUNNING_UNSELECTED_HEX_HUD_TEXT, NO_ACTION_RUNNING_UNSELECTED_HEX_SHORTCUT_TEXT, SPAWN_MENU_HUD_TEXT, SPAWN_MENU_SHORTCUT_TEXT};
+use crate::ui::hud::resources::{BUILD_MENU_HUD_TEXT, FACTIONS_HUD_TEXT, BUILD_MENU_SHORTCUT_TEXT, MOVE_MENU_SELECTED_HEX_HUD_TEXT, MOVE_MENU_SELECTED_HEX_SHORTCUT_TEXT, MOVE_MENU_UNSELECTED_HEX_HUD_TEXT, MOVE_MENU_UNSELECTED_HEX_SHORTCUT_TEXT, NO_ACTION_RUNNING_SELECTED_HEX_HUD_TEXT, NO_ACTION_RUNNING_SELECTED_HEX_SHORTCUT_TEXT, NO_ACTION_RUNNING_UNSELECTED_HEX_HUD_TEXT, NO_ACTION_RUNNING_UNSELECTED_HEX_SHORTCUT_TEXT, SPAWN_MENU_HUD_TEXT, SPAWN_MENU_SHORTCUT_TEXT};
+use crate::ui::stats::resources::Round;
 use crate::world::actions::ActionsState;
+use crate::world::factions::Factions;
+use crate::world::objectives::Objectives;
 use crate::world::player::{Player, Stats};
 use crate::world::resources::GameResources;
+use crate::world::scenario::Scenario;
 use crate::world::setup_world_grid::SelectedHex;
 
 pub fn update_score_text(
     mut text_query: Query<&mut Text, With<ScoreText>>,
     game_resources: Res<GameResources>,
+    objectives: Res<Objectives>,
+    factions: Res<Factions>,
     player_query: Query<(&Player, &Stats)>,
 ) {
-    if game_resources.is_changed() {
+    if game_resources.is_changed() || objectives.is_changed() {
         let res = game_resources.clone();
-        let resource_text = res.to_string();
+        let resource_text = res.to_string(&factions);
         for mut text in text_query.iter_mut() {
-            let winning_points_text = get_winning_points_str(&player_query);
+            let winning_points_text = get_winning_points_str(&player_query, &objectives, &factions);
             text.sections[1].value = format!("{}", resource_text);
             text.sections[3].value = winning_points_text.clone()
         }
     }
 }
 
-fn get_winning_points_str(player_query: &Query<(&Player, &Stats)>) -> String {
+fn get_winning_points_str(player_query: &Query<(&Player, &Stats)>, objectives: &Objectives, factions: &Factions) -> String {
     let mut winning_points_text = String::new();
-    let mut vec: Vec<(i32, i32)> = player_query.iter().map(|(player, stats)| {
-        (player.id, stats.win_points)
-    }).collect::<Vec<(i32, i32)>>();
-    vec.sort();
-    for (id, win_points) in vec {
-        winning_points_text.push_str(format!("Player {} -> {}\n", id, win_points).as_str())
+    let mut vec: Vec<(Player, i32)> = player_query.iter().map(|(player, stats)| {
+        (*player, stats.win_points)
+    }).collect::<Vec<(Player, i32)>>();
+    vec.sort_by_key(|(player, _)| player.id);
+    for (player, win_points) in vec {
+        winning_points_text.push_str(format!("{} -> {}\n", factions.name(&player), win_points).as_str());
+        for objective in objectives.list.iter() {
+            winning_points_text.push_str(format!(
+                "  ({}, {}) +{}/round: {}\n",
+                objective.hex.x, objective.hex.y, objective.points, objectives.earned_by(&player, &objective.hex)
+            ).as_str())
+        }
     }
     return winning_points_text;
 }
 
+/// Until the first round is over, players learn how to lead another faction next time.
+fn get_factions_str(factions: &Factions) -> String {
+    let ids: Vec<&str> = factions.list.iter().map(|faction| faction.id.as_str()).collect();
+    format!("{} {}", FACTIONS_HUD_TEXT, ids.join(","))
+}
+
 pub fn update_hud_text(
     mut text_query: Query<&mut Text, With<HudTipsText>>,
     selected_hex: Res<SelectedHex>,
     state: Res<State<ActionsState>>,
+    round: Res<Round>,
+    factions: Res<Factions>,
+    scenario: Option<Res<Scenario>>,
 ) {
     let mut text = text_query.single_mut();
     match state.get() {
         ActionsState::NoActionRunning => {
             text.sections[0].value = if selected_hex.is_selected {
                 NO_ACTION_RUNNING_SELECTED_HEX_HUD_TEXT.to_string()
+            } else if round.number == 1 && scenario.is_none() {
+                // Scenarios decide the factions themselves
+                format!("{}\n{}", NO_ACTION_RUNNING_UNSELECTED_HEX_HUD_TEXT, get_factions_str(&factions))
             } else {
                 NO_ACTION_RUNNING_UNSELECTED_HEX_HUD_TEXT.to_string()
             }
@@ -54,6 +78,10 @@ pub fn update_hud_text(
             debug_assert!(selected_hex.is_selected);
             text.sections[0].value = SPAWN_MENU_HUD_TEXT.to_string()
         }
+        ActionsState::ConstructingBuildings => {
+            debug_assert!(selected_hex.is_selected);
+            text.sections[0].value = BUILD_MENU_HUD_TEXT.to_string()
+        }
         ActionsState::MovingSpaceShips => {
             debug_assert!(selected_hex.is_selected);
             text.sections[0].value = if selected_hex.is_selected_for_move {
@@ -81,6 +109,10 @@ pub fn update_shortcuts_hud_text(
             debug_assert!(selected_hex.is_selected);
             text.sections[0].value = SPAWN_MENU_SHORTCUT_TEXT.to_string()
         }
+        ActionsState::ConstructingBuildings => {
+            debug_assert!(selected_hex.is_selected);
+            text.sections[0].value = BUILD_MENU_SHORTCUT_TEXT.to_string()
+        }
         ActionsState::MovingSpaceShips => {
             debug_assert!(selected_hex.is_selected);
             text.sections[0].value = if selected_hex.is_selected_for_move {
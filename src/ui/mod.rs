@@ -3,4 +3,9 @@ pub(crate) mod hud;
 pub(crate) mod stats;
 
 pub(crate) mod action_panel;
+pub(crate) mod diplomacy;
+pub(crate) mod galactic_event;
 pub(crate) mod game_result;
+pub(crate) mod handover;
+pub(crate) mod tech_tree;
+pub(crate) mod tutorial;
@@ -8,3 +8,6 @@ pub struct MovesLeftText;
 
 #[derive(Component)]
 pub struct RoundText;
+
+#[derive(Component)]
+pub struct ClockText;
@@ -1,8 +1,9 @@
 use bevy::app::{Plugin, Startup, Update};
 use bevy::prelude::IntoSystemConfigs;
 use crate::game_state::UpdateUI;
+use crate::network::client::is_local_game;
 use crate::ui::stats::resources::Round;
-use crate::ui::stats::systems::interaction::{update_round_number_text, update_turn_text, update_win_points_number};
+use crate::ui::stats::systems::interaction::{sync_round_number_text, update_clock_text, update_round_number_text, update_turn_text, update_win_points_number};
 use crate::ui::stats::systems::layout::setup_stats;
 
 pub struct StatsPlugin;
@@ -13,12 +14,14 @@ impl Plugin for StatsPlugin {
             .add_systems(Startup, setup_stats)
             .add_systems(Update,
             (
-                update_round_number_text,
+                update_round_number_text.run_if(is_local_game),
                 update_turn_text,
+                sync_round_number_text,
+                update_clock_text,
             )
                 .in_set(UpdateUI::RenderStats),
         )
             .add_systems(Update,
-                         (update_win_points_number, update_round_number_text).chain().in_set(UpdateUI::NewRound));
+                         (update_win_points_number, update_round_number_text).chain().run_if(is_local_game).in_set(UpdateUI::NewRound));
     }
 }
\ No newline at end of file
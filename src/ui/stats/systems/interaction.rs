@@ -1,59 +1,62 @@
 use bevy::log::info;
 use bevy::prelude::{Commands, Entity, NextState, Query, Res, ResMut, State, Text, With};
-use hexx::Hex;
 
 use crate::game_state::{AppState, GamePhaseState};
 use crate::ui::action_panel::plugin::TurnSwitchedState;
-use crate::ui::stats::components::{MovesLeftText, RoundText, TurnText};
+use crate::ui::stats::components::{ClockText, MovesLeftText, RoundText, TurnText};
 use crate::ui::stats::resources::Round;
-use crate::world::player::{INITIAL_MOVES, Movable, Player, Stats};
+use crate::world::diplomacy::Diplomacy;
+use crate::world::factions::Factions;
+use crate::world::player::{Movable, Player, start_round, Stats, TurnOrder};
 use crate::world::resources::GameResources;
-use crate::world::setup_world_grid::{HexGrid, Planet};
+use crate::world::fleets::Fleet;
+use crate::world::objectives::Objectives;
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::turn_timer::{format_seconds, TimeControl, TurnClock};
+use crate::world::victory::{end_round, GameOutcome, VictoryConditions};
 
-static CENTRAL_HEX: Hex = Hex { x: 0, y: 0 };
-pub(crate) const MAX_WIN_POINTS: i32 = 5;
-
-pub(in crate::ui::stats) fn reset_player(commands: &mut Commands, id: Entity, stats: &mut Stats) {
-    stats.moves_left = INITIAL_MOVES;
-    commands.entity(id).remove::<Movable>();
-}
 
 pub(in crate::ui::stats) fn set_round_number_text(text: &mut Text, value: i32) {
     text.sections[0].value = format!("Round: {}", value.to_string());
 }
 
 
-pub(in crate::ui::stats) fn set_player_turn_text(text: &mut Text, value: i32) {
-    text.sections[0].value = format!("Player: {}", value.to_string());
+pub(in crate::ui::stats) fn set_player_turn_text(text: &mut Text, factions: &Factions, player: &Player) {
+    text.sections[0].value = format!("Turn: {}", factions.name(player));
+    text.sections[0].style.color = factions.color(player);
 }
 
 
 pub(in crate::ui::stats) fn update_turn_text(
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+    factions: Res<Factions>,
     mut turn_text_query: Query<&mut Text, With<TurnText>>,
 ) {
-    let player = current_player_query.single();
+    let Ok(player) = current_player_query.get_single() else { return; };
     let mut turn_text = turn_text_query.single_mut();
-    set_player_turn_text(&mut turn_text, player.id);
+    set_player_turn_text(&mut turn_text, &factions, player);
 }
 
 pub fn update_win_points_number(
+    mut commands: Commands,
     grid: Res<HexGrid>,
+    round: Res<Round>,
+    victory_conditions: Res<VictoryConditions>,
+    mut objectives: ResMut<Objectives>,
+    game_resources: Res<GameResources>,
+    outcome: Option<Res<GameOutcome>>,
+    fleets_query: Query<&Fleet>,
+    diplomacy: Res<Diplomacy>,
     mut players: Query<(&Player, &mut Stats)>,
     mut game_phase: ResMut<NextState<AppState>>,
 ) {
+    if outcome.is_some() { return; }
     if players.iter().all(|(_, stats)| stats.moves_left == 0) {
-        let planet: &Planet = grid.planets.get(&CENTRAL_HEX).unwrap();
-        players.iter_mut().for_each(|(player, mut stats)| {
-            info!("Before Player: {:?} win_points: {:?}", player.id, stats.win_points);
-            if planet.owner.id == player.id {
-                stats.win_points += 1;
-                if (stats.win_points == MAX_WIN_POINTS) {
-                    game_phase.set(AppState::GameOver)
-                }
-            }
-            info!("After Player: {:?} win_points: {:?}", player.id, stats.win_points);
-        })
+        if let Some(outcome) = end_round(players.iter_mut(), &mut objectives, &victory_conditions, round.number, &grid, &game_resources, fleets_query.iter(), &diplomacy) {
+            info!("Game over: {:?}", outcome);
+            commands.insert_resource(outcome);
+            game_phase.set(AppState::GameOver);
+        }
     }
 }
 
@@ -62,6 +65,7 @@ pub fn update_round_number_text(
     mut round_text_query: Query<&mut Text, With<RoundText>>,
     mut players: Query<(Entity, &Player, &mut Stats)>,
     mut round_res: ResMut<Round>,
+    turn_order: Res<TurnOrder>,
     turn_switch_state: Res<State<TurnSwitchedState>>,
     mut turn_switch_state_mutable: ResMut<NextState<TurnSwitchedState>>,
 ) {
@@ -71,9 +75,10 @@ pub fn update_round_number_text(
             let round = round_res.as_mut();
             round.number += 1;
             set_round_number_text(&mut round_text, round.number);
-            players.iter_mut().for_each(|(entity, player, mut stats)| {
-                reset_player(&mut commands, entity, &mut stats);
-                if player.id == 1 {
+            let first = start_round(&turn_order, players.iter_mut().map(|(_, player, stats)| (player, stats)));
+            players.iter().for_each(|(entity, player, _)| {
+                commands.entity(entity).remove::<Movable>();
+                if Some(*player) == first {
                     commands.entity(entity).insert(Movable);
                 }
             });
@@ -83,3 +88,31 @@ pub fn update_round_number_text(
         }
     }
 }
+
+/// Keeps the round label in sync when the round is restored from elsewhere (network, saves).
+pub(in crate::ui::stats) fn sync_round_number_text(
+    round_res: Res<Round>,
+    mut round_text_query: Query<&mut Text, With<RoundText>>,
+) {
+    if !round_res.is_changed() { return; }
+    if let Ok(mut round_text) = round_text_query.get_single_mut() {
+        set_round_number_text(&mut round_text, round_res.number);
+    }
+}
+
+/// Time left on the turn, followed by the bank of the current player when playing with a chess clock.
+pub(in crate::ui::stats) fn update_clock_text(
+    clock: Res<TurnClock>,
+    time_control: Res<TimeControl>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut clock_text_query: Query<&mut Text, With<ClockText>>,
+) {
+    let Ok(mut clock_text) = clock_text_query.get_single_mut() else { return; };
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let Some(time_left) = clock.time_left(player, &time_control) else { return; };
+    let mut value = format!("Time: {}", format_seconds(time_left));
+    if let Some(bank) = clock.banks.get(player) {
+        value.push_str(&format!(" (bank {})", format_seconds(*bank)));
+    }
+    clock_text.sections[0].value = value;
+}
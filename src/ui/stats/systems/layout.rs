@@ -2,10 +2,13 @@ use bevy::asset::AssetServer;
 use bevy::hierarchy::{BuildChildren, ChildBuilder};
 use bevy::prelude::{Commands, Interaction, NodeBundle, Res, TextBundle};
 use bevy::utils::default;
-use crate::ui::stats::components::{RoundText, TurnText};
+use crate::ui::stats::components::{ClockText, RoundText, TurnText};
 use crate::ui::stats::styles::{get_stats_container_style, get_stats_style, get_stats_text_style};
 use crate::ui::stats::systems::interaction::{set_player_turn_text, set_round_number_text};
+use crate::world::factions::Factions;
 use crate::world::fonts_and_styles::colors::BACKGROUND_COLOR;
+use crate::world::player::TurnOrder;
+use crate::world::turn_timer::TimeControl;
 
 fn spawn_round_number(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
     let mut round_text = TextBundle::from_section(
@@ -15,18 +18,26 @@ fn spawn_round_number(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>
     parent.spawn(round_text).insert(RoundText);
 }
 
-fn spawn_player_turn(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+fn spawn_player_turn(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, factions: &Factions, turn_order: &TurnOrder) {
     let mut player_turn_text = TextBundle::from_section(
         String::new(), get_stats_text_style(asset_server),
     );
-    set_player_turn_text(&mut player_turn_text.text, 1);
+    set_player_turn_text(&mut player_turn_text.text, factions, &turn_order.first());
 
     parent.spawn(player_turn_text).insert(TurnText);
 }
 
+fn spawn_clock(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent.spawn(TextBundle::from_section(
+        String::new(), get_stats_text_style(asset_server),
+    )).insert(ClockText);
+}
 
 pub(in crate::ui::stats) fn setup_stats(mut commands: Commands,
-                                        asset_server: Res<AssetServer>) {
+                                        asset_server: Res<AssetServer>,
+                                        factions: Res<Factions>,
+                                        turn_order: Res<TurnOrder>,
+                                        time_control: Res<TimeControl>) {
     commands
         .spawn((NodeBundle {
             style: get_stats_style(),
@@ -39,7 +50,10 @@ pub(in crate::ui::stats) fn setup_stats(mut commands: Commands,
                 ..default()
             }).with_children(|parent| {
                 spawn_round_number(parent, &asset_server);
-                spawn_player_turn(parent, &asset_server);
+                spawn_player_turn(parent, &asset_server, &factions, &turn_order);
+                if time_control.is_enabled() {
+                    spawn_clock(parent, &asset_server);
+                }
             });
         });
 }
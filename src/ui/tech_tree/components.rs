@@ -0,0 +1,17 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct TechTreeScreen;
+
+#[derive(Component)]
+pub struct TechButton {
+    pub tech: String,
+}
+
+#[derive(Component)]
+pub struct TechStatusText {
+    pub tech: String,
+}
+
+#[derive(Component)]
+pub struct CloseTechTreeButton;
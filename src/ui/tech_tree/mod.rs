@@ -0,0 +1,4 @@
+pub(crate) mod plugin;
+pub(crate) mod systems;
+mod components;
+mod styles;
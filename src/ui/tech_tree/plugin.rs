@@ -0,0 +1,34 @@
+use bevy::app::{App, Update};
+use bevy::prelude::{in_state, IntoSystemConfigs, OnEnter, OnExit, Plugin, States};
+
+use crate::game_state::UpdateUI;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::diplomacy::plugin::DiplomacyState;
+use crate::ui::handover::plugin::HandoverState;
+use crate::ui::tech_tree::systems::interaction::{close_tech_tree, interact_with_close_button, interact_with_tech_button, toggle_tech_tree, update_tech_statuses};
+use crate::ui::tech_tree::systems::layout::{despawn_tech_tree_screen, spawn_tech_tree_screen};
+
+pub struct TechTreePlugin;
+
+impl Plugin for TechTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<TechTreeState>()
+            .add_systems(Update, toggle_tech_tree.run_if(in_state(HandoverState::Hidden)).run_if(in_state(DiplomacyState::Hidden)).in_set(UpdateUI::UserInput))
+            .add_systems(Update, (
+                interact_with_tech_button,
+                interact_with_close_button,
+            ).run_if(in_state(TechTreeState::Shown)).in_set(UpdateUI::UserInput))
+            .add_systems(Update, update_tech_statuses.run_if(in_state(TechTreeState::Shown)).in_set(UpdateUI::RenderStats))
+            .add_systems(OnEnter(TurnSwitchedState::OnTurnSwitched), close_tech_tree)
+            .add_systems(OnEnter(TechTreeState::Shown), spawn_tech_tree_screen)
+            .add_systems(OnExit(TechTreeState::Shown), despawn_tech_tree_screen);
+    }
+}
+
+/// Research happens on its own screen on top of the board.
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub(crate) enum TechTreeState {
+    #[default]
+    Hidden,
+    Shown,
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use crate::ui::stats::resources::Round;
+use crate::ui::tech_tree::components::{CloseTechTreeButton, TechButton, TechStatusText};
+use crate::ui::tech_tree::plugin::TechTreeState;
+use crate::world::actions::ActionsState;
+use crate::world::fonts_and_styles::colors::{DISABLED_BUTTON, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
+use crate::world::game_log::GameLog;
+use crate::world::player::{Movable, Player};
+use crate::world::research::{Research, start_research};
+use crate::world::resources::GameResources;
+use crate::world::turn_actions::TurnAction;
+
+pub(in crate::ui::tech_tree) fn toggle_tech_tree(
+    keyboard_input: Res<Input<KeyCode>>,
+    state: Res<State<TechTreeState>>,
+    actions_state: Res<State<ActionsState>>,
+    mut next_state: ResMut<NextState<TechTreeState>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::T) { return; }
+    match state.get() {
+        TechTreeState::Hidden if *actions_state.get() == ActionsState::NoActionRunning => next_state.set(TechTreeState::Shown),
+        TechTreeState::Shown => next_state.set(TechTreeState::Hidden),
+        _ => {}
+    }
+}
+
+pub(in crate::ui::tech_tree) fn close_tech_tree(mut next_state: ResMut<NextState<TechTreeState>>) {
+    next_state.set(TechTreeState::Hidden);
+}
+
+pub(in crate::ui::tech_tree) fn interact_with_tech_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &TechButton), Changed<Interaction>>,
+    mut research: ResMut<Research>,
+    mut resources: ResMut<GameResources>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    for (interaction, mut color, tech_button) in button_query.iter_mut() {
+        let Some(tech) = research.tech(&tech_button.tech).cloned() else { continue; };
+        let can_start = research.can_start(player, &tech, &resources.resources[player]);
+        match *interaction {
+            Interaction::Pressed => {
+                if !can_start { continue; }
+                *color = PRESSED_BUTTON.into();
+                if !start_research(&mut research, &mut resources, player, &tech.id, &mut game_log, round.number) { continue; }
+                turn_actions.send(TurnAction::StartResearch { tech: tech.id.clone() });
+            }
+            Interaction::Hovered if can_start => {
+                *color = HOVERED_BUTTON.into();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Shows whether every tech is researched, in progress, available or still locked.
+pub(in crate::ui::tech_tree) fn update_tech_statuses(
+    research: Res<Research>,
+    resources: Res<GameResources>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut text_query: Query<(&mut Text, &TechStatusText)>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &TechButton)>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let player_research = research.of(player);
+    for (mut text, status) in text_query.iter_mut() {
+        let Some(tech) = research.tech(&status.tech) else { continue; };
+        let value = if research.has_researched(player, &tech.id) {
+            "Researched".to_string()
+        } else if let Some(progress) = player_research.current.as_ref().filter(|progress| progress.tech == tech.id) {
+            format!("In progress, {} turns left", progress.turns_left)
+        } else if !research.is_available(player, tech) {
+            let missing: Vec<String> = tech.requires.iter()
+                .filter(|id| !research.has_researched(player, id))
+                .map(|id| research.tech(id).map_or(id.clone(), |tech| tech.name.clone()))
+                .collect();
+            format!("Requires {}", missing.join(", "))
+        } else if player_research.current.is_some() {
+            "Available once the current research is finished".to_string()
+        } else {
+            "Available".to_string()
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+    for (interaction, mut color, tech_button) in button_query.iter_mut() {
+        if *interaction != Interaction::None { continue; }
+        let Some(tech) = research.tech(&tech_button.tech) else { continue; };
+        let new_color: BackgroundColor = if research.can_start(player, tech, &resources.resources[player]) { NORMAL_BUTTON.into() } else { DISABLED_BUTTON.into() };
+        if color.0 != new_color.0 {
+            *color = new_color;
+        }
+    }
+}
+
+pub(in crate::ui::tech_tree) fn interact_with_close_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CloseTechTreeButton>)>,
+    mut next_state: ResMut<NextState<TechTreeState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                next_state.set(TechTreeState::Hidden);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::ui::tech_tree::components::{CloseTechTreeButton, TechButton, TechStatusText, TechTreeScreen};
+use crate::ui::tech_tree::styles::{get_button_style, get_screen_style, get_tech_button_style, get_tech_description_style, get_tech_tree_title_style, SCREEN_COLOR};
+use crate::world::fonts_and_styles::colors::NORMAL_BUTTON;
+use crate::world::fonts_and_styles::fonts::get_button_text_style;
+use crate::world::research::{Research, Tech};
+
+pub(in crate::ui::tech_tree) fn spawn_tech_tree_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    research: Res<Research>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_screen_style(),
+                background_color: SCREEN_COLOR.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            TechTreeScreen,
+            Interaction::None,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Research", get_tech_tree_title_style(&asset_server)));
+            for tech in research.techs.iter() {
+                spawn_tech_button(parent, &asset_server, tech);
+            }
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: get_button_style(),
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    CloseTechTreeButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Close",
+                        get_button_text_style(&asset_server),
+                    ));
+                });
+        });
+}
+
+fn spawn_tech_button(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, tech: &Tech) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: get_tech_button_style(),
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            TechButton { tech: tech.id.clone() },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("{} (cost - {}/{}; turns - {})", tech.name, tech.cost.resources, tech.cost.influence, tech.turns),
+                get_button_text_style(asset_server),
+            ));
+            parent.spawn(TextBundle::from_section(
+                tech.description.clone(),
+                get_tech_description_style(asset_server),
+            ));
+            parent.spawn((
+                TextBundle::from_section("", get_tech_description_style(asset_server)),
+                TechStatusText { tech: tech.id.clone() },
+            ));
+        });
+}
+
+pub(in crate::ui::tech_tree) fn despawn_tech_tree_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<TechTreeScreen>>,
+) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
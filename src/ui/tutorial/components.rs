@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct TutorialPanel;
+
+#[derive(Component)]
+pub struct TutorialText;
+
+/// Frame drawn around the hex the current step points at.
+#[derive(Component)]
+pub struct TutorialHexMarker;
@@ -0,0 +1,4 @@
+pub(crate) mod plugin;
+mod components;
+mod styles;
+mod systems;
@@ -0,0 +1,21 @@
+use bevy::app::{App, Startup, Update};
+use bevy::prelude::{IntoSystemConfigs, Plugin, resource_exists};
+
+use crate::game_state::UpdateUI;
+use crate::ui::tutorial::systems::layout::spawn_tutorial_panel;
+use crate::ui::tutorial::systems::updates::{highlight_tutorial_buttons, highlight_tutorial_hex, update_tutorial_text};
+use crate::world::tutorial::Tutorial;
+
+pub struct TutorialPanelPlugin;
+
+impl Plugin for TutorialPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, spawn_tutorial_panel.run_if(resource_exists::<Tutorial>()))
+            .add_systems(Update, (
+                update_tutorial_text,
+                highlight_tutorial_buttons,
+                highlight_tutorial_hex,
+            ).run_if(resource_exists::<Tutorial>()).in_set(UpdateUI::RenderStats));
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::asset::AssetServer;
+use bevy::prelude::{AlignItems, Color, Display, FlexDirection, JustifyContent, PositionType, Res, Style, TextStyle, UiRect, Val};
+
+pub(in crate::ui::tutorial) const PANEL_COLOR: Color = Color::rgba(0.02, 0.02, 0.05, 0.8);
+pub(in crate::ui::tutorial) const HIGHLIGHT_COLOR: Color = Color::rgb(1.0, 0.85, 0.4);
+pub(in crate::ui::tutorial) const HIGHLIGHT_BORDER: Val = Val::Px(4.0);
+
+pub(in crate::ui::tutorial) fn get_panel_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        top: Val::Px(150.0),
+        left: Val::Percent(25.0),
+        width: Val::Percent(50.0),
+        padding: UiRect::all(Val::Px(12.0)),
+        ..Style::DEFAULT
+    }
+}
+
+pub(in crate::ui::tutorial) fn get_step_title_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: HIGHLIGHT_COLOR,
+    }
+}
+
+pub(in crate::ui::tutorial) fn get_step_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 22.0,
+        color: Color::rgb(0.9, 0.9, 0.9),
+    }
+}
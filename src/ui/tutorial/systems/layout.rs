@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::ui::tutorial::components::{TutorialHexMarker, TutorialPanel, TutorialText};
+use crate::ui::tutorial::styles::{get_panel_style, get_step_text_style, get_step_title_style, HIGHLIGHT_COLOR, PANEL_COLOR};
+
+pub(in crate::ui::tutorial) fn spawn_tutorial_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_panel_style(),
+                background_color: PANEL_COLOR.into(),
+                ..default()
+            },
+            TutorialPanel,
+            Interaction::None,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("", get_step_title_style(&asset_server)),
+                    TextSection::new("", get_step_text_style(&asset_server)),
+                ]).with_text_alignment(TextAlignment::Center),
+                TutorialText,
+            ));
+        });
+
+    // Sized and moved onto its hex once the grid is there
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("kenney - Simpe Icons/my_hex.png"),
+            sprite: Sprite {
+                color: HIGHLIGHT_COLOR,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TutorialHexMarker,
+    ));
+}
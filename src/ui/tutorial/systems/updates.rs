@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use crate::ui::action_panel::components::{FortifyButton, HireArmyButton, OpenBuildPanelButton, OpenMovePanelButton, SkipRoundButton};
+use crate::ui::tutorial::components::{TutorialHexMarker, TutorialText};
+use crate::ui::tutorial::styles::{HIGHLIGHT_BORDER, HIGHLIGHT_COLOR};
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::tutorial::{Tutorial, TutorialButton, TutorialHighlight};
+
+/// Drawn just above the planet tiles, below their icons.
+const MARKER_Z: f32 = -9.5;
+
+pub(in crate::ui::tutorial) fn update_tutorial_text(
+    tutorial: Res<Tutorial>,
+    mut text_query: Query<&mut Text, With<TutorialText>>,
+) {
+    if !tutorial.is_changed() { return; }
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    let step = tutorial.step();
+    text.sections[0].value = format!("{}/{}: {}\n", tutorial.current + 1, tutorial.steps.len(), step.title);
+    text.sections[1].value = step.text.clone();
+}
+
+pub(in crate::ui::tutorial) fn highlight_tutorial_buttons(
+    tutorial: Res<Tutorial>,
+    mut button_query: Query<(
+        &mut Style,
+        &mut BorderColor,
+        Option<&HireArmyButton>,
+        Option<&OpenMovePanelButton>,
+        Option<&OpenBuildPanelButton>,
+        Option<&FortifyButton>,
+        Option<&SkipRoundButton>,
+    ), With<Button>>,
+) {
+    if !tutorial.is_changed() { return; }
+    for (mut style, mut border_color, spawn, move_, build, fortify, skip_round) in button_query.iter_mut() {
+        let button = if spawn.is_some() {
+            TutorialButton::Spawn
+        } else if move_.is_some() {
+            TutorialButton::Move
+        } else if build.is_some() {
+            TutorialButton::Build
+        } else if fortify.is_some() {
+            TutorialButton::Fortify
+        } else if skip_round.is_some() {
+            TutorialButton::SkipRound
+        } else {
+            continue;
+        };
+        let highlighted = tutorial.highlights().contains(&TutorialHighlight::Button { button });
+        style.border = if highlighted { UiRect::all(HIGHLIGHT_BORDER) } else { UiRect::all(Val::Px(0.0)) };
+        *border_color = if highlighted { HIGHLIGHT_COLOR.into() } else { Color::NONE.into() };
+    }
+}
+
+pub(in crate::ui::tutorial) fn highlight_tutorial_hex(
+    tutorial: Res<Tutorial>,
+    grid: Res<HexGrid>,
+    mut marker_query: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<TutorialHexMarker>>,
+) {
+    if !tutorial.is_changed() { return; }
+    let Ok((mut transform, mut sprite, mut visibility)) = marker_query.get_single_mut() else { return; };
+    let hex = tutorial.highlights().iter().find_map(|highlight| match highlight {
+        TutorialHighlight::Hex { hex } => Some(*hex),
+        _ => None,
+    });
+    let Some(hex) = hex else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    let position = grid.layout.hex_to_world_pos(hex);
+    transform.translation = Vec3::new(position.x, position.y, MARKER_Z);
+    sprite.custom_size = Some(grid.layout.rect_size());
+    *visibility = Visibility::Inherited;
+}
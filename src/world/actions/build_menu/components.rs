@@ -0,0 +1,16 @@
+use bevy::prelude::Component;
+use crate::world::buildings::BuildingType;
+
+#[derive(Component)]
+pub(super) struct BuildMenu;
+
+#[derive(Component)]
+pub(super) struct PlanetBuildingsText;
+
+#[derive(Component)]
+pub(super) struct BuildButton {
+    pub(crate) building_type: BuildingType
+}
+
+#[derive(Component)]
+pub(super) struct CancelBuildButton;
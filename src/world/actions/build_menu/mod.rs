@@ -0,0 +1,21 @@
+use bevy::asset::AssetServer;
+use bevy::prelude::{Commands, Entity, Query, Res, With};
+
+use crate::world::actions::build_menu::components::BuildMenu;
+use crate::world::actions::build_menu::systems::layout::{build_build_menu, despawn_build_menu};
+
+pub(crate) mod components;
+mod styles;
+pub(crate) mod systems;
+pub(super) mod plugin;
+
+pub fn spawn_building_window(mut commands: Commands, asset_server: Res<AssetServer>) {
+    build_build_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_building_window(
+    commands: Commands,
+    build_menu_query: Query<Entity, With<BuildMenu>>,
+) {
+    despawn_build_menu(commands, build_menu_query)
+}
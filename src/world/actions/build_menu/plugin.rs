@@ -0,0 +1,24 @@
+use bevy::app::{App, Update};
+use bevy::prelude::{in_state, IntoSystemConfigs, OnEnter, OnExit, Plugin};
+use crate::game_state::UpdateUI;
+use crate::world::actions::{ActionsState, build_menu};
+use crate::world::actions::build_menu::systems::interactions::{interact_cancel_build_button_click, interact_with_build_button, update_build_buttons_disabled, update_planet_buildings_text};
+
+pub(in crate::world::actions) struct BuildMenuPlugin;
+
+
+impl Plugin for BuildMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(ActionsState::ConstructingBuildings), build_menu::spawn_building_window)
+            .add_systems(Update, (
+                interact_with_build_button,
+                interact_cancel_build_button_click,
+            ).run_if(in_state(ActionsState::ConstructingBuildings)).in_set(UpdateUI::UserInput))
+            .add_systems(Update, (
+                update_build_buttons_disabled,
+                update_planet_buildings_text,
+            ).run_if(in_state(ActionsState::ConstructingBuildings)).in_set(UpdateUI::RenderStats))
+            .add_systems(OnExit(ActionsState::ConstructingBuildings), build_menu::despawn_building_window);
+    }
+}
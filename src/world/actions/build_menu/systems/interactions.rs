@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::stats::resources::Round;
+use crate::world::actions::ActionsState;
+use crate::world::actions::build_menu::components::{BuildButton, CancelBuildButton, PlanetBuildingsText};
+use crate::world::buildings::{BUILDING_SLOTS, can_build, start_building};
+use crate::world::fonts_and_styles::colors::*;
+use crate::world::game_log::GameLog;
+use crate::world::player::{Movable, Player};
+use crate::world::resources::GameResources;
+use crate::world::setup_world_grid::{HexGrid, SelectedHex};
+use crate::world::turn_actions::TurnAction;
+
+pub(in crate::world::actions::build_menu) fn update_build_buttons_disabled(
+    mut button_query: Query<(&mut BackgroundColor, &BuildButton)>,
+    grid: Res<HexGrid>,
+    resources: Res<GameResources>,
+    selected_hex: Res<SelectedHex>,
+    current_player_query: Query<&Player, With<Movable>>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let Some(planet) = grid.planets.get(&selected_hex.hex) else { return; };
+    for (mut color, build_button) in button_query.iter_mut() {
+        if color.0 == HOVERED_BUTTON || color.0 == PRESSED_BUTTON { continue; }
+        *color = if can_build(planet, player, &resources.resources[player], build_button.building_type) {
+            NORMAL_BUTTON.into()
+        } else {
+            DISABLED_BUTTON.into()
+        };
+    }
+}
+
+pub(in crate::world::actions::build_menu) fn interact_with_build_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &BuildButton), Changed<Interaction>>,
+    mut resources: ResMut<GameResources>,
+    mut grid: ResMut<HexGrid>,
+    selected_hex: Res<SelectedHex>,
+    mut action_state: ResMut<NextState<ActionsState>>,
+    mut switched_turn: ResMut<NextState<TurnSwitchedState>>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    for (interaction, mut color, build_button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                let player = current_player_query.single();
+                let building_type = build_button.building_type;
+                let Some(planet) = grid.planets.get_mut(&selected_hex.hex) else { return; };
+                if !start_building(planet, player, &mut resources, building_type, &mut game_log, round.number) { return; }
+
+                turn_actions.send(TurnAction::Build { hex: selected_hex.hex, building: building_type });
+                action_state.set(ActionsState::NoActionRunning);
+                switched_turn.set(TurnSwitchedState::OnTurnSwitched)
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+pub(in crate::world::actions::build_menu) fn update_planet_buildings_text(
+    grid: Res<HexGrid>,
+    selected_hex: Res<SelectedHex>,
+    mut text_query: Query<&mut Text, With<PlanetBuildingsText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    let Some(planet) = grid.planets.get(&selected_hex.hex) else { return; };
+    let mut value = format!("Slots: {}/{}\n", planet.buildings.len(), BUILDING_SLOTS);
+    for building in planet.buildings.iter() {
+        if building.is_finished() {
+            value.push_str(format!("{}\n", building.building_type).as_str());
+        } else {
+            value.push_str(format!("{} ({} turns left)\n", building.building_type, building.turns_left).as_str());
+        }
+    }
+    if text.sections[0].value != value {
+        text.sections[0].value = value;
+    }
+}
+
+pub(in crate::world::actions::build_menu) fn interact_cancel_build_button_click(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CancelBuildButton>)>,
+    mut action_state: ResMut<NextState<ActionsState>>,
+) {
+    for (interaction, mut color) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                action_state.set(ActionsState::NoActionRunning);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::world::actions::build_menu::components::*;
+use crate::world::actions::build_menu::styles::*;
+use crate::world::buildings::{ALL_BUILDINGS, BuildingCharacteristics, BuildingType};
+use crate::world::fonts_and_styles::colors::*;
+use crate::world::fonts_and_styles::fonts::*;
+
+pub(crate) fn despawn_build_menu(
+    mut commands: Commands,
+    build_menu_query: Query<Entity, With<BuildMenu>>,
+) {
+    if let Ok(build_menu_entity) = build_menu_query.get_single() {
+        commands.entity(build_menu_entity).despawn_recursive();
+    }
+}
+
+pub(crate) fn build_build_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_build_menu_style(),
+                z_index: ZIndex::Local(1),
+                ..default()
+            },
+            BuildMenu,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((NodeBundle {
+                    style: get_build_menu_container_style(),
+                    background_color: BACKGROUND_COLOR.into(),
+                    ..default()
+                }, Interaction::None))
+                .with_children(|parent| {
+                    // Title
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Build Menu",
+                                get_title_text_style(&asset_server),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new("", get_info_text_style(&asset_server))],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        PlanetBuildingsText,
+                    ));
+
+                    for building_type in ALL_BUILDINGS {
+                        spawn_build_button(&asset_server, parent, building_type);
+                    }
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: get_button_style(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            CancelBuildButton,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle {
+                                style: Style { ..default() },
+                                text: Text {
+                                    sections: vec![TextSection::new(
+                                        "Cancel",
+                                        get_button_text_style(&asset_server),
+                                    )],
+                                    alignment: TextAlignment::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            });
+                        });
+                });
+        })
+        .id()
+}
+
+fn spawn_build_button(asset_server: &Res<AssetServer>, parent: &mut ChildBuilder, building_type: BuildingType) {
+    let characteristics = BuildingCharacteristics::get_by_building_type(building_type);
+    parent
+        .spawn((
+            ButtonBundle {
+                style: get_button_style(),
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            BuildButton {
+                building_type
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style { ..default() },
+                text: Text {
+                    sections: vec![
+                        TextSection::new(
+                            format!("Build {}\n", building_type),
+                            get_button_text_style(&asset_server),
+                        ),
+                        TextSection::new(
+                            format!("(cost - {}/{}; turns - {})", characteristics.price.resources, characteristics.price.influence, characteristics.build_time),
+                            get_info_text_style(&asset_server),
+                        ),
+                    ],
+                    alignment: TextAlignment::Center,
+                    ..default()
+                },
+                ..default()
+            });
+        });
+}
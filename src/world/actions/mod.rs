@@ -3,22 +3,37 @@ use bevy::utils::HashMap;
 use hexx::Hex;
 
 use crate::game_state::UpdateUI;
-use crate::space_ships::{SpaceShipCharacteristics, SpaceShipType};
-use crate::space_ships::SpaceShipType::{Carrier, Destroyer, Frigate};
-use crate::ui::action_panel::systems::interaction::{has_neighbour_in_planets, is_selected_hex_belongs_to_player, is_selected_hex_has_neighbours};
+use crate::ui::handover::plugin::HandoverState;
+use crate::ui::diplomacy::plugin::DiplomacyState;
+use crate::ui::tech_tree::plugin::TechTreeState;
+use crate::space_ships::{ALL_SPACE_SHIPS, SpaceShipType};
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::stats::resources::Round;
+use crate::ui::action_panel::systems::interaction::{is_selected_hex_belongs_to_player, is_selected_hex_reachable, is_selected_hex_shipyard_of_player};
+use crate::world::actions::build_menu::plugin::BuildMenuPlugin;
 use crate::world::actions::move_menu::animations::animation_tick;
 use crate::world::actions::move_menu::plugin::MoveMenuPlugin;
 use crate::world::actions::spawn_menu::plugin::SpawnMenuPlugin;
 use crate::world::fonts_and_styles::fonts::{get_info_text_style, get_win_probability_text_style};
+use crate::world::pathfinding::get_reachable_hexes;
 use crate::world::player::{Movable, Player};
+use crate::world::production::ProductionDraft;
+use crate::world::defense::{can_fortify, fortify};
+use crate::world::diplomacy::Diplomacy;
+use crate::world::game_log::GameLog;
+use crate::world::research::Research;
 use crate::world::resources::GameResources;
 use crate::world::setup_world_grid::{create_sprite_bundle_with_image, HexGrid, Planet, SelectedHex};
+use crate::world::supply::Supply;
+use crate::world::turn_actions::TurnAction;
 
 pub(crate) mod spawn_menu;
 
 
 pub(crate) mod move_menu;
 
+pub(crate) mod build_menu;
+
 pub(crate) struct ActionsPlugin;
 
 impl Plugin for ActionsPlugin {
@@ -27,7 +42,8 @@ impl Plugin for ActionsPlugin {
             .add_state::<ActionsState>()
             .add_plugins(MoveMenuPlugin)
             .add_plugins(SpawnMenuPlugin)
-            .add_systems(Update, change_action_state.in_set(UpdateUI::UserInput))
+            .add_plugins(BuildMenuPlugin)
+            .add_systems(Update, change_action_state.run_if(in_state(HandoverState::Hidden)).run_if(in_state(TechTreeState::Hidden)).run_if(in_state(DiplomacyState::Hidden)).in_set(UpdateUI::UserInput))
             .add_systems(Update, update_lock.in_set(UpdateUI::RenderStats))
             .add_systems(Update, animation_tick);
     }
@@ -40,6 +56,7 @@ pub(crate) enum ActionsState {
     // ChoosingAction,
     SpawningSpaceShips,
     MovingSpaceShips,
+    ConstructingBuildings,
 }
 
 pub fn clear_action_state(mut commands: Commands) {
@@ -54,43 +71,72 @@ fn change_action_state(
     mut player_resources: ResMut<GameResources>,
     selected_hex: Res<SelectedHex>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+    research: Res<Research>,
+    diplomacy: Res<Diplomacy>,
+    supply: Res<Supply>,
+    draft: Option<Res<ProductionDraft>>,
+    mut switched_turn: ResMut<NextState<TurnSwitchedState>>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut turn_actions: EventWriter<TurnAction>,
 ) {
     let player = current_player_query.single();
     match current_state.get() {
         ActionsState::NoActionRunning => {
             let grid = grid_mut.as_ref();
-            if keyboard_input.just_pressed(KeyCode::S) && is_selected_hex_belongs_to_player(player, grid, &selected_hex.hex) {
+            if keyboard_input.just_pressed(KeyCode::S) && is_selected_hex_shipyard_of_player(player, grid, &selected_hex.hex, &supply) {
                 commands.insert_resource(NextState(Some(ActionsState::SpawningSpaceShips)))
-            } else if keyboard_input.just_pressed(KeyCode::M) && (is_selected_hex_belongs_to_player(player, grid, &selected_hex.hex) || is_selected_hex_has_neighbours(player, grid, &selected_hex.hex)) {
+            } else if keyboard_input.just_pressed(KeyCode::B) && is_selected_hex_belongs_to_player(player, grid, &selected_hex.hex) {
+                commands.insert_resource(NextState(Some(ActionsState::ConstructingBuildings)))
+            } else if keyboard_input.just_pressed(KeyCode::F) && can_fortify(player, grid, &selected_hex.hex) {
+                fortify(player, &mut grid_mut, &selected_hex.hex, &mut game_log, round.number);
+                turn_actions.send(TurnAction::Fortify { hex: selected_hex.hex });
+                switched_turn.set(TurnSwitchedState::OnTurnSwitched)
+            } else if keyboard_input.just_pressed(KeyCode::M) && (is_selected_hex_belongs_to_player(player, grid, &selected_hex.hex) || is_selected_hex_reachable(player, grid, &selected_hex.hex, &research, &diplomacy)) {
                 commands.insert_resource(NextState(Some(ActionsState::MovingSpaceShips)))
             }
         }
         _ => {
             if keyboard_input.just_pressed(KeyCode::C) {
-                reset_selected_for_move_ships(&mut grid_mut);
-                reset_selected_for_buy_ships(&mut grid_mut, &mut player_resources);
-                commands.insert_resource(NextState(Some(ActionsState::NoActionRunning)))
+                cancel_running_action(&mut commands, &mut grid_mut, &mut player_resources, draft.as_deref());
             }
         }
     }
 }
 
+/// Drops the ships selected for a move and the production queue being edited, then leaves the action.
+pub(crate) fn cancel_running_action(
+    commands: &mut Commands,
+    grid: &mut ResMut<HexGrid>,
+    player_resources: &mut ResMut<GameResources>,
+    draft: Option<&ProductionDraft>,
+) {
+    reset_selected_for_move_ships(grid);
+    if let Some(draft) = draft {
+        draft.restore(grid, player_resources);
+    }
+    commands.insert_resource(NextState(Some(ActionsState::NoActionRunning)))
+}
+
 pub fn update_lock(
     mut grid_mut: ResMut<HexGrid>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
     mut commands: Commands,
     assets: Res<AssetServer>,
+    research: Res<Research>,
+    diplomacy: Res<Diplomacy>,
 ) {
     let player = current_player_query.single();
     let mut vec_to_add_lock = vec![];
     let mut vec_to_remove_lock = vec![];
+    let reachable_hexes = get_reachable_hexes(player, &grid_mut.planets, &research, &diplomacy);
     for (hex, planet) in grid_mut.planets.iter() {
         match &planet.planet_locked_entity {
             None => {
-                if !has_neighbour_in_planets(player, hex, &grid_mut.planets) && !is_selected_hex_belongs_to_player(&player, &grid_mut, hex) { vec_to_add_lock.push(hex.clone()) }
+                if !reachable_hexes.contains(hex) && !is_selected_hex_belongs_to_player(&player, &grid_mut, hex) { vec_to_add_lock.push(hex.clone()) }
             }
             Some(_) => {
-                if has_neighbour_in_planets(player, hex, &grid_mut.planets) || is_selected_hex_belongs_to_player(&player, &grid_mut, hex) { vec_to_remove_lock.push(hex.clone()) }
+                if reachable_hexes.contains(hex) || is_selected_hex_belongs_to_player(&player, &grid_mut, hex) { vec_to_remove_lock.push(hex.clone()) }
             }
         }
     }
@@ -141,20 +187,6 @@ fn despawn_lock_entity(planet: &mut Planet, commands: &mut Commands) {
     }
 }
 
-pub(self) fn reset_selected_for_buy_ships(hex_grid: &mut ResMut<HexGrid>, mut player_resources: &mut ResMut<GameResources>) {
-    for (_, mut planet) in hex_grid.planets.iter_mut() {
-        let player = planet.owner;
-        if (player.id != -1) {
-            let mut resources = player_resources.resources.remove(&player).unwrap();
-            planet.owner_army.iter_mut().filter(|spaceship| spaceship.is_selected_for_buy).for_each(
-                |spaceship| resources += SpaceShipCharacteristics::get_by_spaceship_type(spaceship.ship_type).price
-            );
-            planet.owner_army.retain(|spaceship| !spaceship.is_selected_for_buy);
-            player_resources.resources.insert(player, resources);
-        }
-    }
-}
-
 pub(self) fn reset_selected_for_move_ships(hex_grid: &mut ResMut<HexGrid>) {
     hex_grid.planets.iter_mut().for_each(|(_, mut planet)| {
         planet.owner_army.iter_mut().for_each(|ship| ship.is_selected_for_move = false);
@@ -181,50 +213,23 @@ pub(self) fn get_win_probability_text(asset_server: &Res<AssetServer>) -> TextBu
 }
 
 
-pub(self) fn get_buy_ship_stats_text(asset_server: &Res<AssetServer>) -> TextBundle {
-    let carrier = SpaceShipCharacteristics::get_by_spaceship_type(Carrier);
-    let destroyer = SpaceShipCharacteristics::get_by_spaceship_type(Destroyer);
-    let frigate = SpaceShipCharacteristics::get_by_spaceship_type(Frigate);
+/// Three sections per ship type, the first one of each is the count, see `get_spaceship_index_by_type`.
+pub(self) fn get_buy_ship_stats_text(asset_server: &Res<AssetServer>, research: &Research, player: &Player) -> TextBundle {
+    let mut sections = vec![];
+    for ship_type in ALL_SPACE_SHIPS {
+        let characteristics = research.ship_characteristics(player, ship_type);
+        let details = if research.is_ship_unlocked(player, ship_type) {
+            format!("(cost - {}; power - {})\n", characteristics.price.resources, characteristics.power)
+        } else {
+            "(not researched)\n".to_string()
+        };
+        sections.push(TextSection::new("0", get_info_text_style(asset_server)));
+        sections.push(TextSection::new(format!(" - {} ", ship_type), get_info_text_style(asset_server)));
+        sections.push(TextSection::new(details, get_info_text_style(asset_server)));
+    }
     TextBundle {
         text: Text {
-            sections: vec![
-                TextSection::new(
-                    "0",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    " - Carrier ",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    format!("(cost - {}; power - {})\n", carrier.price.resources, carrier.power),
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    "0",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    " - Destroyer ",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    format!("(cost - {}; power - {})\n", destroyer.price.resources, destroyer.power),
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    "0",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    " - Frigate ",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    format!("(cost - {}; power - {})\n", frigate.price.resources, frigate.power),
-                    get_info_text_style(asset_server),
-                ),
-            ],
+            sections,
             alignment: TextAlignment::Center,
             ..default()
         },
@@ -232,50 +237,20 @@ pub(self) fn get_buy_ship_stats_text(asset_server: &Res<AssetServer>) -> TextBun
     }
 }
 
-pub(self) fn get_move_ship_stats_text(asset_server: &Res<AssetServer>) -> TextBundle {
-    let carrier = SpaceShipCharacteristics::get_by_spaceship_type(Carrier);
-    let destroyer = SpaceShipCharacteristics::get_by_spaceship_type(Destroyer);
-    let frigate = SpaceShipCharacteristics::get_by_spaceship_type(Frigate);
+pub(self) fn get_move_ship_stats_text(asset_server: &Res<AssetServer>, research: &Research, player: &Player) -> TextBundle {
+    let mut sections = vec![];
+    for ship_type in ALL_SPACE_SHIPS {
+        let characteristics = research.ship_characteristics(player, ship_type);
+        sections.push(TextSection::new("0", get_info_text_style(asset_server)));
+        sections.push(TextSection::new(format!(" - {} ", ship_type), get_info_text_style(asset_server)));
+        sections.push(TextSection::new(
+            format!("(power - {}; range - {})\n", characteristics.power, characteristics.range),
+            get_info_text_style(asset_server),
+        ));
+    }
     TextBundle {
         text: Text {
-            sections: vec![
-                TextSection::new(
-                    "0",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    " - Carrier ",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    format!("(power - {})\n", carrier.power),
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    "0",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    " - Destroyer ",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    format!("(power - {})\n", destroyer.power),
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    "0",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    " - Frigate ",
-                    get_info_text_style(asset_server),
-                ),
-                TextSection::new(
-                    format!("(power - {})\n", frigate.power),
-                    get_info_text_style(asset_server),
-                ),
-            ],
+            sections,
             alignment: TextAlignment::Center,
             ..default()
         },
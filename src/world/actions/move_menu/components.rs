@@ -13,6 +13,16 @@ pub(super) struct MoveShipButton {
 }
 
 
+/// Toggles a single veteran, rookies are picked with `MoveShipButton`.
+#[derive(Component)]
+pub(super) struct MoveVeteranButton {
+    pub(crate) ship_id: u32,
+}
+
+/// Holds one `MoveVeteranButton` for every veteran on the planet the ships depart from.
+#[derive(Component)]
+pub(super) struct VeteranList;
+
 #[derive(Component)]
 pub(super) struct CancelButton;
 
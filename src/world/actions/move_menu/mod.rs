@@ -4,6 +4,7 @@ use bevy::prelude::{Commands, Entity, Query, Res, With};
 use crate::world::actions::move_menu::components::MoveMenu;
 use crate::world::actions::move_menu::systems::layout::{build_move_menu, despawn_move_menu};
 use crate::world::player::{Movable, Player};
+use crate::world::research::Research;
 use crate::world::setup_world_grid::{HexGrid, SelectedHex};
 
 pub(crate) mod components;
@@ -47,10 +48,11 @@ impl Plugin for PauseMenuPlugin {
 pub(self) fn spawn_move_space_ships_window(selected_hex: Res<SelectedHex>,
                        hex_grid: Res<HexGrid>,
                        current_player_query: Query<&Player, With<Movable>>,
+                       research: Res<Research>,
                        commands: Commands,
                        asset_server: Res<AssetServer>) {
     println!("Spawning Pause Menu");
-    build_move_menu(selected_hex, hex_grid, current_player_query, commands, &asset_server);
+    build_move_menu(selected_hex, hex_grid, current_player_query, research, commands, &asset_server);
 }
 
 pub(self) fn despawn_move_space_ships_window(
@@ -21,13 +21,54 @@ pub fn get_move_menu_container_style() -> Style {
         justify_content: JustifyContent::Center,
         align_items: AlignItems::Center,
         width: Val::Px(250.0),
-        height: Val::Px(450.0),
+        min_height: Val::Px(450.0),
         row_gap: Val::Px(8.0),
         column_gap: Val::Px(8.0),
         ..Style::DEFAULT
     }
 }
 
+pub fn get_veteran_list_style() -> Style {
+    Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(4.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_veteran_button_style() -> Style {
+    Style {
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Px(220.0),
+        height: Val::Px(30.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_veteran_ship_image_style() -> Style {
+    Style {
+        width: Val::Px(26.0),
+        height: Val::Px(26.0),
+        margin: UiRect::right(Val::Px(8.0)),
+        ..Style::DEFAULT
+    }
+}
+
+/// Chevrons are stacked down from the top of the ship image, one per rank.
+pub fn get_veteran_chevron_style(rank: u32) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(8.0),
+        top: Val::Px(4.0 * (rank - 1) as f32),
+        width: Val::Px(10.0),
+        height: Val::Px(10.0),
+        ..Style::DEFAULT
+    }
+}
+
 pub fn get_button_style() -> Style {
     Style {
         justify_content: JustifyContent::Center,
@@ -1,37 +1,71 @@
 use rand::prelude::SliceRandom;
-use rand::{Rng, thread_rng};
-use crate::space_ships::{SpaceShip, SpaceShipCharacteristics};
+use rand::Rng;
+use crate::space_ships::SpaceShip;
+use crate::world::buildings::{BuildingCharacteristics, BuildingType};
+use crate::world::defense::BattleModifiers;
 use crate::world::player::Player;
+use crate::world::research::Research;
 
-pub(in crate::world::actions::move_menu) fn move_army_to_planet(attack_player: Player, defense_player: Player, mut attack_player_army: Vec<SpaceShip>, mut defense_player_army: Vec<SpaceShip>) -> (Player, Vec<SpaceShip>) {
+/// `defense_platforms` of the planet fight first, the ones destroyed are subtracted from it.
+/// `modifiers` of the planet apply to the platforms and the defending ships alike.
+pub(crate) fn move_army_to_planet(rng: &mut impl Rng, research: &Research, attack_player: Player, defense_player: Player, mut attack_player_army: Vec<SpaceShip>, mut defense_player_army: Vec<SpaceShip>, defense_platforms: &mut usize, modifiers: BattleModifiers) -> (Player, Vec<SpaceShip>) {
     if attack_player.id == defense_player.id {
         attack_player_army.append(&mut defense_player_army);
-        (attack_player, attack_player_army)
+        return (attack_player, attack_player_army);
+    }
+    attack_player_army.shuffle(rng);
+    fight_defense_platforms(rng, research, &attack_player, &mut attack_player_army, defense_platforms, &modifiers);
+    if attack_player_army.is_empty() {
+        (defense_player, defense_player_army)
     } else {
-        perform_fight(attack_player, defense_player, attack_player_army, defense_player_army)
+        perform_fight(rng, research, attack_player, defense_player, attack_player_army, defense_player_army, modifiers)
+    }
+}
+
+/// Every platform duels the attackers one by one until it is destroyed or no attacker is left.
+fn fight_defense_platforms(rng: &mut impl Rng, research: &Research, attack_player: &Player, attack_player_army: &mut Vec<SpaceShip>, defense_platforms: &mut usize, modifiers: &BattleModifiers) {
+    let coeff_platform = modifiers.defense_power(BuildingCharacteristics::get_by_building_type(BuildingType::DefensePlatform).power);
+    while *defense_platforms > 0 && !attack_player_army.is_empty() {
+        let mut attack_ship = attack_player_army.pop().unwrap();
+        let attack_dice = rng.gen_range(0..10);
+        let defense_dice = rng.gen_range(0..10);
+        let coeff_attack = modifiers.attack_power(ship_power(research, attack_player, &attack_ship));
+        if attack_dice * coeff_attack >= defense_dice * coeff_platform {
+            *defense_platforms -= 1;
+            attack_ship.experience += 1;
+            attack_player_army.insert(0, attack_ship);
+        }
     }
 }
 
-pub(in crate::world::actions::move_menu) fn perform_fight(attack_player: Player, defense_player: Player, mut attack_player_army: Vec<SpaceShip>, mut defense_player_army: Vec<SpaceShip>) -> (Player, Vec<SpaceShip>) {
-    let mut rng = thread_rng();
-    attack_player_army.shuffle(&mut rng);
-    defense_player_army.shuffle(&mut rng);
+/// Techs researched by the owner and the rank of the ship both add to its power.
+fn ship_power(research: &Research, player: &Player, ship: &SpaceShip) -> u32 {
+    research.ship_characteristics(player, ship.ship_type).power + ship.rank()
+}
+
+/// Ship powers include the techs each side has researched and the `modifiers` of the battlefield,
+/// ships winning a duel gain experience.
+pub(crate) fn perform_fight(rng: &mut impl Rng, research: &Research, attack_player: Player, defense_player: Player, mut attack_player_army: Vec<SpaceShip>, mut defense_player_army: Vec<SpaceShip>, modifiers: BattleModifiers) -> (Player, Vec<SpaceShip>) {
+    attack_player_army.shuffle(rng);
+    defense_player_army.shuffle(rng);
 
     assert!(!attack_player_army.is_empty() || !defense_player_army.is_empty());
 
     while !attack_player_army.is_empty() && !defense_player_army.is_empty() {
-        let attack_ship = attack_player_army.pop().unwrap();
-        let defense_ship = defense_player_army.pop().unwrap();
+        let mut attack_ship = attack_player_army.pop().unwrap();
+        let mut defense_ship = defense_player_army.pop().unwrap();
         let attack_dice = rng.gen_range(0..10);
         let defense_dice = rng.gen_range(0..10);
-        let coeff_attack = SpaceShipCharacteristics::get_by_spaceship_type(attack_ship.ship_type).power;
-        let coeff_defense = SpaceShipCharacteristics::get_by_spaceship_type(defense_ship.ship_type).power;
+        let coeff_attack = modifiers.attack_power(ship_power(research, &attack_player, &attack_ship));
+        let coeff_defense = modifiers.defense_power(ship_power(research, &defense_player, &defense_ship));
         if attack_dice * coeff_attack > defense_dice * coeff_defense {
+            attack_ship.experience += 1;
             attack_player_army.push(attack_ship);
         } else if attack_dice * coeff_attack == defense_dice * coeff_defense {
             attack_player_army.push(attack_ship);
             defense_player_army.push(defense_ship);
         } else {
+            defense_ship.experience += 1;
             defense_player_army.push(defense_ship);
         }
     }
@@ -1,17 +1,27 @@
 use bevy::prelude::*;
 use bevy::prelude::KeyCode;
+use rand::thread_rng;
 
 use crate::space_ships::{get_count_spaceship_dict, SpaceShip, SpaceSipTextureAtlas};
 use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::stats::resources::Round;
 use crate::world::actions::{ActionsState, get_spaceship_index_by_type, reset_selected_for_move_ships};
-use crate::world::actions::move_menu::animations::run_spaceship_moving_animation;
-use crate::world::actions::move_menu::components::{CancelButton, EndMoveButton, MoveShipButton, SelectedSpaceshipsText, WinProbabilityText};
+use crate::world::actions::move_menu::components::{CancelButton, EndMoveButton, MoveShipButton, MoveVeteranButton, SelectedSpaceshipsText, WinProbabilityText};
 use crate::world::actions::move_menu::events::ShipMoved;
 use crate::world::actions::move_menu::systems::battle::{move_army_to_planet};
+use crate::world::defense::BattleModifiers;
+use crate::world::buildings::BuildingType;
+use crate::world::diplomacy::{break_treaty, can_attack, Diplomacy};
+use crate::world::factions::Factions;
+use crate::world::research::Research;
+use crate::world::fleets::launch_fleets;
 use crate::world::fonts_and_styles::colors::*;
+use crate::world::game_log::GameLog;
+use crate::world::pathfinding::can_fly_path;
 use crate::world::player::{Movable, Player};
 use crate::world::resources::GameResources;
-use crate::world::setup_world_grid::{HEX_NOWHERE, HexGrid, Planet, SelectedHex};
+use crate::world::setup_world_grid::{HEX_NOWHERE, HexGrid, SelectedHex};
+use crate::world::turn_actions::TurnAction;
 
 
 const TOTAL_NUMBER_OF_SIMULATIONS: u32 = 100;
@@ -19,13 +29,18 @@ const TOTAL_NUMBER_OF_SIMULATIONS: u32 = 100;
 pub(in crate::world::actions::move_menu) fn update_end_move_button_disabled(
     mut button_query: Query<&mut BackgroundColor, With<EndMoveButton>>,
     grid: ResMut<HexGrid>,
+    selected_hex: Res<SelectedHex>,
+    diplomacy: Res<Diplomacy>,
+    resources: Res<GameResources>,
+    current_player_query: Query<&Player, With<Movable>>,
 ) {
     if let Err(_) = button_query.get_single() {
         return;
     }
+    let Ok(player) = current_player_query.get_single() else { return; };
     let mut color = button_query.single_mut();
     if color.0 == HOVERED_BUTTON || color.clone().0 == PRESSED_BUTTON { return; }
-    if get_selected_ships(&grid).len() == 0 {
+    if get_selected_ships(&grid).len() == 0 || !can_attack(player, &grid.planets[&selected_hex.hex].owner, &diplomacy, &resources) {
         *color = DISABLED_BUTTON.into();
     } else {
         *color = NORMAL_BUTTON.into();
@@ -43,35 +58,34 @@ pub(in crate::world::actions::move_menu) fn interact_with_end_move_button(
     mut grid: ResMut<HexGrid>,
     mut switched_turn: ResMut<NextState<TurnSwitchedState>>,
     handle: Res<SpaceSipTextureAtlas>,
+    asset_server: Res<AssetServer>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut diplomacy: ResMut<Diplomacy>,
+    mut resources: ResMut<GameResources>,
     mut commands: Commands,
+    mut turn_actions: EventWriter<TurnAction>,
 ) {
     let player = current_player_query.single().clone();
     let hex_under_fight = selected_hex.hex.clone();
-    let player2 = grid.planets.get(&hex_under_fight).unwrap().owner;
-
+    let defender = grid.planets[&hex_under_fight].owner;
 
     for (interaction, mut color) in button_query.iter_mut() {
         if color.0 == DISABLED_BUTTON.into() { return; }
         match *interaction {
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON.into();
+                if !can_attack(&player, &defender, &diplomacy, &resources) { return; }
+                if diplomacy.is_at_peace(&player, &defender) {
+                    break_treaty(&player, &defender, &mut diplomacy, &mut resources, &mut game_log, round.number);
+                }
                 let army = get_selected_ships(&grid);
                 reset_selected_ships(&mut grid);
-                for ship in army.iter() {
-                    let ship = ship.clone();
-                    run_spaceship_moving_animation(ship.ship_type, ship.ship_hex, hex_under_fight.clone(), &grid, &handle, &mut commands)
-                }
                 for ship in army.iter() {
                     assert_eq!(ship.ship_owner, player.clone())
                 }
-                let mut planet_under_fight: Planet = grid.planets.remove(&hex_under_fight).unwrap();
-                let (winner, winner_army) = move_army_to_planet(player, player2, army, planet_under_fight.owner_army);
-                planet_under_fight.owner = winner;
-                planet_under_fight.owner_army = winner_army;
-                for ship in planet_under_fight.owner_army.iter_mut() {
-                    ship.ship_hex = hex_under_fight
-                }
-                grid.planets.insert(selected_hex.hex, planet_under_fight);
+                turn_actions.send(TurnAction::MoveShips { ships: army.iter().map(|ship| ship.id).collect(), destination: hex_under_fight });
+                launch_fleets(&player, army, hex_under_fight, &mut grid, &diplomacy, &mut game_log, round.number, &handle, &asset_server, &mut commands);
                 simulation_state_next_state.set(ActionsState::NoActionRunning);
                 switched_turn.set(TurnSwitchedState::OnTurnSwitched)
             }
@@ -145,6 +159,7 @@ pub(in crate::world::actions::move_menu) fn interact_with_move_ship_button(
     selected_hex: Res<SelectedHex>,
     mut event_writer: EventWriter<ShipMoved>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+    research: Res<Research>,
 ) {
     for (interaction, mut color, move_ship_button) in button_query.iter_mut() {
         let player = current_player_query.single();
@@ -159,12 +174,15 @@ pub(in crate::world::actions::move_menu) fn interact_with_move_ship_button(
                 {   // Have to insert planet back
                     let mut planet = grid.planets.remove(current_hex).unwrap();
                     let mut is_new_ship_selected_to_move = false;
-                    for ship in &mut planet.owner_army {
-                        if ship.ship_type == move_ship_button.space_ship_type && !ship.is_selected_for_move {
-                            ship.is_selected_for_move = true;
-                            is_new_ship_selected_to_move = true;
-                            if !all_ships_move { break; }
-                        }
+                    // Rookies go first, veterans are picked one by one from their own list
+                    let mut candidates: Vec<&mut SpaceShip> = planet.owner_army.iter_mut()
+                        .filter(|ship| ship.ship_type == move_ship_button.space_ship_type && !ship.is_selected_for_move && can_fly_path(ship, &selected_hex.move_path, &grid.planets, &research))
+                        .collect();
+                    candidates.sort_by_key(|ship| ship.experience);
+                    for ship in candidates {
+                        ship.is_selected_for_move = true;
+                        is_new_ship_selected_to_move = true;
+                        if !all_ships_move { break; }
                     }
                     if is_new_ship_selected_to_move {
                         event_writer.send(ShipMoved);
@@ -184,6 +202,66 @@ pub(in crate::world::actions::move_menu) fn interact_with_move_ship_button(
     }
 }
 
+pub(in crate::world::actions::move_menu) fn interact_with_move_veteran_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &MoveVeteranButton), Changed<Interaction>>,
+    mut grid: ResMut<HexGrid>,
+    selected_hex: Res<SelectedHex>,
+    mut event_writer: EventWriter<ShipMoved>,
+    current_player_query: Query<&Player, With<Movable>>,
+    research: Res<Research>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let current_hex = selected_hex.hex_selected_for_move;
+    for (interaction, mut color, veteran_button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if !selected_hex.is_selected_for_move || current_hex == HEX_NOWHERE { return; }
+                let Some(planet) = grid.planets.get(&current_hex).filter(|planet| planet.owner == *player) else { return; };
+                let Some(ship) = planet.owner_army.iter().find(|ship| ship.id == veteran_button.ship_id) else { continue; };
+                let is_selected = if ship.is_selected_for_move {
+                    false
+                } else if can_fly_path(ship, &selected_hex.move_path, &grid.planets, &research) {
+                    true
+                } else {
+                    continue;
+                };
+                let planet = grid.planets.get_mut(&current_hex).unwrap();
+                planet.owner_army.iter_mut().find(|ship| ship.id == veteran_button.ship_id).unwrap().is_selected_for_move = is_selected;
+                *color = PRESSED_BUTTON.into();
+                event_writer.send(ShipMoved);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Veterans on their way are green, the ones unable to fly the route are greyed out.
+pub(in crate::world::actions::move_menu) fn update_veteran_buttons(
+    grid: Res<HexGrid>,
+    selected_hex: Res<SelectedHex>,
+    research: Res<Research>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &MoveVeteranButton)>,
+) {
+    let Some(planet) = grid.planets.get(&selected_hex.hex_selected_for_move) else { return; };
+    for (interaction, mut color, veteran_button) in button_query.iter_mut() {
+        if *interaction != Interaction::None { continue; }
+        let Some(ship) = planet.owner_army.iter().find(|ship| ship.id == veteran_button.ship_id) else { continue; };
+        let new_color: BackgroundColor = if ship.is_selected_for_move {
+            PRESSED_BUTTON.into()
+        } else if can_fly_path(ship, &selected_hex.move_path, &grid.planets, &research) {
+            NORMAL_BUTTON.into()
+        } else {
+            DISABLED_BUTTON.into()
+        };
+        if color.0 != new_color.0 {
+            *color = new_color;
+        }
+    }
+}
+
 pub(in crate::world::actions::move_menu) fn interact_with_cancel_button(
     mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<CancelButton>)>,
     mut simulation_state_next_state: ResMut<NextState<ActionsState>>,
@@ -231,6 +309,8 @@ pub(in crate::world::actions::move_menu) fn recalculate_probability(
     current_player_query: Query<&Player, With<Movable>>,
     hex_grid: Res<HexGrid>,
     selected_hex: Res<SelectedHex>,
+    research: Res<Research>,
+    factions: Res<Factions>,
     mut text_query: Query<&mut Text, With<WinProbabilityText>>,
 ) {
     if let Err(_) = current_player_query.get_single() { return; }
@@ -243,14 +323,19 @@ pub(in crate::world::actions::move_menu) fn recalculate_probability(
         let selected_planet = &hex_grid.planets[&hex_under_fight];
         let defense_player = selected_planet.owner.clone();
         let defense_player_army = selected_planet.owner_army.clone();
+        let defense_platforms = selected_planet.count_finished(BuildingType::DefensePlatform);
+        let modifiers = BattleModifiers::of_planet(selected_planet, &factions);
         let mut number_of_success: u32 = 0;
+        // Simulations must not consume the game dice
+        let mut rng = thread_rng();
         for _ in 0..TOTAL_NUMBER_OF_SIMULATIONS {
-            let (player, _) = move_army_to_planet(current_player.clone(), defense_player.clone(), attack_player_army.clone(), defense_player_army.clone());
+            let (player, _) = move_army_to_planet(&mut rng, &research, current_player.clone(), defense_player.clone(), attack_player_army.clone(), defense_player_army.clone(), &mut defense_platforms.clone(), modifiers);
             if player.id == current_player.id {
                 number_of_success += 1;
             }
         }
         let probability = ((number_of_success as f64 / TOTAL_NUMBER_OF_SIMULATIONS as f64) * 100.0) as u32;
         text.sections[1].value = format!("{}", probability);
+        text.sections[2].value = format!("%{}", modifiers);
     }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
-use crate::space_ships::SpaceShipType;
-use crate::space_ships::SpaceShipType::{Carrier, Destroyer, Frigate};
+use hexx::Hex;
+
+use crate::space_ships::{ALL_SPACE_SHIPS, CHEVRON_ICON, get_sprite_by_type, SpaceShip, SpaceShipType, SpaceSipTextureAtlas};
 use crate::world::actions::{get_move_ship_stats_text, get_win_probability_text};
 
 use crate::world::actions::move_menu::components::*;
@@ -8,16 +9,19 @@ use crate::world::actions::move_menu::components::MoveMenu;
 use crate::world::actions::move_menu::styles::*;
 use crate::world::fonts_and_styles::colors::*;
 use crate::world::fonts_and_styles::fonts::*;
+use crate::world::ownership::CHEVRON_COLOR;
 use crate::world::player::{Movable, Player};
+use crate::world::research::Research;
 use crate::world::setup_world_grid::{HexGrid, SelectedHex};
 
 pub fn spawn_move_menu(selected_hex: Res<SelectedHex>,
                        hex_grid: Res<HexGrid>,
                        current_player_query: Query<&Player, With<Movable>>,
+                       research: Res<Research>,
                        commands: Commands,
                        asset_server: Res<AssetServer>) {
     println!("Spawning Pause Menu");
-    build_move_menu(selected_hex, hex_grid, current_player_query, commands, &asset_server);
+    build_move_menu(selected_hex, hex_grid, current_player_query, research, commands, &asset_server);
 }
 
 pub(crate) fn despawn_move_menu(
@@ -31,8 +35,10 @@ pub(crate) fn despawn_move_menu(
 
 pub(crate) fn build_move_menu(selected_hex: Res<SelectedHex>,
                               hex_grid: Res<HexGrid>,
-                              current_player_query: Query<&Player, With<Movable>>, 
+                              current_player_query: Query<&Player, With<Movable>>,
+                              research: Res<Research>,
                               mut commands: Commands, asset_server: &Res<AssetServer>) -> Entity {
+    let player = current_player_query.get_single().ok().copied().unwrap_or(Player { id: -1 });
     let move_menu_entity = commands
         .spawn((
             NodeBundle {
@@ -70,7 +76,7 @@ pub(crate) fn build_move_menu(selected_hex: Res<SelectedHex>,
                         }
                     }
                     
-                    parent.spawn((get_move_ship_stats_text(asset_server), SelectedSpaceshipsText));
+                    parent.spawn((get_move_ship_stats_text(asset_server, &research, &player), SelectedSpaceshipsText));
                     parent
                         .spawn((
                             ButtonBundle {
@@ -105,9 +111,10 @@ pub(crate) fn build_move_menu(selected_hex: Res<SelectedHex>,
                             });
                         });
                     // Main Menu Button
-                    build_move_space_ship_button(asset_server, parent, Carrier);
-                    build_move_space_ship_button(asset_server, parent, Destroyer);
-                    build_move_space_ship_button(asset_server, parent, Frigate);
+                    for space_ship_type in ALL_SPACE_SHIPS.into_iter().filter(|ship_type| research.is_ship_unlocked(&player, *ship_type)) {
+                        build_move_space_ship_button(asset_server, parent, space_ship_type);
+                    }
+                    parent.spawn((NodeBundle { style: get_veteran_list_style(), ..default() }, VeteranList));
                     // Spawn second ship type button
                     parent
                         .spawn((
@@ -167,6 +174,65 @@ fn build_move_space_ship_button(asset_server: &Res<AssetServer>, parent: &mut Ch
         });
 }
 
+/// Veterans are listed one by one, so that players can choose which of them to send.
+pub(in crate::world::actions::move_menu) fn update_veteran_list(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    handle: Res<SpaceSipTextureAtlas>,
+    selected_hex: Res<SelectedHex>,
+    hex_grid: Res<HexGrid>,
+    current_player_query: Query<&Player, With<Movable>>,
+    list_query: Query<(Entity, Ref<VeteranList>)>,
+    mut listed_hex: Local<Option<Hex>>,
+) {
+    let Ok((list_entity, list)) = list_query.get_single() else { return; };
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let source = Some(selected_hex.hex_selected_for_move).filter(|_| selected_hex.is_selected_for_move);
+    if !list.is_added() && *listed_hex == source { return; }
+    *listed_hex = source;
+
+    commands.entity(list_entity).despawn_descendants();
+    let Some(planet) = source.and_then(|hex| hex_grid.planets.get(&hex)).filter(|planet| planet.owner == *player) else { return; };
+    let mut veterans: Vec<&SpaceShip> = planet.owner_army.iter().filter(|ship| ship.is_veteran()).collect();
+    veterans.sort_by_key(|ship| std::cmp::Reverse(ship.experience));
+    commands.entity(list_entity).with_children(|parent| {
+        for ship in veterans {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: get_veteran_button_style(),
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    MoveVeteranButton { ship_id: ship.id },
+                ))
+                .with_children(|parent| {
+                    parent
+                        .spawn(AtlasImageBundle {
+                            style: get_veteran_ship_image_style(),
+                            texture_atlas: handle.spaceship_grid_atlas.clone(),
+                            texture_atlas_image: UiTextureAtlasImage { index: get_sprite_by_type(ship.ship_type).index, ..default() },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for rank in 1..=ship.rank() {
+                                parent.spawn(ImageBundle {
+                                    style: get_veteran_chevron_style(rank),
+                                    image: asset_server.load(CHEVRON_ICON).into(),
+                                    background_color: CHEVRON_COLOR.into(),
+                                    ..default()
+                                });
+                            }
+                        });
+                    parent.spawn(TextBundle::from_section(
+                        format!("{} ({} wins)", ship.ship_type, ship.experience),
+                        get_info_text_style(&asset_server),
+                    ));
+                });
+        }
+    });
+}
+
 // References
 // 1. UI Z-Index
 // https://github.com/bevyengine/bevy/blob/latest/examples/ui/z_index.rs
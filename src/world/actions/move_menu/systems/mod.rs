@@ -1,4 +1,4 @@
 pub mod interactions;
 pub mod layout;
-mod battle;
+pub(crate) mod battle;
 
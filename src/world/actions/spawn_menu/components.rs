@@ -16,4 +16,20 @@ pub(super) struct SpawnShipButton {
 }
 
 #[derive(Component)]
-pub(super) struct CancelButton;
\ No newline at end of file
+pub(super) struct CancelButton;
+
+/// Holds one row for every ship in the production queue of the selected planet.
+#[derive(Component)]
+pub(super) struct QueueList;
+
+/// Moves the order at `index` one place closer to delivery.
+#[derive(Component)]
+pub(super) struct QueueUpButton {
+    pub(crate) index: usize,
+}
+
+/// Removes the order at `index` and refunds its price.
+#[derive(Component)]
+pub(super) struct QueueCancelButton {
+    pub(crate) index: usize,
+}
\ No newline at end of file
@@ -1,6 +1,12 @@
 use bevy::asset::AssetServer;
 use bevy::prelude::{Commands, Entity, Query, Res, With};
 
+use crate::world::player::{Movable, Player};
+use crate::world::production::ProductionDraft;
+use crate::world::research::Research;
+use crate::world::resources::GameResources;
+use crate::world::setup_world_grid::{HexGrid, SelectedHex};
+
 use crate::world::actions::spawn_menu::components::SpawnMenu;
 use crate::world::actions::spawn_menu::systems::layout::{build_spawn_menu, despawn_pause_menu};
 
@@ -39,9 +45,21 @@ impl Plugin for PauseMenuPlugin {
 }
 */
 
-pub fn spawn_spawning_space_ships_window(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_spawning_space_ships_window(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    research: Res<Research>,
+    current_player_query: Query<&Player, With<Movable>>,
+    grid: Res<HexGrid>,
+    selected_hex: Res<SelectedHex>,
+    resources: Res<GameResources>,
+) {
     println!("Spawning Spawn Menu");
-    build_spawn_menu(&mut commands, &asset_server);
+    let Ok(player) = current_player_query.get_single() else { return; };
+    if let Some(planet) = grid.planets.get(&selected_hex.hex) {
+        commands.insert_resource(ProductionDraft::new(planet, &resources));
+    }
+    build_spawn_menu(&mut commands, &asset_server, &research, player);
 }
 
 pub fn despawn_spawning_space_ships_window(
@@ -49,5 +67,6 @@ pub fn despawn_spawning_space_ships_window(
     pause_menu_query: Query<Entity, With<SpawnMenu>>,
 ) {
     println!("DeSpawning Spawn Menu");
+    commands.remove_resource::<ProductionDraft>();
     despawn_pause_menu(commands, pause_menu_query)
 }
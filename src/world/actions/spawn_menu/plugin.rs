@@ -2,7 +2,8 @@ use bevy::app::{App, Update};
 use bevy::prelude::{in_state, IntoSystemConfigs, OnEnter, OnExit, Plugin};
 use crate::game_state::UpdateUI;
 use crate::world::actions::{ActionsState, spawn_menu};
-use crate::world::actions::spawn_menu::systems::interactions::{interact_cancel_button_click, interact_with_end_spawn_button, interact_with_spawn_ship_button, update_end_spawn_button_disabled, update_ships_to_buy_text};
+use crate::world::actions::spawn_menu::systems::interactions::{interact_cancel_button_click, interact_with_end_spawn_button, interact_with_queue_cancel_button, interact_with_queue_up_button, interact_with_spawn_ship_button, update_end_spawn_button_disabled, update_ships_to_buy_text};
+use crate::world::actions::spawn_menu::systems::layout::update_queue_list;
 
 pub(in crate::world::actions) struct SpawnMenuPlugin;
 
@@ -14,10 +15,12 @@ impl Plugin for SpawnMenuPlugin {
             .add_systems(Update, (
                 interact_with_end_spawn_button,
                 interact_with_spawn_ship_button,
+                interact_with_queue_up_button,
+                interact_with_queue_cancel_button,
                 interact_cancel_button_click,
                 update_end_spawn_button_disabled
             ).run_if(in_state(ActionsState::SpawningSpaceShips)).in_set(UpdateUI::UserInput))
-            .add_systems(Update, (update_ships_to_buy_text).run_if(in_state(ActionsState::SpawningSpaceShips)).in_set(UpdateUI::RenderStats))
+            .add_systems(Update, (update_ships_to_buy_text, update_queue_list).run_if(in_state(ActionsState::SpawningSpaceShips)).in_set(UpdateUI::RenderStats))
             .add_systems(OnExit(ActionsState::SpawningSpaceShips), spawn_menu::despawn_spawning_space_ships_window);
     }
 }
\ No newline at end of file
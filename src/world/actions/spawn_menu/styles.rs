@@ -21,13 +21,43 @@ pub fn get_spawn_menu_container_style() -> Style {
         justify_content: JustifyContent::Center,
         align_items: AlignItems::Center,
         width: Val::Px(250.0),
-        height: Val::Px(400.0),
+        min_height: Val::Px(400.0),
         row_gap: Val::Px(8.0),
         column_gap: Val::Px(8.0),
         ..Style::DEFAULT
     }
 }
 
+pub fn get_queue_list_style() -> Style {
+    Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(4.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_queue_row_style() -> Style {
+    Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(4.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_queue_button_style() -> Style {
+    Style {
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Px(30.0),
+        height: Val::Px(30.0),
+        ..Style::DEFAULT
+    }
+}
+
 pub fn get_button_style() -> Style {
     Style {
         justify_content: JustifyContent::Center,
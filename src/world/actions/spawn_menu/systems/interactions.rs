@@ -1,25 +1,33 @@
 use bevy::prelude::*;
 
-use crate::space_ships::{get_count_spaceship_dict, SpaceShip, SpaceShipCharacteristics, SpaceShipType};
+use crate::space_ships::{ALL_SPACE_SHIPS, SpaceShipType};
 use crate::ui::action_panel::plugin::TurnSwitchedState;
-use crate::world::actions::{ActionsState, get_spaceship_index_by_type, reset_selected_for_buy_ships};
-use crate::world::actions::spawn_menu::components::{CancelButton, EndSpawnButton, ShipsToBuyText, SpawnShipButton};
+use crate::ui::stats::resources::Round;
+use crate::world::actions::{ActionsState, get_spaceship_index_by_type};
+use crate::world::actions::spawn_menu::components::{CancelButton, EndSpawnButton, QueueCancelButton, QueueUpButton, ShipsToBuyText, SpawnShipButton};
 use crate::world::fonts_and_styles::colors::*;
+use crate::world::game_log::{GameEvent, GameLog};
 use crate::world::player::{Movable, Player};
+use crate::world::production::{change_production, ProductionChange, ProductionDraft};
+use crate::world::research::Research;
 use crate::world::resources::GameResources;
-use crate::world::setup_world_grid::{HexGrid, SelectedHex};
+use crate::world::setup_world_grid::{HexGrid, Planet, SelectedHex};
+use crate::world::turn_actions::TurnAction;
 
 
+/// The queue can only be confirmed once it differs from what the planet had when the menu was opened.
 pub(in crate::world::actions::spawn_menu) fn update_end_spawn_button_disabled(
     mut button_query: Query<&mut BackgroundColor, With<EndSpawnButton>>,
-    hex_grid: ResMut<HexGrid>,
+    hex_grid: Res<HexGrid>,
+    draft: Option<Res<ProductionDraft>>,
 ) {
     if let Err(_) = button_query.get_single() {
         return;
     }
     let mut color = button_query.single_mut();
     if color.0 == HOVERED_BUTTON.into() || color.0 == PRESSED_BUTTON.into() { return; }
-    if hex_grid.planets.iter().any(|(hex, planet)| planet.owner_army.iter().any(|unit| unit.is_selected_for_buy)) {
+    let is_changed = draft.map_or(false, |draft| hex_grid.planets.get(&draft.hex).map_or(false, |planet| planet.production != draft.queue));
+    if is_changed {
         *color = NORMAL_BUTTON.into();
     } else {
         *color = DISABLED_BUTTON.into();
@@ -31,17 +39,27 @@ pub(in crate::world::actions::spawn_menu) fn interact_with_end_spawn_button(
         (&Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<EndSpawnButton>),
     >,
-    mut hex_grid: ResMut<HexGrid>,
+    hex_grid: Res<HexGrid>,
+    draft: Option<Res<ProductionDraft>>,
     mut simulation_state_next_state: ResMut<NextState<ActionsState>>,
     mut switched_turn: ResMut<NextState<TurnSwitchedState>>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut turn_actions: EventWriter<TurnAction>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
         if color.0 == DISABLED_BUTTON.into() { return; }
         match *interaction {
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON.into();
-                for (_, mut planet) in hex_grid.planets.iter_mut() {
-                    planet.owner_army.iter_mut().for_each(|unit| unit.is_selected_for_buy = false)
+                let player = current_player_query.single();
+                if let Some(draft) = draft.as_ref() {
+                    let ordered = hex_grid.planets.get(&draft.hex).map(|planet| draft.ordered(planet)).unwrap_or_default();
+                    if !ordered.is_empty() {
+                        game_log.record(round.number, *player, GameEvent::ShipsBought { hex: draft.hex, ships: ordered });
+                    }
+                    turn_actions.send(TurnAction::ChangeProduction { hex: draft.hex, changes: draft.changes.clone() });
                 }
                 simulation_state_next_state.set(ActionsState::NoActionRunning);
                 switched_turn.set(TurnSwitchedState::OnTurnSwitched)
@@ -57,6 +75,7 @@ pub(in crate::world::actions::spawn_menu) fn interact_with_end_spawn_button(
 }
 
 
+/// Counts of every ship type waiting in the queue of the selected planet.
 pub(in crate::world::actions::spawn_menu) fn update_ships_to_buy_text(
     grid: Res<HexGrid>,
     selected_hex: Res<SelectedHex>,
@@ -64,60 +83,27 @@ pub(in crate::world::actions::spawn_menu) fn update_ships_to_buy_text(
     game_state: Res<State<ActionsState>>,
 ) {
     if !selected_hex.is_selected || *game_state != ActionsState::SpawningSpaceShips { return; }
+    let Some(planet) = grid.planets.get(&selected_hex.hex) else { return; };
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
 
-    let ships_to_buy = get_all_ships_to_buy(&grid, &selected_hex);
-    let dict = get_count_spaceship_dict(ships_to_buy);
-
-    let mut text = text_query.single_mut();
-
-    for (t, count) in dict {
-        let index = get_spaceship_index_by_type(t);
-        text.sections.get_mut(index).unwrap().value = format!("{count}");
-    }
-}
-
-fn get_all_ships_to_buy(grid: &Res<HexGrid>, selected_hex: &Res<SelectedHex>) -> Vec<SpaceShip> {
-    let planet = &grid.planets[&selected_hex.hex];
-
-    let army = planet.owner_army.clone();
-    let mut vec: Vec<SpaceShip> = vec![];
-    for mut spaceship in army.iter() {
-        if spaceship.is_selected_for_buy {
-            vec.push(spaceship.clone())
+    for ship_type in ALL_SPACE_SHIPS {
+        let count = planet.production.iter().filter(|queued| queued.ship_type == ship_type).count();
+        let index = get_spaceship_index_by_type(ship_type);
+        let value = format!("{count}");
+        if text.sections[index].value != value {
+            text.sections[index].value = value;
         }
     }
-    vec
 }
 
 
-fn buy_ship(resources: &mut ResMut<GameResources>, grid: &mut ResMut<HexGrid>, selected_hex: &mut ResMut<SelectedHex>, player: &Player, interaction: &Interaction, color: &mut BackgroundColor, space_ship_type: SpaceShipType) -> bool {
-    let mut player_resources = &resources.resources[player];
+fn queue_ship(research: &Research, resources: &mut ResMut<GameResources>, grid: &mut ResMut<HexGrid>, draft: &mut ProductionDraft, selected_hex: &ResMut<SelectedHex>, player: &Player, interaction: &Interaction, color: &mut BackgroundColor, space_ship_type: SpaceShipType) -> bool {
     match *interaction {
         Interaction::Pressed => {
             *color = PRESSED_BUTTON.into();
             if !selected_hex.is_selected { return false; }
-
-            let spaceship = SpaceShip {
-                ship_type: space_ship_type,
-                ship_owner: player.clone(),
-                ship_hex: selected_hex.hex.clone(),
-                is_selected_for_move: false,
-                is_selected_for_buy: true,
-            };
-            let price = SpaceShipCharacteristics::get_by_spaceship_type(spaceship.ship_type).price;
-
-            if player_resources.influence < price.influence || player_resources.resources < price.resources { return false; }
-            let mut player_resources = resources.resources.remove(player).unwrap();
-            player_resources -= price;
-
-            resources.resources.insert(player.clone(), player_resources);
-            let mut planet = grid.planets.remove(&selected_hex.hex).unwrap();
-            planet.owner_army.push(spaceship);
-            grid.planets.insert(selected_hex.hex.clone(), planet);
-            resources.set_changed();
-            grid.set_changed();
-            selected_hex.set_changed();
-            true
+            let Some(planet) = grid.planets.get_mut(&selected_hex.hex) else { return false; };
+            change_draft(planet, player, resources, research, draft, ProductionChange::Queue(space_ship_type))
         }
         Interaction::Hovered => {
             *color = HOVERED_BUTTON.into();
@@ -130,6 +116,15 @@ fn buy_ship(resources: &mut ResMut<GameResources>, grid: &mut ResMut<HexGrid>, s
     }
 }
 
+/// Changes the queue and remembers the change, so that it can be sent to the server.
+fn change_draft(planet: &mut Planet, player: &Player, resources: &mut GameResources, research: &Research, draft: &mut ProductionDraft, change: ProductionChange) -> bool {
+    let is_changed = change_production(planet, player, resources, research, change);
+    if is_changed {
+        draft.changes.push(change);
+    }
+    is_changed
+}
+
 pub(in crate::world::actions::spawn_menu) fn interact_with_spawn_ship_button(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor, &SpawnShipButton),
@@ -138,14 +133,75 @@ pub(in crate::world::actions::spawn_menu) fn interact_with_spawn_ship_button(
     keyboard_input: Res<Input<KeyCode>>, // Include keyboard input for shortcut detection
     mut resources: ResMut<GameResources>,
     mut grid: ResMut<HexGrid>,
-    mut selected_hex: ResMut<SelectedHex>,
+    selected_hex: ResMut<SelectedHex>,
     current_player_query: Query<&Player, (With<Player>, With<Movable>)>,
+    research: Res<Research>,
+    draft: Option<ResMut<ProductionDraft>>,
 ) {
+    let Some(mut draft) = draft else { return; };
     for (interaction, mut color, spawn_ship_button) in button_query.iter_mut() {
         let player = current_player_query.single();
         if keyboard_input.pressed(KeyCode::Space) {
-            while buy_ship(&mut resources, &mut grid, &mut selected_hex, player, interaction, &mut color, spawn_ship_button.space_ship_type) {}
-        } else { buy_ship(&mut resources, &mut grid, &mut selected_hex, player, interaction, &mut color, spawn_ship_button.space_ship_type); }
+            while queue_ship(&research, &mut resources, &mut grid, &mut draft, &selected_hex, player, interaction, &mut color, spawn_ship_button.space_ship_type) {}
+        } else { queue_ship(&research, &mut resources, &mut grid, &mut draft, &selected_hex, player, interaction, &mut color, spawn_ship_button.space_ship_type); }
+    }
+}
+
+pub(in crate::world::actions::spawn_menu) fn interact_with_queue_up_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &QueueUpButton), Changed<Interaction>>,
+    mut grid: ResMut<HexGrid>,
+    mut resources: ResMut<GameResources>,
+    selected_hex: Res<SelectedHex>,
+    current_player_query: Query<&Player, With<Movable>>,
+    research: Res<Research>,
+    draft: Option<ResMut<ProductionDraft>>,
+) {
+    let Some(mut draft) = draft else { return; };
+    let Ok(player) = current_player_query.get_single() else { return; };
+    for (interaction, mut color, button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if let Some(planet) = grid.planets.get_mut(&selected_hex.hex) {
+                    change_draft(planet, player, &mut resources, &research, &mut draft, ProductionChange::MoveUp(button.index));
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Cancelled orders are refunded in full, even the ones already partly built.
+pub(in crate::world::actions::spawn_menu) fn interact_with_queue_cancel_button(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &QueueCancelButton), Changed<Interaction>>,
+    mut grid: ResMut<HexGrid>,
+    mut resources: ResMut<GameResources>,
+    selected_hex: Res<SelectedHex>,
+    current_player_query: Query<&Player, With<Movable>>,
+    research: Res<Research>,
+    draft: Option<ResMut<ProductionDraft>>,
+) {
+    let Some(mut draft) = draft else { return; };
+    let Ok(player) = current_player_query.get_single() else { return; };
+    for (interaction, mut color, button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                let Some(planet) = grid.planets.get_mut(&selected_hex.hex) else { continue; };
+                change_draft(planet, player, &mut resources, &research, &mut draft, ProductionChange::Cancel(button.index));
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
     }
 }
 
@@ -155,6 +211,7 @@ pub(in crate::world::actions::spawn_menu) fn interact_cancel_button_click(
     mut action_state: ResMut<NextState<ActionsState>>,
     mut hex_grid: ResMut<HexGrid>,
     mut game_resources: ResMut<GameResources>,
+    draft: Option<Res<ProductionDraft>>,
 ) {
     if let Err(_) = button_query.get_single() {
         return;
@@ -164,7 +221,9 @@ pub(in crate::world::actions::spawn_menu) fn interact_cancel_button_click(
     match interaction {
         Interaction::Pressed => {
             *color = PRESSED_BUTTON.into();
-            reset_selected_for_buy_ships(&mut hex_grid, &mut game_resources);
+            if let Some(draft) = draft {
+                draft.restore(&mut hex_grid, &mut game_resources);
+            }
             action_state.set(ActionsState::NoActionRunning);
         }
         Interaction::Hovered => {
@@ -1,18 +1,16 @@
 use bevy::prelude::*;
-use crate::space_ships::SpaceShipType;
-use crate::space_ships::SpaceShipType::{Carrier, Destroyer, Frigate};
+use crate::space_ships::{ALL_SPACE_SHIPS, SpaceShipType};
 use crate::world::actions::get_buy_ship_stats_text;
+use crate::world::production::QueuedShip;
+use crate::world::setup_world_grid::{HexGrid, SelectedHex};
 
 use crate::world::actions::spawn_menu::components::*;
 use crate::world::actions::spawn_menu::components::SpawnMenu;
 use crate::world::actions::spawn_menu::styles::*;
 use crate::world::fonts_and_styles::colors::*;
 use crate::world::fonts_and_styles::fonts::*;
-
-pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    println!("Spawning Pause Menu");
-    build_spawn_menu(&mut commands, &asset_server);
-}
+use crate::world::player::Player;
+use crate::world::research::Research;
 
 pub(crate) fn despawn_pause_menu(
     mut commands: Commands,
@@ -24,7 +22,7 @@ pub(crate) fn despawn_pause_menu(
 }
 
 // System Piping Example
-pub(crate) fn build_spawn_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+pub(crate) fn build_spawn_menu(commands: &mut Commands, asset_server: &Res<AssetServer>, research: &Research, player: &Player) -> Entity {
     let pause_menu_entity = commands
         .spawn((
             NodeBundle {
@@ -46,7 +44,7 @@ pub(crate) fn build_spawn_menu(commands: &mut Commands, asset_server: &Res<Asset
                     parent.spawn(TextBundle {
                         text: Text {
                             sections: vec![TextSection::new(
-                                "Spawn Menu",
+                                "Production",
                                 get_title_text_style(&asset_server),
                             )],
                             alignment: TextAlignment::Center,
@@ -54,7 +52,7 @@ pub(crate) fn build_spawn_menu(commands: &mut Commands, asset_server: &Res<Asset
                         },
                         ..default()
                     });
-                    parent.spawn((get_buy_ship_stats_text(&asset_server), ShipsToBuyText));
+                    parent.spawn((get_buy_ship_stats_text(&asset_server, research, player), ShipsToBuyText));
                     parent
                         .spawn((
                             ButtonBundle {
@@ -69,7 +67,7 @@ pub(crate) fn build_spawn_menu(commands: &mut Commands, asset_server: &Res<Asset
                                 style: Style { ..default() },
                                 text: Text {
                                     sections: vec![TextSection::new(
-                                        "Perform buy",
+                                        "Confirm queue",
                                         get_button_text_style(&asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
@@ -79,9 +77,10 @@ pub(crate) fn build_spawn_menu(commands: &mut Commands, asset_server: &Res<Asset
                             });
                         });
 
-                    spawn_ship_button(&asset_server, parent, Carrier);
-                    spawn_ship_button(&asset_server, parent, Destroyer);
-                    spawn_ship_button(&asset_server, parent, Frigate);
+                    for space_ship_type in ALL_SPACE_SHIPS.into_iter().filter(|ship_type| research.is_ship_unlocked(player, *ship_type)) {
+                        spawn_ship_button(&asset_server, parent, space_ship_type);
+                    }
+                    parent.spawn((NodeBundle { style: get_queue_list_style(), ..default() }, QueueList));
                     parent
                         .spawn((
                             ButtonBundle {
@@ -129,7 +128,7 @@ fn spawn_ship_button(asset_server: &&Res<AssetServer>, parent: &mut ChildBuilder
                 style: Style { ..default() },
                 text: Text {
                     sections: vec![TextSection::new(
-                        format!("Spawn {}", space_ship_type),
+                        format!("Queue {}", space_ship_type),
                         get_button_text_style(&asset_server),
                     )],
                     alignment: TextAlignment::Center,
@@ -140,6 +139,54 @@ fn spawn_ship_button(asset_server: &&Res<AssetServer>, parent: &mut ChildBuilder
         });
 }
 
+/// One row per queued ship with buttons to move it up or cancel it, rebuilt whenever the queue changes.
+pub(in crate::world::actions::spawn_menu) fn update_queue_list(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected_hex: Res<SelectedHex>,
+    hex_grid: Res<HexGrid>,
+    list_query: Query<(Entity, Ref<QueueList>)>,
+    mut listed_queue: Local<Vec<QueuedShip>>,
+) {
+    let Ok((list_entity, list)) = list_query.get_single() else { return; };
+    let Some(planet) = hex_grid.planets.get(&selected_hex.hex) else { return; };
+    if !list.is_added() && *listed_queue == planet.production { return; }
+    *listed_queue = planet.production.clone();
+
+    commands.entity(list_entity).despawn_descendants();
+    commands.entity(list_entity).with_children(|parent| {
+        for (index, queued) in planet.production.iter().enumerate() {
+            parent
+                .spawn(NodeBundle { style: get_queue_row_style(), ..default() })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        format!("{}. {} ({} turns)", index + 1, queued.ship_type, queued.turns_left),
+                        get_info_text_style(&asset_server),
+                    ));
+                    if index > 0 {
+                        spawn_queue_button(&asset_server, parent, "^", QueueUpButton { index });
+                    }
+                    spawn_queue_button(&asset_server, parent, "X", QueueCancelButton { index });
+                });
+        }
+    });
+}
+
+fn spawn_queue_button(asset_server: &Res<AssetServer>, parent: &mut ChildBuilder, label: &str, button: impl Component) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: get_queue_button_style(),
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(label, get_info_text_style(asset_server)));
+        });
+}
+
 // References
 // 1. UI Z-Index
 // https://github.com/bevyengine/bevy/blob/latest/examples/ui/z_index.rs
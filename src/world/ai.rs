@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use hexx::Hex;
+
+use crate::game_state::UpdateUI;
+use crate::space_ships::{ALL_SPACE_SHIPS, SpaceShip, SpaceShipType, SpaceSipTextureAtlas};
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::handover::plugin::HandoverState;
+use crate::ui::stats::resources::Round;
+use crate::world::defense::BattleModifiers;
+use crate::world::diplomacy::{answer_proposal, Diplomacy};
+use crate::world::factions::Factions;
+use crate::world::fleets::launch_fleets;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::pathfinding::{can_fly_path, find_path};
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+use crate::world::production::can_queue;
+use crate::world::research::Research;
+use crate::world::resources::GameResources;
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::supply::Supply;
+use crate::world::victory::GameOutcome;
+
+/// Power an attack needs over the defenders, in percent, before the computer risks it.
+const ATTACK_MARGIN_PERCENT: u32 = 150;
+/// Seconds between two moves when nobody but the computer plays, so the game can be followed.
+const WATCHED_MOVE_INTERVAL: f32 = 0.6;
+
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AiPlayers>()
+            .add_systems(Update, play_ai_turn.run_if(in_state(HandoverState::Hidden)).run_if(is_ai_move_due).in_set(UpdateUI::UserInput));
+    }
+}
+
+/// Seats played by the computer, scenarios fill it.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct AiPlayers {
+    pub players: Vec<Player>,
+}
+
+impl AiPlayers {
+    pub(crate) fn contains(&self, player: &Player) -> bool {
+        self.players.contains(player)
+    }
+
+    pub(crate) fn plays_all(&self, players: &[Player]) -> bool {
+        !players.is_empty() && players.iter().all(|player| self.contains(player))
+    }
+}
+
+/// Nobody at the screen plays, the computer sides are only watched.
+pub(crate) fn is_watched_game(ai_players: Res<AiPlayers>, turn_order: Res<TurnOrder>) -> bool {
+    ai_players.plays_all(&turn_order.players)
+}
+
+fn is_ai_move_due(
+    time: Res<Time>,
+    ai_players: Res<AiPlayers>,
+    turn_order: Res<TurnOrder>,
+    mut interval: Local<Option<Timer>>,
+) -> bool {
+    if !ai_players.plays_all(&turn_order.players) { return true; }
+    let interval = interval.get_or_insert_with(|| Timer::from_seconds(WATCHED_MOVE_INTERVAL, TimerMode::Repeating));
+    interval.tick(time.delta()).just_finished()
+}
+
+fn army_power(army: &[SpaceShip], research: &Research, power: impl Fn(u32) -> u32) -> u32 {
+    army.iter()
+        .map(|ship| power(research.ship_characteristics(&ship.ship_owner, ship.ship_type).power + ship.rank()))
+        .sum()
+}
+
+/// The richest planet one of the armies of `player` can reach and clearly beat, as `(from, to, ships)`.
+/// Only the ships with the range for the whole path are sent, so only they count for the attack.
+fn choose_attack(player: &Player, grid: &HexGrid, research: &Research, diplomacy: &Diplomacy, factions: &Factions) -> Option<(Hex, Hex, Vec<u32>)> {
+    let mut best: Option<(u32, Hex, Hex, Vec<u32>)> = None;
+    for (source, planet) in grid.planets.iter().filter(|(_, planet)| planet.owner == *player && !planet.owner_army.is_empty()) {
+        for (target, target_planet) in grid.planets.iter() {
+            if target_planet.owner == *player || diplomacy.treaty(player, &target_planet.owner).is_some() { continue; }
+            let value = target_planet.resource + target_planet.influence;
+            if value == 0 || best.as_ref().map_or(false, |(best_value, ..)| *best_value >= value) { continue; }
+            let Some(path) = find_path(player, *source, *target, &grid.planets, diplomacy) else { continue; };
+            let attackers: Vec<SpaceShip> = planet.owner_army.iter()
+                .filter(|ship| can_fly_path(ship, &path, &grid.planets, research))
+                .copied()
+                .collect();
+            if attackers.is_empty() { continue; }
+
+            let modifiers = BattleModifiers::of_planet(target_planet, factions);
+            let attack = army_power(&attackers, research, |power| modifiers.attack_power(power));
+            let defense = army_power(&target_planet.owner_army, research, |power| modifiers.defense_power(power));
+            if attack * 100 >= defense * ATTACK_MARGIN_PERCENT {
+                best = Some((value, *source, *target, attackers.iter().map(|ship| ship.id).collect()));
+            }
+        }
+    }
+    best.map(|(_, source, target, ships)| (source, target, ships))
+}
+
+/// Spends everything on the strongest ships the first shipyard of `player` can build.
+/// Shipyards cut off from supply are passed over, the others are tried in hex order.
+fn order_ships(player: &Player, grid: &mut HexGrid, resources: &mut GameResources, research: &Research, supply: &Supply) -> Option<(Hex, Vec<SpaceShipType>)> {
+    let mut shipyards: Vec<Hex> = grid.planets.iter()
+        .filter(|(hex, planet)| planet.owner == *player && planet.has_shipyard() && !supply.is_cut_off(hex))
+        .map(|(hex, _)| *hex)
+        .collect();
+    shipyards.sort_by_key(|hex| (hex.x, hex.y));
+    let planet = grid.planets.get_mut(shipyards.first()?)?;
+    let mut ordered = vec![];
+    loop {
+        let player_resources = resources.resources.get(player).copied().unwrap_or_default();
+        let strongest = ALL_SPACE_SHIPS.into_iter()
+            .filter(|ship_type| can_queue(planet, player, &player_resources, research, *ship_type))
+            .max_by_key(|ship_type| research.ship_characteristics(player, *ship_type).power);
+        let Some(ship_type) = strongest else { break; };
+        let price = research.ship_characteristics(player, ship_type).price;
+        planet.queue_ship(ship_type, price);
+        resources.resources.insert(*player, player_resources - price);
+        ordered.push(ship_type);
+    }
+    if ordered.is_empty() { None } else { Some((planet.hex, ordered)) }
+}
+
+/// Trades are only taken when they pay off, treaties and gifts are always welcome.
+/// What the computer can't pay for is declined, so the proposer gets their cost back.
+fn answer_proposals(player: &Player, diplomacy: &mut Diplomacy, resources: &mut GameResources, game_log: &mut GameLog, round: i32) {
+    for proposal in diplomacy.incoming(player) {
+        let accept = proposal.kind.is_favourable();
+        if !answer_proposal(diplomacy, resources, proposal, accept, game_log, round) {
+            answer_proposal(diplomacy, resources, proposal, false, game_log, round);
+        }
+    }
+}
+
+/// The computer answers its proposals first, then attacks when it is sure to win,
+/// builds ships otherwise and skips the round when it can do neither.
+fn play_ai_turn(
+    mut commands: Commands,
+    mut current_player_query: Query<(&Player, &mut Stats), With<Movable>>,
+    ai_players: Res<AiPlayers>,
+    mut grid: ResMut<HexGrid>,
+    mut resources: ResMut<GameResources>,
+    research: Res<Research>,
+    supply: Res<Supply>,
+    mut diplomacy: ResMut<Diplomacy>,
+    factions: Res<Factions>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    handle: Res<SpaceSipTextureAtlas>,
+    asset_server: Res<AssetServer>,
+    outcome: Option<Res<GameOutcome>>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    mut next_turn_switched_state: ResMut<NextState<TurnSwitchedState>>,
+) {
+    if outcome.is_some() || ai_players.players.is_empty() { return; }
+    if *turn_switched_state.get() != TurnSwitchedState::OnDefaultState { return; }
+    let Ok((player, mut stats)) = current_player_query.get_single_mut() else { return; };
+    if !ai_players.contains(player) { return; }
+
+    answer_proposals(player, &mut diplomacy, &mut resources, &mut game_log, round.number);
+    if let Some((source, target, ships)) = choose_attack(player, &grid, &research, &diplomacy, &factions) {
+        let planet = grid.planets.get_mut(&source).unwrap();
+        let (army, staying) = std::mem::take(&mut planet.owner_army).into_iter().partition(|ship| ships.contains(&ship.id));
+        planet.owner_army = staying;
+        launch_fleets(player, army, target, &mut grid, &diplomacy, &mut game_log, round.number, &handle, &asset_server, &mut commands);
+    } else if let Some((hex, ships)) = order_ships(player, &mut grid, &mut resources, &research, &supply) {
+        game_log.record(round.number, *player, GameEvent::ShipsBought { hex, ships });
+    } else {
+        stats.moves_left = 1;
+        game_log.record(round.number, *player, GameEvent::RoundSkipped);
+    }
+    next_turn_switched_state.set(TurnSwitchedState::OnTurnSwitched);
+}
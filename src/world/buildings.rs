@@ -0,0 +1,225 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::UpdateUI;
+use crate::ui::stats::resources::Round;
+use crate::world::fog_of_war::{FogOfWar, update_fog_of_war, Viewer};
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Movable, Player};
+use crate::world::resources::{GameResources, PlayerResources};
+use crate::world::setup_world_grid::{create_sprite_bundle_with_image, HexGrid, Planet};
+
+/// How many buildings, finished or not, fit on a single planet.
+pub(crate) const BUILDING_SLOTS: usize = 3;
+
+const UNDER_CONSTRUCTION_ALPHA: f32 = 0.35;
+
+pub struct BuildingsPlugin;
+
+impl Plugin for BuildingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, update_building_icons.in_set(UpdateUI::RenderStats).after(update_fog_of_war));
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum BuildingType {
+    Mine,
+    Embassy,
+    Shipyard,
+    DefensePlatform,
+}
+
+pub(crate) const ALL_BUILDINGS: [BuildingType; 4] = [BuildingType::Mine, BuildingType::Embassy, BuildingType::Shipyard, BuildingType::DefensePlatform];
+
+impl fmt::Display for BuildingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            BuildingType::Mine => { "Mine" }
+            BuildingType::Embassy => { "Embassy" }
+            BuildingType::Shipyard => { "Shipyard" }
+            BuildingType::DefensePlatform => { "Defense platform" }
+        })
+    }
+}
+
+pub struct BuildingCharacteristics {
+    pub price: PlayerResources,
+    /// Turns of the owner needed until the building works.
+    pub build_time: u32,
+    /// Added to the planet income at the start of every turn of the owner.
+    pub income: PlayerResources,
+    /// Power the building fights with when the planet is attacked, 0 if it does not fight.
+    pub power: u32,
+    /// Whether the building stays when the planet is captured.
+    pub survives_capture: bool,
+    pub icon: &'static str,
+}
+
+impl BuildingCharacteristics {
+    pub fn get_by_building_type(building_type: BuildingType) -> Self {
+        match building_type {
+            BuildingType::Mine => BuildingCharacteristics { price: PlayerResources { influence: 0, resources: 8 }, build_time: 2, income: PlayerResources { influence: 0, resources: 3 }, power: 0, survives_capture: true, icon: "resource_iron.png" },
+            BuildingType::Embassy => BuildingCharacteristics { price: PlayerResources { influence: 3, resources: 4 }, build_time: 2, income: PlayerResources { influence: 2, resources: 0 }, power: 0, survives_capture: true, icon: "flag_triangle.png" },
+            BuildingType::Shipyard => BuildingCharacteristics { price: PlayerResources { influence: 0, resources: 10 }, build_time: 3, income: PlayerResources::default(), power: 0, survives_capture: false, icon: "structure_gate.png" },
+            BuildingType::DefensePlatform => BuildingCharacteristics { price: PlayerResources { influence: 0, resources: 6 }, build_time: 1, income: PlayerResources::default(), power: 7, survives_capture: false, icon: "shield.png" },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Building {
+    pub building_type: BuildingType,
+    /// Turns of the owner left until the building is finished, 0 once it works.
+    pub turns_left: u32,
+}
+
+impl Building {
+    pub(crate) fn new(building_type: BuildingType) -> Self {
+        Self { building_type, turns_left: BuildingCharacteristics::get_by_building_type(building_type).build_time }
+    }
+
+    pub(crate) fn finished(building_type: BuildingType) -> Self {
+        Self { building_type, turns_left: 0 }
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.turns_left == 0
+    }
+}
+
+/// Icon of a building drawn on the hex, they are redrawn whenever the buildings of the hex change.
+#[derive(Component)]
+pub(crate) struct BuildingIcon {
+    pub hex: Hex,
+}
+
+impl Planet {
+    pub(crate) fn count_finished(&self, building_type: BuildingType) -> usize {
+        self.buildings.iter().filter(|building| building.building_type == building_type && building.is_finished()).count()
+    }
+
+    pub(crate) fn has_free_building_slot(&self) -> bool {
+        self.buildings.len() < BUILDING_SLOTS
+    }
+
+    pub(crate) fn has_shipyard(&self) -> bool {
+        self.count_finished(BuildingType::Shipyard) > 0
+    }
+
+    /// What the planet gives to its owner every turn, buildings included.
+    pub(crate) fn income(&self) -> PlayerResources {
+        let mut income = PlayerResources { influence: self.influence, resources: self.resource };
+        for building in self.buildings.iter().filter(|building| building.is_finished()) {
+            income += BuildingCharacteristics::get_by_building_type(building.building_type).income;
+        }
+        income
+    }
+
+    /// Economic buildings are taken over by the new owner, military ones, unfinished constructions,
+    /// the production queue and the fortification are lost.
+    pub(crate) fn on_captured(&mut self) {
+        self.production.clear();
+        self.fortified = false;
+        self.buildings.retain(|building| building.is_finished() && BuildingCharacteristics::get_by_building_type(building.building_type).survives_capture);
+    }
+
+    /// Removes finished buildings of `building_type` until only `left` remain.
+    pub(crate) fn destroy_buildings(&mut self, building_type: BuildingType, left: usize) {
+        let mut to_destroy = self.count_finished(building_type).saturating_sub(left);
+        self.buildings.retain(|building| {
+            if to_destroy > 0 && building.building_type == building_type && building.is_finished() {
+                to_destroy -= 1;
+                return false;
+            }
+            true
+        });
+    }
+}
+
+/// Whether `player` can afford and has room to start a `building_type` construction on `planet`.
+pub(crate) fn can_build(planet: &Planet, player: &Player, player_resources: &PlayerResources, building_type: BuildingType) -> bool {
+    let price = BuildingCharacteristics::get_by_building_type(building_type).price;
+    planet.owner == *player
+        && planet.has_free_building_slot()
+        && player_resources.resources >= price.resources
+        && player_resources.influence >= price.influence
+}
+
+/// Pays for a `building_type` construction on `planet` and starts it, returns whether `player` could.
+pub(crate) fn start_building(planet: &mut Planet, player: &Player, resources: &mut GameResources, building_type: BuildingType, game_log: &mut GameLog, round: i32) -> bool {
+    let Some(player_resources) = resources.resources.get_mut(player) else { return false; };
+    if !can_build(planet, player, player_resources, building_type) { return false; }
+    *player_resources -= BuildingCharacteristics::get_by_building_type(building_type).price;
+    planet.buildings.push(Building::new(building_type));
+    game_log.record(round, *player, GameEvent::BuildingStarted { hex: planet.hex, building: building_type });
+    true
+}
+
+pub(crate) fn advance_construction(
+    player_query: Query<&Player, With<Movable>>,
+    mut grid: ResMut<HexGrid>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+    advance_buildings(player, &mut grid, &mut game_log, round.number);
+}
+
+/// Constructions of the player who starts their turn get one turn closer to being finished.
+pub(crate) fn advance_buildings(player: &Player, grid: &mut HexGrid, game_log: &mut GameLog, round: i32) {
+    for (hex, planet) in grid.planets.iter_mut().filter(|(_, planet)| planet.owner == *player) {
+        for building in planet.buildings.iter_mut().filter(|building| !building.is_finished()) {
+            building.turns_left -= 1;
+            if building.is_finished() {
+                game_log.record(round, *player, GameEvent::BuildingFinished { hex: *hex, building: building.building_type });
+            }
+        }
+    }
+}
+
+fn update_building_icons(
+    mut commands: Commands,
+    grid: Res<HexGrid>,
+    fog: Res<FogOfWar>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    icons_query: Query<(Entity, &BuildingIcon)>,
+    asset_server: Res<AssetServer>,
+    mut shown: Local<HashMap<Hex, Vec<Building>>>,
+) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
+    for (hex, planet) in grid.planets.iter() {
+        // Buildings of the enemies are only known while their planet is in sensor range
+        let is_known = player.as_ref().map_or(true, |player| planet.owner == *player || fog.is_visible(player, hex));
+        let buildings = if is_known { planet.buildings.clone() } else { vec![] };
+        if shown.get(hex).map_or(buildings.is_empty(), |shown| *shown == buildings) { continue; }
+
+        for (entity, _) in icons_query.iter().filter(|(_, icon)| icon.hex == *hex) {
+            commands.entity(entity).despawn_recursive();
+        }
+        let Some(entity) = grid.entities.get(hex).copied() else { continue; };
+        commands.entity(entity).with_children(|parent| {
+            for (index, building) in buildings.iter().enumerate() {
+                parent.spawn((create_building_sprite_bundle(&asset_server, building, index), BuildingIcon { hex: *hex }));
+            }
+        });
+        shown.insert(*hex, buildings);
+    }
+}
+
+fn create_building_sprite_bundle(asset_server: &Res<AssetServer>, building: &Building, index: usize) -> SpriteBundle {
+    let image_path = format!("kenney - Simpe Icons/{}", BuildingCharacteristics::get_by_building_type(building.building_type).icon);
+    let transform = Transform {
+        translation: Vec3::new(-20. + 15. * index as f32, -38., 1.0),
+        scale: Vec3::splat(0.1),
+        ..Default::default()
+    };
+    let color = if building.is_finished() { Color::WHITE } else { Color::WHITE.with_a(UNDER_CONSTRUCTION_ALPHA) };
+    create_sprite_bundle_with_image(asset_server, image_path, transform, color)
+}
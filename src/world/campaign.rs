@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::AppState;
+use crate::world::data::load_data;
+use crate::world::player::Player;
+use crate::world::scenario::Scenario;
+use crate::world::victory::GameOutcome;
+
+/// Campaigns are described in data files, `--campaign <id>` plays `assets/data/campaigns/<id>.json`.
+const CAMPAIGNS_DIR: &str = "data/campaigns";
+/// Progress is kept between sessions, one file per campaign.
+const SAVES_DIR: &str = "saves";
+
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::GameOver), save_campaign_progress.run_if(resource_exists::<CampaignRun>()));
+    }
+}
+
+/// Scenarios played one after another, each one is unlocked by winning the previous.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Campaign {
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub scenarios: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct CampaignProgress {
+    /// Ids of the scenarios won so far.
+    pub completed: Vec<String>,
+}
+
+/// The campaign being played and which of its scenarios is on.
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct CampaignRun {
+    pub campaign: Campaign,
+    pub progress: CampaignProgress,
+    pub scenario: String,
+    /// Sides of the scenario played by people, the campaign goes on when one of them wins.
+    pub humans: Vec<Player>,
+}
+
+fn progress_path(campaign_id: &str) -> String {
+    format!("{}/campaign_{}.json", SAVES_DIR, campaign_id)
+}
+
+/// A campaign never played has no progress file yet, an unreadable one starts it over.
+fn load_progress(campaign_id: &str) -> CampaignProgress {
+    let path = progress_path(campaign_id);
+    if !Path::new(&path).exists() { return CampaignProgress::default(); }
+    std::fs::read_to_string(&path).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| {
+            warn!("Cannot read campaign progress from {}, starting over", path);
+            CampaignProgress::default()
+        })
+}
+
+fn store_progress(campaign_id: &str, progress: &CampaignProgress) {
+    let path = progress_path(campaign_id);
+    let result = std::fs::create_dir_all(SAVES_DIR)
+        .and_then(|_| serde_json::to_string_pretty(progress).map_err(std::io::Error::from))
+        .and_then(|content| std::fs::write(&path, content));
+    if let Err(error) = result {
+        warn!("Cannot save campaign progress to {}: {}", path, error);
+    }
+}
+
+impl Campaign {
+    /// First scenario not won yet, the last one is replayed once the campaign is over.
+    pub(crate) fn next_scenario(&self, progress: &CampaignProgress) -> Option<&String> {
+        self.scenarios.iter()
+            .find(|scenario| !progress.completed.contains(scenario))
+            .or(self.scenarios.last())
+    }
+}
+
+impl CampaignRun {
+    /// Picks the scenario to play from the saved progress, `None` without `--campaign`.
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Option<(Self, Scenario)> {
+        let args: Vec<String> = args.collect();
+        let id = args.iter()
+            .position(|arg| arg == "--campaign")
+            .and_then(|index| args.get(index + 1))?;
+        let mut campaign: Campaign = load_data(&format!("{}/{}.json", CAMPAIGNS_DIR, id));
+        campaign.id = id.clone();
+        let progress = load_progress(id);
+        let scenario_id = campaign.next_scenario(&progress)
+            .unwrap_or_else(|| panic!("Campaign {} has no scenarios", id))
+            .clone();
+        let scenario = Scenario::named(&scenario_id);
+        let humans = scenario.human_players();
+        Some((Self { campaign, progress, scenario: scenario_id, humans }, scenario))
+    }
+
+    pub(crate) fn is_won(&self, outcome: &GameOutcome) -> bool {
+        outcome.winner.into_iter()
+            .chain(outcome.allies.iter().copied())
+            .any(|player| self.humans.contains(&player))
+    }
+
+    /// Shown on the result screen.
+    pub(crate) fn summary(&self, outcome: &GameOutcome) -> String {
+        if !self.is_won(outcome) {
+            return format!("{}: scenario lost, play it again to go on", self.campaign.name);
+        }
+        let mut progress = self.progress.clone();
+        if !progress.completed.contains(&self.scenario) {
+            progress.completed.push(self.scenario.clone());
+        }
+        match self.campaign.scenarios.iter().find(|scenario| !progress.completed.contains(scenario)) {
+            Some(next) => format!("{}: scenario won, next up is {}", self.campaign.name, next),
+            None => format!("{}: campaign complete", self.campaign.name),
+        }
+    }
+}
+
+fn save_campaign_progress(mut run: ResMut<CampaignRun>, outcome: Option<Res<GameOutcome>>) {
+    let Some(outcome) = outcome else { return; };
+    if !run.is_won(&outcome) || run.progress.completed.contains(&run.scenario) { return; }
+    let scenario = run.scenario.clone();
+    run.progress.completed.push(scenario);
+    store_progress(&run.campaign.id, &run.progress);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::victory::VictoryCondition;
+
+    use super::*;
+
+    const HUMAN: Player = Player { id: 1 };
+    const COMPUTER: Player = Player { id: 2 };
+
+    fn campaign() -> Campaign {
+        Campaign { id: "test".to_string(), name: "Test".to_string(), scenarios: vec!["first".to_string(), "second".to_string()] }
+    }
+
+    fn progress(completed: &[&str]) -> CampaignProgress {
+        CampaignProgress { completed: completed.iter().map(|scenario| scenario.to_string()).collect() }
+    }
+
+    fn run(completed: &[&str], scenario: &str) -> CampaignRun {
+        CampaignRun { campaign: campaign(), progress: progress(completed), scenario: scenario.to_string(), humans: vec![HUMAN] }
+    }
+
+    fn won_by(winner: Player, allies: Vec<Player>) -> GameOutcome {
+        GameOutcome { winner: Some(winner), allies, condition: VictoryCondition::Elimination }
+    }
+
+    #[test]
+    fn next_scenario_is_the_first_not_won() {
+        let campaign = campaign();
+        assert_eq!(campaign.next_scenario(&progress(&[])).map(String::as_str), Some("first"));
+        assert_eq!(campaign.next_scenario(&progress(&["first"])).map(String::as_str), Some("second"));
+        assert_eq!(campaign.next_scenario(&progress(&["second"])).map(String::as_str), Some("first"));
+    }
+
+    #[test]
+    fn last_scenario_is_replayed_once_the_campaign_is_over() {
+        assert_eq!(campaign().next_scenario(&progress(&["first", "second"])).map(String::as_str), Some("second"));
+        let empty = Campaign { scenarios: vec![], ..campaign() };
+        assert_eq!(empty.next_scenario(&progress(&[])), None);
+    }
+
+    #[test]
+    fn scenario_is_won_when_a_human_side_shares_the_victory() {
+        let run = run(&[], "first");
+        assert!(run.is_won(&won_by(HUMAN, vec![])));
+        assert!(run.is_won(&won_by(COMPUTER, vec![HUMAN])));
+        assert!(!run.is_won(&won_by(COMPUTER, vec![])));
+        assert!(!run.is_won(&GameOutcome { winner: None, allies: vec![], condition: VictoryCondition::Elimination }));
+    }
+
+    #[test]
+    fn summary_names_the_scenario_unlocked() {
+        assert_eq!(run(&[], "first").summary(&won_by(HUMAN, vec![])), "Test: scenario won, next up is second");
+        assert_eq!(run(&["first"], "second").summary(&won_by(HUMAN, vec![])), "Test: campaign complete");
+        assert_eq!(run(&[], "first").summary(&won_by(COMPUTER, vec![])), "Test: scenario lost, play it again to go on");
+    }
+}
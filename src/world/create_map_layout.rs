@@ -1,4 +1,5 @@
 use bevy::utils::HashMap;
+use hexx::Hex;
 
 fn create_setup_field_map_for_r3() -> HashMap<usize, usize> {
     let mut game_map: HashMap<usize, usize> = HashMap::new();
@@ -64,3 +65,15 @@ pub fn create_setup_field_map_for_radius(radius: u32) -> HashMap<usize, usize> {
         _ => { panic!("Radius <{}> is not supported", radius) }
     }
 }
+
+/// Objective hexes of the map and how many win points holding each gives per round.
+pub fn create_objectives_for_radius(radius: u32) -> HashMap<Hex, i32> {
+    let mut objectives: HashMap<Hex, i32> = HashMap::new();
+    objectives.insert(Hex::ZERO, 2);
+    if radius >= 3 {
+        // Equally far from both home planets
+        objectives.insert(Hex { x: 1, y: 1 }, 1);
+        objectives.insert(Hex { x: -1, y: -1 }, 1);
+    }
+    objectives
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use bevy::asset::io::file::FileAssetReader;
+use serde::de::DeserializeOwned;
+
+/// Same folder the `AssetPlugin` loads textures from.
+const ASSETS_FOLDER: &str = "assets";
+
+/// Location of `path` inside the asset folder, resolved the way Bevy resolves it,
+/// so the game finds its data wherever it is started from.
+pub(crate) fn asset_path(path: &str) -> PathBuf {
+    FileAssetReader::get_base_path().join(ASSETS_FOLDER).join(path)
+}
+
+/// Reads the JSON data file at `path`, relative to the asset folder.
+/// The data ships with the game, a missing or broken file is a bug and stops it right away.
+pub(crate) fn load_data<T: DeserializeOwned>(path: &str) -> T {
+    try_load_data(path).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Reads the JSON data file at `path` like `load_data`, for files the player picks by name,
+/// which may be missing or broken and are reported to the caller instead.
+pub(crate) fn try_load_data<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let full_path = asset_path(path);
+    let content = std::fs::read_to_string(&full_path)
+        .map_err(|error| format!("Cannot read {}: {}", full_path.display(), error))?;
+    serde_json::from_str(&content)
+        .map_err(|error| format!("Cannot parse {}: {}", full_path.display(), error))
+}
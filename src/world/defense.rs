@@ -0,0 +1,108 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use hexx::Hex;
+
+use crate::game_state::UpdateUI;
+use crate::world::factions::Factions;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::Player;
+use crate::world::setup_world_grid::{HexGrid, Planet};
+use crate::world::terrain::Terrain;
+
+/// Power every home planet adds to its defenders, on top of the bonus of their faction.
+const HOME_DEFENSE_BONUS: u32 = 1;
+/// Power added to the defenders of a fortified planet.
+const FORTIFICATION_BONUS: u32 = 2;
+/// Power attackers lose when fighting in an asteroid field.
+const ASTEROID_ATTACK_PENALTY: u32 = 2;
+
+pub struct DefensePlugin;
+
+impl Plugin for DefensePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_fortification_icons.in_set(UpdateUI::RenderStats));
+    }
+}
+
+/// Marks the icon shown on the planet at `hex` while it is fortified.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct FortificationInfo {
+    pub(crate) hex: Hex,
+}
+
+/// Changes to ship powers when a planet is attacked, powers never drop below 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct BattleModifiers {
+    pub defense_bonus: u32,
+    pub attack_penalty: u32,
+}
+
+impl BattleModifiers {
+    /// Modifiers for an attack on `planet` as it is now, fleets meeting in open space use the default.
+    pub(crate) fn of_planet(planet: &Planet, factions: &Factions) -> Self {
+        let mut defense_bonus = 0;
+        if planet.is_home() {
+            defense_bonus += HOME_DEFENSE_BONUS + factions.home_defense(&planet.owner);
+        }
+        if planet.fortified {
+            defense_bonus += FORTIFICATION_BONUS;
+        }
+        let attack_penalty = if planet.terrain == Terrain::AsteroidField { ASTEROID_ATTACK_PENALTY } else { 0 };
+        Self { defense_bonus, attack_penalty }
+    }
+
+    pub(crate) fn attack_power(&self, power: u32) -> u32 {
+        power.saturating_sub(self.attack_penalty).max(1)
+    }
+
+    pub(crate) fn defense_power(&self, power: u32) -> u32 {
+        power + self.defense_bonus
+    }
+}
+
+/// Shown next to the win probability, empty when nothing changes.
+impl fmt::Display for BattleModifiers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = vec![];
+        if self.defense_bonus > 0 {
+            parts.push(format!("defenders +{}", self.defense_bonus));
+        }
+        if self.attack_penalty > 0 {
+            parts.push(format!("attackers -{}", self.attack_penalty));
+        }
+        if parts.is_empty() { Ok(()) } else { write!(f, " ({})", parts.join(", ")) }
+    }
+}
+
+impl Planet {
+    /// Whether the planet is the home of its current owner.
+    pub(crate) fn is_home(&self) -> bool {
+        self.home_of == Some(self.owner)
+    }
+}
+
+/// Own planets can be fortified once, the fortification is lost when the planet is captured.
+pub(crate) fn can_fortify(player: &Player, grid: &HexGrid, hex: &Hex) -> bool {
+    grid.planets.get(hex).map_or(false, |planet| planet.owner == *player && !planet.fortified)
+}
+
+/// Spends the turn of `player` on fortifying the planet at `hex`, the caller ends the turn.
+pub(crate) fn fortify(player: &Player, grid: &mut HexGrid, hex: &Hex, game_log: &mut GameLog, round: i32) {
+    let Some(planet) = grid.planets.get_mut(hex) else { return; };
+    planet.fortified = true;
+    game_log.record(round, *player, GameEvent::PlanetFortified { hex: *hex });
+}
+
+fn update_fortification_icons(
+    grid: Res<HexGrid>,
+    mut icon_query: Query<(&mut Visibility, &FortificationInfo)>,
+) {
+    if !grid.is_changed() { return; }
+    for (mut visibility, info) in icon_query.iter_mut() {
+        let new_visibility = if grid.planets[&info.hex].fortified { Visibility::Inherited } else { Visibility::Hidden };
+        if *visibility != new_visibility {
+            *visibility = new_visibility;
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ui::stats::resources::Round;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::Player;
+use crate::world::resources::{GameResources, PlayerResources};
+
+/// How many rounds a non-aggression pact lasts.
+pub(crate) const PACT_ROUNDS: i32 = 5;
+/// Influence paid by whoever attacks a player they have a treaty with.
+pub(crate) const TREATY_BREAK_PENALTY: u32 = 5;
+const PACT_COST: PlayerResources = PlayerResources { influence: 2, resources: 0 };
+const ALLIANCE_COST: PlayerResources = PlayerResources { influence: 5, resources: 0 };
+/// What a single gift proposal transfers.
+pub(crate) const GIFT: PlayerResources = PlayerResources { influence: 0, resources: 5 };
+/// The two lots players can offer in a trade, resources for influence and the other way round.
+pub(crate) const TRADE_RESOURCES: PlayerResources = PlayerResources { influence: 0, resources: 6 };
+pub(crate) const TRADE_INFLUENCE: PlayerResources = PlayerResources { influence: 2, resources: 0 };
+/// How many resources a point of influence is worth when a trade is judged.
+const INFLUENCE_VALUE: u32 = 3;
+
+pub struct DiplomacyPlugin;
+
+impl Plugin for DiplomacyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Diplomacy::default());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum ProposalKind {
+    Pact { rounds: i32 },
+    /// Allies pass through each other's planets and share the victory.
+    Alliance,
+    Gift { gift: PlayerResources },
+    /// The proposer hands over `give` and gets `take` from the other side.
+    Trade { give: PlayerResources, take: PlayerResources },
+}
+
+impl fmt::Display for ProposalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProposalKind::Pact { rounds } => write!(f, "a non-aggression pact for {} rounds", rounds),
+            ProposalKind::Alliance => write!(f, "an alliance"),
+            ProposalKind::Gift { gift } => write!(f, "a gift of {}", gift),
+            ProposalKind::Trade { give, take } => write!(f, "a trade of {} for {}", give, take),
+        }
+    }
+}
+
+impl ProposalKind {
+    /// Paid by the proposer when the proposal is made, given back if it is declined.
+    pub(crate) fn cost(&self) -> PlayerResources {
+        match self {
+            ProposalKind::Pact { .. } => PACT_COST,
+            ProposalKind::Alliance => ALLIANCE_COST,
+            ProposalKind::Gift { gift } => *gift,
+            ProposalKind::Trade { give, .. } => *give,
+        }
+    }
+
+    /// Paid by the recipient when the proposal is accepted.
+    pub(crate) fn price(&self) -> PlayerResources {
+        match self {
+            ProposalKind::Trade { take, .. } => *take,
+            _ => PlayerResources::default(),
+        }
+    }
+
+    /// Whether the recipient gets at least as much as they pay, the way a computer player would judge it.
+    pub(crate) fn is_favourable(&self) -> bool {
+        match self {
+            ProposalKind::Trade { give, take } => value_of(give) >= value_of(take),
+            _ => true,
+        }
+    }
+}
+
+fn value_of(resources: &PlayerResources) -> u32 {
+    resources.resources + resources.influence * INFLUENCE_VALUE
+}
+
+fn can_afford(player_resources: &PlayerResources, cost: &PlayerResources) -> bool {
+    player_resources.resources >= cost.resources && player_resources.influence >= cost.influence
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Proposal {
+    pub from: Player,
+    pub to: Player,
+    pub kind: ProposalKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum Treaty {
+    /// Neither side attacks the other until the round is over.
+    Pact { until_round: i32 },
+    Alliance,
+}
+
+impl fmt::Display for Treaty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Treaty::Pact { until_round } => write!(f, "pact until round {}", until_round),
+            Treaty::Alliance => write!(f, "allied"),
+        }
+    }
+}
+
+/// Proposals waiting for an answer and treaties in force, between every pair of players.
+#[derive(Debug, Clone, Default, Resource, Serialize, Deserialize)]
+pub(crate) struct Diplomacy {
+    pub proposals: Vec<Proposal>,
+    pub treaties: Vec<(Player, Player, Treaty)>,
+}
+
+impl Diplomacy {
+    pub(crate) fn treaty(&self, player: &Player, other: &Player) -> Option<Treaty> {
+        self.treaties.iter()
+            .find(|(first, second, _)| (first == player && second == other) || (first == other && second == player))
+            .map(|(_, _, treaty)| *treaty)
+    }
+
+    pub(crate) fn is_allied(&self, player: &Player, other: &Player) -> bool {
+        self.treaty(player, other) == Some(Treaty::Alliance)
+    }
+
+    /// Players at peace don't fight each other, attacking anyway breaks the treaty.
+    pub(crate) fn is_at_peace(&self, player: &Player, other: &Player) -> bool {
+        self.treaty(player, other).is_some()
+    }
+
+    pub(crate) fn allies_of(&self, player: &Player) -> Vec<Player> {
+        self.treaties.iter()
+            .filter(|(_, _, treaty)| *treaty == Treaty::Alliance)
+            .filter_map(|(first, second, _)| {
+                if first == player { Some(*second) } else if second == player { Some(*first) } else { None }
+            })
+            .collect()
+    }
+
+    pub(crate) fn incoming(&self, player: &Player) -> Vec<Proposal> {
+        self.proposals.iter().filter(|proposal| proposal.to == *player).copied().collect()
+    }
+
+    pub(crate) fn can_propose(&self, proposal: &Proposal, player_resources: &PlayerResources) -> bool {
+        let is_pending = self.proposals.iter().any(|other| other.from == proposal.from && other.to == proposal.to && other.kind == proposal.kind);
+        let is_needed = match proposal.kind {
+            ProposalKind::Pact { .. } => !self.is_at_peace(&proposal.from, &proposal.to),
+            ProposalKind::Alliance => !self.is_allied(&proposal.from, &proposal.to),
+            ProposalKind::Gift { .. } | ProposalKind::Trade { .. } => true,
+        };
+        proposal.from != proposal.to
+            && !is_pending
+            && is_needed
+            && can_afford(player_resources, &proposal.kind.cost())
+    }
+
+    /// `player_resources` are the ones of the recipient.
+    pub(crate) fn can_accept(&self, proposal: &Proposal, player_resources: &PlayerResources) -> bool {
+        self.proposals.contains(proposal) && can_afford(player_resources, &proposal.kind.price())
+    }
+
+    /// The cost has to be paid by the caller.
+    pub(crate) fn propose(&mut self, proposal: Proposal) {
+        self.proposals.push(proposal);
+    }
+
+    /// Removes the proposal, the caller settles its cost.
+    pub(crate) fn answer(&mut self, proposal: &Proposal) -> bool {
+        let Some(index) = self.proposals.iter().position(|other| other == proposal) else { return false; };
+        self.proposals.remove(index);
+        true
+    }
+
+    /// Signs the treaty of an accepted proposal, gifts and trades are settled by the caller.
+    pub(crate) fn sign(&mut self, proposal: &Proposal, round: i32) {
+        let treaty = match proposal.kind {
+            ProposalKind::Pact { rounds } => Treaty::Pact { until_round: round + rounds },
+            ProposalKind::Alliance => Treaty::Alliance,
+            ProposalKind::Gift { .. } | ProposalKind::Trade { .. } => return,
+        };
+        self.break_treaty(&proposal.from, &proposal.to);
+        self.treaties.push((proposal.from, proposal.to, treaty));
+    }
+
+    pub(crate) fn break_treaty(&mut self, player: &Player, other: &Player) {
+        self.treaties.retain(|(first, second, _)| !((first == player && second == other) || (first == other && second == player)));
+    }
+
+    /// Pacts end once the round they were signed for is over.
+    pub(crate) fn expire(&mut self, round: i32) {
+        self.treaties.retain(|(_, _, treaty)| !matches!(treaty, Treaty::Pact { until_round } if *until_round < round));
+    }
+}
+
+/// Allies can't be attacked, pacts can only be broken by players able to pay for it.
+pub(crate) fn can_attack(player: &Player, defender: &Player, diplomacy: &Diplomacy, resources: &GameResources) -> bool {
+    match diplomacy.treaty(player, defender) {
+        None => true,
+        Some(Treaty::Alliance) => false,
+        Some(Treaty::Pact { .. }) => resources.resources.get(player).map_or(false, |stock| stock.influence >= TREATY_BREAK_PENALTY),
+    }
+}
+
+/// The proposer pays the cost and the proposal waits for an answer, returns whether it could be made.
+pub(crate) fn make_proposal(diplomacy: &mut Diplomacy, resources: &mut GameResources, proposal: Proposal, game_log: &mut GameLog, round: i32) -> bool {
+    let player_resources = resources.resources.get(&proposal.from).copied().unwrap_or_default();
+    if !diplomacy.can_propose(&proposal, &player_resources) { return false; }
+    *resources.resources.entry(proposal.from).or_insert_with(PlayerResources::default) -= proposal.kind.cost();
+    diplomacy.propose(proposal);
+    game_log.record(round, proposal.from, GameEvent::ProposalMade { to: proposal.to, kind: proposal.kind });
+    true
+}
+
+/// Accepted gifts go to the recipient and trades swap both sides' lots,
+/// declined proposals give the proposer their cost back. Returns whether the proposal could be answered.
+pub(crate) fn answer_proposal(diplomacy: &mut Diplomacy, resources: &mut GameResources, proposal: Proposal, accept: bool, game_log: &mut GameLog, round: i32) -> bool {
+    let recipient_resources = resources.resources.get(&proposal.to).copied().unwrap_or_default();
+    if accept && !diplomacy.can_accept(&proposal, &recipient_resources) { return false; }
+    if !diplomacy.answer(&proposal) { return false; }
+    if accept {
+        diplomacy.sign(&proposal, round);
+        match proposal.kind {
+            ProposalKind::Gift { gift } => {
+                *resources.resources.entry(proposal.to).or_insert_with(PlayerResources::default) += gift;
+            }
+            ProposalKind::Trade { give, take } => {
+                let recipient = resources.resources.entry(proposal.to).or_insert_with(PlayerResources::default);
+                *recipient = *recipient + give - take;
+                *resources.resources.entry(proposal.from).or_insert_with(PlayerResources::default) += take;
+            }
+            _ => {}
+        }
+        game_log.record(round, proposal.to, GameEvent::ProposalAccepted { from: proposal.from, kind: proposal.kind });
+    } else {
+        *resources.resources.entry(proposal.from).or_insert_with(PlayerResources::default) += proposal.kind.cost();
+        game_log.record(round, proposal.to, GameEvent::ProposalDeclined { from: proposal.from, kind: proposal.kind });
+    }
+    true
+}
+
+/// Proposals to or from `player`, who is out of the game, can't be answered anymore.
+/// Their proposers get the cost back, the ones made to `player` count as declined.
+pub(crate) fn withdraw_proposals(player: &Player, diplomacy: &mut Diplomacy, resources: &mut GameResources, game_log: &mut GameLog, round: i32) {
+    let (withdrawn, kept): (Vec<Proposal>, Vec<Proposal>) = diplomacy.proposals.iter().copied()
+        .partition(|proposal| proposal.from == *player || proposal.to == *player);
+    diplomacy.proposals = kept;
+    for proposal in withdrawn {
+        *resources.resources.entry(proposal.from).or_insert_with(PlayerResources::default) += proposal.kind.cost();
+        if proposal.to == *player {
+            game_log.record(round, proposal.to, GameEvent::ProposalDeclined { from: proposal.from, kind: proposal.kind });
+        }
+    }
+}
+
+/// `attacker` pays the penalty, as much of it as they can, and the treaty is gone.
+pub(crate) fn break_treaty(attacker: &Player, defender: &Player, diplomacy: &mut Diplomacy, game_resources: &mut GameResources, game_log: &mut GameLog, round: i32) {
+    diplomacy.break_treaty(attacker, defender);
+    if let Some(resources) = game_resources.resources.get_mut(attacker) {
+        resources.influence = resources.influence.saturating_sub(TREATY_BREAK_PENALTY);
+    }
+    game_log.record(round, *attacker, GameEvent::TreatyBroken { with: *defender, penalty: TREATY_BREAK_PENALTY });
+}
+
+pub(crate) fn expire_pacts(mut diplomacy: ResMut<Diplomacy>, round: Res<Round>) {
+    if !diplomacy.treaties.iter().any(|(_, _, treaty)| matches!(treaty, Treaty::Pact { until_round } if *until_round < round.number)) { return; }
+    diplomacy.expire(round.number);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: Player = Player { id: 1 };
+    const SECOND: Player = Player { id: 2 };
+    const WEALTH: PlayerResources = PlayerResources { influence: 10, resources: 10 };
+
+    fn proposal(kind: ProposalKind) -> Proposal {
+        Proposal { from: FIRST, to: SECOND, kind }
+    }
+
+    #[test]
+    fn proposals_need_a_partner_and_the_cost() {
+        let diplomacy = Diplomacy::default();
+        assert!(diplomacy.can_propose(&proposal(ProposalKind::Alliance), &WEALTH));
+        assert!(!diplomacy.can_propose(&Proposal { from: FIRST, to: FIRST, kind: ProposalKind::Alliance }, &WEALTH));
+        assert!(!diplomacy.can_propose(&proposal(ProposalKind::Alliance), &PlayerResources { influence: 4, resources: 10 }));
+    }
+
+    #[test]
+    fn same_proposal_is_not_made_twice() {
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.propose(proposal(ProposalKind::Pact { rounds: PACT_ROUNDS }));
+        assert!(!diplomacy.can_propose(&proposal(ProposalKind::Pact { rounds: PACT_ROUNDS }), &WEALTH));
+        assert!(diplomacy.can_propose(&proposal(ProposalKind::Alliance), &WEALTH));
+    }
+
+    #[test]
+    fn treaties_in_force_are_not_proposed_again() {
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.sign(&proposal(ProposalKind::Pact { rounds: PACT_ROUNDS }), 1);
+        assert!(!diplomacy.can_propose(&proposal(ProposalKind::Pact { rounds: PACT_ROUNDS }), &WEALTH));
+        assert!(diplomacy.can_propose(&proposal(ProposalKind::Alliance), &WEALTH));
+
+        diplomacy.sign(&proposal(ProposalKind::Alliance), 1);
+        assert!(!diplomacy.can_propose(&Proposal { from: SECOND, to: FIRST, kind: ProposalKind::Alliance }, &WEALTH));
+    }
+
+    #[test]
+    fn only_pending_proposals_the_recipient_can_pay_are_accepted() {
+        let trade = proposal(ProposalKind::Trade { give: TRADE_RESOURCES, take: TRADE_INFLUENCE });
+        let mut diplomacy = Diplomacy::default();
+        assert!(!diplomacy.can_accept(&trade, &WEALTH));
+
+        diplomacy.propose(trade);
+        assert!(diplomacy.can_accept(&trade, &WEALTH));
+        assert!(!diplomacy.can_accept(&trade, &PlayerResources { influence: 1, resources: 10 }));
+    }
+
+    #[test]
+    fn trades_are_favourable_when_the_recipient_gets_at_least_what_they_pay() {
+        assert!(ProposalKind::Trade { give: TRADE_RESOURCES, take: TRADE_INFLUENCE }.is_favourable());
+        assert!(ProposalKind::Trade { give: TRADE_INFLUENCE, take: TRADE_RESOURCES }.is_favourable());
+        assert!(!ProposalKind::Trade { give: TRADE_INFLUENCE, take: PlayerResources { influence: 0, resources: 7 } }.is_favourable());
+        assert!(ProposalKind::Gift { gift: GIFT }.is_favourable());
+        assert!(ProposalKind::Alliance.is_favourable());
+    }
+}
@@ -0,0 +1,143 @@
+use std::ops::DerefMut;
+
+use bevy::prelude::*;
+
+use crate::game_state::{AppState, UpdateUI};
+use crate::network::client::is_local_game;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::galactic_event::plugin::GalacticEventState;
+use crate::ui::stats::resources::Round;
+use crate::world::diplomacy::{Diplomacy, withdraw_proposals};
+use crate::world::factions::Factions;
+use crate::world::fleets::Fleet;
+use crate::world::galactic_events::GalacticEvents;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+use crate::world::resources::GameResources;
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::victory::{GameOutcome, last_standing, VictoryCondition};
+
+const NEUTRALS: Player = Player { id: -1 };
+
+pub struct EliminationPlugin;
+
+impl Plugin for EliminationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, detect_eliminations.run_if(is_local_game).in_set(UpdateUI::RenderStats));
+    }
+}
+
+/// A player owning no planet and no fleet can't come back.
+pub(crate) fn is_out_of_game<'a>(player: &Player, grid: &HexGrid, mut fleets: impl Iterator<Item=&'a Fleet>) -> bool {
+    !grid.planets.values().any(|planet| planet.owner == *player) && !fleets.any(|fleet| fleet.owner == *player)
+}
+
+/// Planets of `player` fall to the neutrals together with their garrisons, fleets in flight are scuttled.
+/// The player is eliminated as soon as the fleets are gone.
+pub(crate) fn surrender<'a>(
+    player: &Player,
+    grid: &mut HexGrid,
+    fleets: impl Iterator<Item=(Entity, &'a Fleet)>,
+    commands: &mut Commands,
+    game_log: &mut GameLog,
+    round: i32,
+) {
+    for (entity, _) in fleets.filter(|(_, fleet)| fleet.owner == *player) {
+        commands.entity(entity).despawn_recursive();
+    }
+    surrender_planets(player, grid, game_log, round);
+}
+
+/// Hands the planets of `player` to the neutrals, their fleets have to be removed by the caller.
+pub(crate) fn surrender_planets(player: &Player, grid: &mut HexGrid, game_log: &mut GameLog, round: i32) {
+    for planet in grid.planets.values_mut().filter(|planet| planet.owner == *player) {
+        planet.on_captured();
+        planet.owner = NEUTRALS;
+        planet.owner_army.iter_mut().for_each(|ship| ship.ship_owner = NEUTRALS);
+    }
+    game_log.record(round, *player, GameEvent::Surrendered);
+}
+
+/// Players knocked out by `eliminate_players`, `outcome` is set once only one player or alliance is left.
+pub(crate) struct Eliminations {
+    pub players: Vec<Player>,
+    /// The player to move is out, their turn has to pass on.
+    pub is_current_out: bool,
+    pub outcome: Option<GameOutcome>,
+}
+
+/// Knocked out players lose their turns and their pending proposals, the game ends once only one player
+/// or alliance is left, whatever the victory conditions are.
+pub(crate) fn eliminate_players<'a, S: DerefMut<Target=Stats>>(
+    stats: impl Iterator<Item=(&'a Player, S)>,
+    current: Option<Player>,
+    turn_order: &TurnOrder,
+    grid: &HexGrid,
+    fleets: &[&Fleet],
+    diplomacy: &mut Diplomacy,
+    resources: &mut GameResources,
+    game_log: &mut GameLog,
+    round: i32,
+) -> Eliminations {
+    let mut stats: Vec<(&Player, S)> = stats.collect();
+    let mut eliminations = Eliminations { players: vec![], is_current_out: false, outcome: None };
+    for (player, stats) in stats.iter_mut() {
+        if stats.eliminated || !is_out_of_game(player, grid, fleets.iter().copied()) { continue; }
+        stats.eliminated = true;
+        eliminations.players.push(**player);
+        game_log.record(round, **player, GameEvent::PlayerEliminated);
+        withdraw_proposals(player, diplomacy, resources, game_log, round);
+        if current == Some(**player) {
+            // Like skipping the round, the turn passes on and no moves are left afterwards
+            stats.moves_left = 1;
+            eliminations.is_current_out = true;
+        } else {
+            stats.moves_left = 0;
+        }
+    }
+    if eliminations.players.is_empty() { return eliminations; }
+
+    let alive = turn_order.active(stats.iter().map(|(player, stats)| (*player, &**stats)));
+    if let Some(winner) = last_standing(&alive, diplomacy) {
+        eliminations.outcome = Some(GameOutcome { winner: Some(winner), allies: diplomacy.allies_of(&winner), condition: VictoryCondition::Elimination });
+    }
+    eliminations
+}
+
+/// Eliminations are announced, the turn of a knocked out player passes on.
+fn detect_eliminations(
+    mut commands: Commands,
+    grid: Res<HexGrid>,
+    fleets_query: Query<&Fleet>,
+    mut players_query: Query<(&Player, &mut Stats, Option<&Movable>)>,
+    turn_order: Res<TurnOrder>,
+    mut diplomacy: ResMut<Diplomacy>,
+    mut resources: ResMut<GameResources>,
+    factions: Res<Factions>,
+    outcome: Option<Res<GameOutcome>>,
+    round: Res<Round>,
+    mut game_log: ResMut<GameLog>,
+    mut galactic_events: ResMut<GalacticEvents>,
+    mut event_state: ResMut<NextState<GalacticEventState>>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    mut next_turn_switched_state: ResMut<NextState<TurnSwitchedState>>,
+    mut game_phase: ResMut<NextState<AppState>>,
+) {
+    if outcome.is_some() { return; }
+    let current = players_query.iter().find(|(_, _, movable)| movable.is_some()).map(|(player, _, _)| *player);
+    let fleets: Vec<&Fleet> = fleets_query.iter().collect();
+    let eliminations = eliminate_players(players_query.iter_mut().map(|(player, stats, _)| (player, stats)), current, &turn_order,
+                                         &grid, &fleets, &mut diplomacy, &mut resources, &mut game_log, round.number);
+    for player in eliminations.players.iter() {
+        galactic_events.announce(format!("{} eliminated", factions.name(player)), format!("{} has no planets and no fleets left", factions.name(player)));
+        event_state.set(GalacticEventState::Shown);
+    }
+    if eliminations.is_current_out && *turn_switched_state.get() == TurnSwitchedState::OnDefaultState {
+        next_turn_switched_state.set(TurnSwitchedState::OnTurnSwitched);
+    }
+    if let Some(outcome) = eliminations.outcome {
+        info!("Game over: {:?}", outcome);
+        commands.insert_resource(outcome);
+        game_phase.set(AppState::GameOver);
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::space_ships::SpaceShipType;
+use crate::world::data::load_data;
+use crate::world::player::Player;
+use crate::world::research::TechEffect;
+use crate::world::resources::PlayerResources;
+
+/// Factions are described in a data file, like the tech tree.
+const FACTIONS_FILE: &str = "data/factions.json";
+
+const NEUTRAL_COLOR: Color = Color::BLACK;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Faction {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub color: [f32; 3],
+    /// Added to the resources every player starts with.
+    #[serde(default)]
+    pub starting_bonus: PlayerResources,
+    /// Ships waiting on the home planet at the start of the game.
+    #[serde(default)]
+    pub starting_ships: Vec<SpaceShipType>,
+    /// Added to the income of every planet the faction owns.
+    #[serde(default)]
+    pub planet_bonus: PlayerResources,
+    /// Added to the power of every ship defending the home planet.
+    #[serde(default)]
+    pub home_defense: u32,
+    /// Work like techs researched from the start, they also shape the ship roster.
+    #[serde(default)]
+    pub effects: Vec<TechEffect>,
+}
+
+impl Faction {
+    pub(crate) fn color(&self) -> Color {
+        Color::rgb(self.color[0], self.color[1], self.color[2])
+    }
+}
+
+/// The available factions and which one every player leads.
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct Factions {
+    pub list: Vec<Faction>,
+    pub players: HashMap<Player, String>,
+}
+
+impl Factions {
+    /// `--factions terran,zorg` picks the factions of the players in turn order,
+    /// players without a valid choice get the first faction nobody leads yet.
+    pub(crate) fn from_args(args: impl Iterator<Item=String>, players: &[Player]) -> Self {
+        let args: Vec<String> = args.collect();
+        let choices: Vec<String> = args.iter()
+            .position(|arg| arg == "--factions")
+            .and_then(|index| args.get(index + 1))
+            .map(|value| value.split(',').map(|id| id.trim().to_string()).collect())
+            .unwrap_or_default();
+        Self::from_choices(&choices, players)
+    }
+
+    /// Factions picked in turn order, an empty choice gets the first faction nobody leads yet.
+    pub(crate) fn from_choices(choices: &[String], players: &[Player]) -> Self {
+        Self::new(load_data(FACTIONS_FILE), choices, players)
+    }
+
+    pub(crate) fn new(list: Vec<Faction>, choices: &[String], players_in_game: &[Player]) -> Self {
+        assert!(!list.is_empty(), "At least one faction has to be defined");
+        let mut players = HashMap::new();
+        for (index, player) in players_in_game.iter().enumerate() {
+            let choice = choices.get(index).filter(|id| !id.is_empty()).filter(|id| {
+                let is_known = list.iter().any(|faction| faction.id == **id);
+                if !is_known { warn!("Unknown faction '{}'", id); }
+                is_known
+            });
+            let id = choice.cloned().unwrap_or_else(|| {
+                let taken: Vec<&String> = players.values().chain(choices.iter()).collect();
+                list.iter()
+                    .find(|faction| !taken.contains(&&faction.id))
+                    .unwrap_or(&list[index % list.len()])
+                    .id.clone()
+            });
+            players.insert(*player, id);
+        }
+        Self { list, players }
+    }
+
+    pub(crate) fn of(&self, player: &Player) -> Option<&Faction> {
+        let id = self.players.get(player)?;
+        self.list.iter().find(|faction| faction.id == *id)
+    }
+
+    /// Name shown to the players instead of the player number.
+    pub(crate) fn name(&self, player: &Player) -> String {
+        if player.id == -1 { return "Neutrals".to_string(); }
+        self.of(player).map_or(player.to_string(), |faction| faction.name.clone())
+    }
+
+    pub(crate) fn color(&self, player: &Player) -> Color {
+        self.of(player).map_or(NEUTRAL_COLOR, |faction| faction.color())
+    }
+
+    pub(crate) fn planet_bonus(&self, player: &Player) -> PlayerResources {
+        self.of(player).map_or(PlayerResources::default(), |faction| faction.planet_bonus)
+    }
+
+    /// Power added to the ships of `player` defending their home planet.
+    pub(crate) fn home_defense(&self, player: &Player) -> u32 {
+        self.of(player).map_or(0, |faction| faction.home_defense)
+    }
+}
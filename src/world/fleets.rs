@@ -0,0 +1,359 @@
+use std::hash::Hash;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::utils::HashMap;
+use hexx::{Hex, HexLayout};
+use serde::{Deserialize, Serialize};
+
+use crate::space_ships::{get_spaceship_sprite_bundle_by_type, SpaceShip, SpaceShipCharacteristics, SpaceSipTextureAtlas};
+use crate::ui::stats::resources::Round;
+use crate::world::actions::move_menu::animations::AnimationUit;
+use crate::world::actions::move_menu::systems::battle::{move_army_to_planet, perform_fight};
+use crate::world::defense::BattleModifiers;
+use crate::world::buildings::BuildingType;
+use crate::world::diplomacy::{break_treaty, Diplomacy};
+use crate::world::factions::Factions;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::pathfinding::{find_path, is_passable_for};
+use crate::world::random::GameRng;
+use crate::world::research::Research;
+use crate::world::resources::GameResources;
+use crate::world::player::{Movable, Player};
+use crate::world::setup_world_grid::HexGrid;
+
+const FLEET_ROUTE_COLOR: Color = Color::YELLOW_GREEN;
+
+pub struct FleetsPlugin;
+
+impl Plugin for FleetsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (update_fleet_labels, draw_fleet_routes));
+    }
+}
+
+/// Ships which left their planet and are flying along `path` towards its last hex.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Fleet {
+    pub owner: Player,
+    pub ships: Vec<SpaceShip>,
+    pub path: Vec<Hex>,
+    /// Index in `path` of the last hex the fleet has passed.
+    pub position: usize,
+}
+
+#[derive(Component)]
+pub(crate) struct FleetLabel;
+
+impl Fleet {
+    pub(crate) fn new(owner: Player, ships: Vec<SpaceShip>, path: Vec<Hex>) -> Self {
+        Self { owner, ships, path, position: 0 }
+    }
+
+    pub(crate) fn speed(&self) -> u32 {
+        self.ships.iter()
+            .map(|ship| SpaceShipCharacteristics::get_by_spaceship_type(ship.ship_type).speed)
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn current_hex(&self) -> Hex {
+        self.path[self.position]
+    }
+
+    pub(crate) fn next_hex(&self) -> Option<Hex> {
+        self.path.get(self.position + 1).copied()
+    }
+
+    pub(crate) fn destination(&self) -> Hex {
+        *self.path.last().unwrap()
+    }
+
+    pub(crate) fn has_arrived(&self) -> bool {
+        self.position + 1 >= self.path.len()
+    }
+
+    /// Hexes the fleet is flying between right now.
+    fn segment(&self) -> (Hex, Hex) {
+        (self.current_hex(), self.next_hex().unwrap_or(self.current_hex()))
+    }
+
+    fn meets(&self, other: &Fleet) -> bool {
+        let (from, to) = self.segment();
+        let (other_from, other_to) = other.segment();
+        from == other_from || (from == other_to && to == other_from)
+    }
+
+    /// In transit fleets are drawn halfway between the hexes they are flying between.
+    pub(crate) fn world_position(&self, layout: &HexLayout) -> Vec2 {
+        let (from, to) = self.segment();
+        (layout.hex_to_world_pos(from) + layout.hex_to_world_pos(to)) / 2.0
+    }
+}
+
+/// Sends ships selected for move towards `destination`, one fleet per planet they depart from.
+pub(crate) fn launch_fleets(
+    player: &Player,
+    army: Vec<SpaceShip>,
+    destination: Hex,
+    grid: &mut HexGrid,
+    diplomacy: &Diplomacy,
+    game_log: &mut GameLog,
+    round: i32,
+    handle: &SpaceSipTextureAtlas,
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+) {
+    for fleet in plan_fleets(player, army, destination, grid, diplomacy, game_log, round) {
+        spawn_fleet(fleet, &grid.layout, handle, asset_server, commands);
+    }
+}
+
+/// Groups the ships by the planet they depart from and routes every group to `destination`,
+/// ships without a route stay where they are.
+pub(crate) fn plan_fleets(
+    player: &Player,
+    army: Vec<SpaceShip>,
+    destination: Hex,
+    grid: &mut HexGrid,
+    diplomacy: &Diplomacy,
+    game_log: &mut GameLog,
+    round: i32,
+) -> Vec<Fleet> {
+    let mut groups: HashMap<Hex, Vec<SpaceShip>> = HashMap::new();
+    for mut ship in army.into_iter() {
+        ship.is_selected_for_move = false;
+        groups.entry(ship.ship_hex).or_insert_with(Vec::new).push(ship);
+    }
+    let mut fleets = vec![];
+    for (source, ships) in groups.into_iter() {
+        match find_path(player, source, destination, &grid.planets, diplomacy) {
+            Some(path) => {
+                game_log.record(round, *player, GameEvent::FleetLaunched { from: source, to: destination, ships: ships.len() });
+                fleets.push(Fleet::new(*player, ships, path));
+            }
+            None => {
+                let planet = grid.planets.get_mut(&source).unwrap();
+                planet.owner_army.extend(ships);
+            }
+        }
+    }
+    fleets
+}
+
+pub(crate) fn spawn_fleet(
+    fleet: Fleet,
+    layout: &HexLayout,
+    handle: &SpaceSipTextureAtlas,
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+) {
+    let start = layout.hex_to_world_pos(fleet.current_hex());
+    let position = fleet.world_position(layout);
+    let flagship = fleet.ships.iter()
+        .max_by_key(|ship| SpaceShipCharacteristics::get_by_spaceship_type(ship.ship_type).power)
+        .unwrap()
+        .ship_type;
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 42.0,
+        color: Color::WHITE,
+    };
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(start.x, start.y, 0.)),
+            AnimationUit::new(start, position),
+        ))
+        .with_children(|parent| {
+            parent.spawn(get_spaceship_sprite_bundle_by_type(&handle.spaceship_grid_atlas, flagship, Transform {
+                translation: Vec3::new(18., 0., 0.),
+                scale: Vec3::splat(0.7),
+                ..default()
+            }));
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(format!("{}", fleet.ships.len()), text_style),
+                    transform: Transform {
+                        translation: Vec3::new(22., 0., 0.5),
+                        scale: Vec3::splat(0.4),
+                        ..default()
+                    },
+                    text_anchor: Anchor::CenterLeft,
+                    ..default()
+                },
+                FleetLabel,
+            ));
+        })
+        .insert(fleet);
+}
+
+/// Fleets of the player whose turn begins make their jumps, nebulas take two of them to enter
+/// but a fleet always makes at least one jump per turn.
+pub(crate) fn advance_fleets(
+    mut commands: Commands,
+    mut fleets_query: Query<(Entity, &mut Fleet, &Transform)>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut grid: ResMut<HexGrid>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    mut rng: ResMut<GameRng>,
+    research: Res<Research>,
+    factions: Res<Factions>,
+    mut diplomacy: ResMut<Diplomacy>,
+    mut game_resources: ResMut<GameResources>,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let mut fleets: HashMap<Entity, Fleet> = fleets_query.iter().map(|(entity, fleet, _)| (entity, fleet.clone())).collect();
+    move_fleets(player, &mut fleets, &mut grid, &mut game_log, round.number, &mut rng, &research, &factions, &mut diplomacy, &mut game_resources);
+
+    for (entity, mut fleet, transform) in fleets_query.iter_mut() {
+        let Some(updated) = fleets.remove(&entity) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        if updated.position != fleet.position {
+            let from = transform.translation.truncate();
+            commands.entity(entity).insert(AnimationUit::new(from, updated.world_position(&grid.layout)));
+        }
+        *fleet = updated;
+    }
+}
+
+/// Moves the fleets of `player`, enemy fleets met on the way are fought and fleets which reached
+/// their destination land on the planet. Destroyed and landed fleets are removed from `fleets`.
+pub(crate) fn move_fleets<K: Copy + Eq + Hash>(
+    player: &Player,
+    fleets: &mut HashMap<K, Fleet>,
+    grid: &mut HexGrid,
+    game_log: &mut GameLog,
+    round: i32,
+    rng: &mut GameRng,
+    research: &Research,
+    factions: &Factions,
+    diplomacy: &mut Diplomacy,
+    game_resources: &mut GameResources,
+) {
+    let mut destroyed: Vec<K> = vec![];
+    let mut arrived: Vec<K> = vec![];
+
+    let moving: Vec<K> = fleets.iter().filter(|(_, fleet)| fleet.owner == *player).map(|(key, _)| *key).collect();
+    for key in moving {
+        let speed = fleets[&key].speed();
+        let mut jumps_left = speed;
+        while jumps_left > 0 {
+            let Some(cost) = fleets[&key].next_hex().map(|next| grid.planets[&next].terrain.movement_cost()) else { break; };
+            if cost > jumps_left && jumps_left < speed { break; }
+            jumps_left = jumps_left.saturating_sub(cost);
+            let mut fleet = fleets.remove(&key).unwrap();
+            let next = fleet.next_hex().unwrap();
+            if next != fleet.destination() && !is_passable_for(&fleet.owner, &grid.planets[&next], diplomacy) {
+                // The route was cut, the fleet attacks whoever is blocking it
+                fleet.path.truncate(fleet.position + 2);
+            }
+            fleet.position += 1;
+
+            for (other_key, other) in fleets.iter_mut() {
+                if other.owner == fleet.owner || diplomacy.is_at_peace(&fleet.owner, &other.owner) || destroyed.contains(other_key) || !fleet.meets(other) { continue; }
+                let (winner, winner_army) = perform_fight(&mut rng.0, research, fleet.owner, other.owner, fleet.ships.clone(), other.ships.clone(), BattleModifiers::default());
+                game_log.record(round, fleet.owner, GameEvent::Battle {
+                    hex: fleet.current_hex(),
+                    defender: other.owner,
+                    winner,
+                    attacker_ships: fleet.ships.len(),
+                    defender_ships: other.ships.len(),
+                    survivors: winner_army.len(),
+                });
+                if winner == fleet.owner {
+                    fleet.ships = winner_army;
+                    destroyed.push(*other_key);
+                } else {
+                    other.ships = winner_army;
+                    destroyed.push(key);
+                    break;
+                }
+            }
+
+            let is_destroyed = destroyed.contains(&key);
+            if !is_destroyed && fleet.has_arrived() {
+                let defender = grid.planets[&fleet.destination()].owner;
+                if diplomacy.is_at_peace(&fleet.owner, &defender) {
+                    // The treaty was signed while the fleet was on its way
+                    break_treaty(&fleet.owner, &defender, diplomacy, game_resources, game_log, round);
+                }
+                land_fleet(&mut fleet, grid, game_log, round, rng, research, factions);
+                arrived.push(key);
+            }
+            fleets.insert(key, fleet);
+            if is_destroyed || arrived.contains(&key) { break; }
+        }
+    }
+
+    for key in destroyed.iter().chain(arrived.iter()) {
+        fleets.remove(key);
+    }
+}
+
+fn land_fleet(fleet: &mut Fleet, grid: &mut HexGrid, game_log: &mut GameLog, round: i32, rng: &mut GameRng, research: &Research, factions: &Factions) {
+    let destination = fleet.destination();
+    let mut planet = grid.planets.remove(&destination).unwrap();
+    let mut army = std::mem::take(&mut fleet.ships);
+    for ship in army.iter_mut() {
+        ship.ship_hex = destination;
+    }
+    let (attacker_ships, defender_ships) = (army.len(), planet.owner_army.len());
+    let mut defense_platforms = planet.count_finished(BuildingType::DefensePlatform);
+    let has_defense = defender_ships > 0 || defense_platforms > 0;
+    let modifiers = BattleModifiers::of_planet(&planet, factions);
+    let (winner, winner_army) = move_army_to_planet(&mut rng.0, research, fleet.owner, planet.owner, army, planet.owner_army, &mut defense_platforms, modifiers);
+    planet.destroy_buildings(BuildingType::DefensePlatform, defense_platforms);
+    if planet.owner != fleet.owner {
+        if has_defense {
+            game_log.record(round, fleet.owner, GameEvent::Battle {
+                hex: destination,
+                defender: planet.owner,
+                winner,
+                attacker_ships,
+                defender_ships,
+                survivors: winner_army.len(),
+            });
+        }
+        if winner == fleet.owner {
+            game_log.record(round, fleet.owner, GameEvent::PlanetCaptured { hex: destination, previous_owner: planet.owner });
+            planet.on_captured();
+        }
+    }
+    planet.owner = winner;
+    planet.owner_army = winner_army;
+    for ship in planet.owner_army.iter_mut() {
+        ship.ship_hex = destination;
+    }
+    grid.planets.insert(destination, planet);
+}
+
+fn update_fleet_labels(
+    fleets_query: Query<(&Fleet, &Children), Changed<Fleet>>,
+    mut labels_query: Query<&mut Text, With<FleetLabel>>,
+) {
+    for (fleet, children) in fleets_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = labels_query.get_mut(*child) {
+                text.sections[0].value = format!("{}", fleet.ships.len());
+            }
+        }
+    }
+}
+
+fn draw_fleet_routes(
+    fleets_query: Query<&Fleet>,
+    current_player_query: Query<&Player, With<Movable>>,
+    grid: Res<HexGrid>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(player) = current_player_query.get_single() else { return; };
+    for fleet in fleets_query.iter().filter(|fleet| fleet.owner == *player) {
+        let mut points = vec![fleet.world_position(&grid.layout)];
+        points.extend(fleet.path.iter().skip(fleet.position + 1).map(|hex| grid.layout.hex_to_world_pos(*hex)));
+        gizmos.linestrip_2d(points, FLEET_ROUTE_COLOR);
+    }
+}
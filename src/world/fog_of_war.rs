@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use hexx::{Hex, shapes};
+
+use crate::game_state::UpdateUI;
+use crate::network::client::is_spectator;
+use crate::space_ships::{SpaceShip, SpaceShipCharacteristics};
+use crate::world::ai::is_watched_game;
+use crate::world::fleets::Fleet;
+use crate::world::ownership::SpaceShipsInfo;
+use crate::world::player::{Movable, Player, TurnOrder};
+use crate::world::setup_world_grid::{HexGrid, Planet};
+
+const FOG_TILE_COLOR: Color = Color::rgb(0.35, 0.35, 0.35);
+
+pub struct FogOfWarPlugin;
+
+impl Plugin for FogOfWarPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(FogOfWar::default())
+            .insert_resource(Viewer::default())
+            .add_systems(Update, (
+                update_fog_of_war,
+                (update_fog_tiles, update_fleets_visibility),
+            ).chain().in_set(UpdateUI::RenderStats))
+            .add_systems(Update, switch_perspective.run_if(is_spectator.or_else(is_watched_game)));
+    }
+}
+
+/// What a player saw on a hex the last time it was visible to them.
+#[derive(Debug, Clone)]
+pub(crate) struct PlanetIntel {
+    pub owner: Player,
+    pub army: Vec<SpaceShip>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PlayerView {
+    pub visible: HashSet<Hex>,
+    pub last_known: HashMap<Hex, PlanetIntel>,
+}
+
+pub(crate) enum Intel<'a> {
+    Live,
+    Remembered(&'a PlanetIntel),
+    Unknown,
+}
+
+/// Whose eyes the map is rendered through.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Resource)]
+pub(crate) enum Viewer {
+    /// Hotseat: the player who moves sees the board.
+    #[default]
+    CurrentPlayer,
+    /// Network game or a spectator following one side: always this player's view.
+    Seat(Player),
+    /// Spectators and those watching a game of computers only may look at the whole map.
+    FullMap,
+}
+
+impl Viewer {
+    /// `None` means the whole map is shown. Nobody moves between rounds or once the game is over,
+    /// the board then stays in the eyes of whoever looked at it last.
+    pub(crate) fn resolve(&self, current_player: Option<&Player>, fog: &FogOfWar) -> Option<Player> {
+        match self {
+            Viewer::CurrentPlayer => current_player.copied().or(fog.last_viewer),
+            Viewer::Seat(player) => Some(*player),
+            Viewer::FullMap => None,
+        }
+    }
+
+    /// The whole map, then what every player sees in turn order, then the whole map again.
+    pub(crate) fn next(&self, players: &[Player]) -> Viewer {
+        match self {
+            Viewer::Seat(player) => match players.iter().position(|other| other == player) {
+                Some(index) if index + 1 < players.len() => Viewer::Seat(players[index + 1]),
+                _ => Viewer::FullMap,
+            },
+            _ => players.first().map_or(Viewer::FullMap, |player| Viewer::Seat(*player)),
+        }
+    }
+}
+
+/// Those who only watch switch between the perspectives with V.
+fn switch_perspective(keyboard_input: Res<Input<KeyCode>>, turn_order: Res<TurnOrder>, mut viewer: ResMut<Viewer>) {
+    if !keyboard_input.just_pressed(KeyCode::V) { return; }
+    *viewer = viewer.next(&turn_order.players);
+}
+
+#[derive(Debug, Default, Clone, Resource)]
+pub(crate) struct FogOfWar {
+    pub views: HashMap<Player, PlayerView>,
+    /// Player the board was last rendered for.
+    pub last_viewer: Option<Player>,
+}
+
+impl FogOfWar {
+    pub(crate) fn is_visible(&self, player: &Player, hex: &Hex) -> bool {
+        self.views.get(player).map_or(false, |view| view.visible.contains(hex))
+    }
+
+    pub(crate) fn get_intel(&self, player: &Player, hex: &Hex) -> Intel {
+        let Some(view) = self.views.get(player) else { return Intel::Unknown; };
+        if view.visible.contains(hex) {
+            return Intel::Live;
+        }
+        match view.last_known.get(hex) {
+            Some(intel) => Intel::Remembered(intel),
+            None => Intel::Unknown,
+        }
+    }
+}
+
+fn get_sensor_range(army: &[SpaceShip]) -> u32 {
+    army.iter()
+        .map(|ship| SpaceShipCharacteristics::get_by_spaceship_type(ship.ship_type).sensor_range)
+        .max()
+        .unwrap_or(0)
+        .max(1)
+}
+
+/// A player sees their own planets, the neighbours of those and whatever their ships' sensors reach.
+pub(crate) fn compute_visible_hexes<'a>(
+    player: &Player,
+    planets: &HashMap<Hex, Planet>,
+    fleets: impl Iterator<Item=&'a Fleet>,
+) -> HashSet<Hex> {
+    let mut visible = HashSet::new();
+    for (hex, planet) in planets.iter().filter(|(_, planet)| planet.owner == *player) {
+        visible.extend(shapes::hexagon(*hex, get_sensor_range(&planet.owner_army)));
+    }
+    for fleet in fleets.filter(|fleet| fleet.owner == *player) {
+        let range = get_sensor_range(&fleet.ships);
+        visible.extend(shapes::hexagon(fleet.current_hex(), range));
+        if let Some(next) = fleet.next_hex() {
+            visible.extend(shapes::hexagon(next, range));
+        }
+    }
+    visible.retain(|hex| planets.contains_key(hex));
+    visible
+}
+
+pub(crate) fn update_fog_of_war(
+    mut fog: ResMut<FogOfWar>,
+    grid: Res<HexGrid>,
+    fleets_query: Query<&Fleet>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+) {
+    let Some(player) = viewer.resolve(current_player_query.get_single().ok(), &fog) else { return; };
+    fog.last_viewer = Some(player);
+    let visible = compute_visible_hexes(&player, &grid.planets, fleets_query.iter());
+    let view = fog.views.entry(player).or_insert_with(PlayerView::default);
+    for hex in visible.iter() {
+        let planet = &grid.planets[hex];
+        view.last_known.insert(*hex, PlanetIntel { owner: planet.owner, army: planet.owner_army.clone() });
+    }
+    if view.visible != visible {
+        view.visible = visible;
+    }
+}
+
+pub(crate) fn grey_out(color: Color) -> Color {
+    let [r, g, b, a] = color.as_rgba_f32();
+    Color::rgba((r + 0.5) / 3.0, (g + 0.5) / 3.0, (b + 0.5) / 3.0, a)
+}
+
+fn fog_tint(color: Color) -> Color {
+    let [r, g, b, a] = color.as_rgba_f32();
+    let [fog_r, fog_g, fog_b, _] = FOG_TILE_COLOR.as_rgba_f32();
+    Color::rgba(r * fog_r, g * fog_g, b * fog_b, a)
+}
+
+fn update_fog_tiles(
+    fog: Res<FogOfWar>,
+    grid: Res<HexGrid>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut tiles: Query<&mut TextureAtlasSprite, Without<SpaceShipsInfo>>,
+) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
+    for (hex, entity) in grid.entities.iter() {
+        let Ok(mut sprite) = tiles.get_mut(*entity) else { continue; };
+        let is_visible = player.map_or(true, |player| fog.is_visible(&player, hex));
+        // Terrain is known from the start, only its tint fades under the fog
+        let tint = grid.planets[hex].terrain.tint();
+        let color = if is_visible { tint } else { fog_tint(tint) };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+/// Fleets inside a nebula are only seen by their owner.
+fn update_fleets_visibility(
+    fog: Res<FogOfWar>,
+    grid: Res<HexGrid>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut fleets_query: Query<(&Fleet, &mut Visibility)>,
+) {
+    let Some(player) = viewer.resolve(current_player_query.get_single().ok(), &fog) else {
+        fleets_query.iter_mut().for_each(|(_, mut visibility)| *visibility = Visibility::Inherited);
+        return;
+    };
+    for (fleet, mut visibility) in fleets_query.iter_mut() {
+        let is_hidden = grid.planets[&fleet.current_hex()].terrain.hides_fleets();
+        let is_seen = fleet.owner == player
+            || (!is_hidden && (fog.is_visible(&player, &fleet.current_hex())
+            || fleet.next_hex().map_or(false, |next| fog.is_visible(&player, &next))));
+        *visibility = if is_seen { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::Hex;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::space_ships::{ShipIds, SpaceShip, SpaceShipType};
+use crate::ui::galactic_event::plugin::GalacticEventState;
+use crate::ui::stats::resources::Round;
+use crate::world::actions::move_menu::systems::battle::move_army_to_planet;
+use crate::world::data::load_data;
+use crate::world::defense::BattleModifiers;
+use crate::world::buildings::BuildingType;
+use crate::world::factions::Factions;
+use crate::world::game_log::{format_hex, GameEvent, GameLog};
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+use crate::world::random::GameRng;
+use crate::world::research::Research;
+use crate::world::resources::{GameResources, PlayerResources};
+use crate::world::scenario::Scenario;
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::tutorial::Tutorial;
+
+/// Events are described in a data file, like the tech tree.
+const EVENTS_FILE: &str = "data/events.json";
+
+pub struct GalacticEventsPlugin;
+
+impl Plugin for GalacticEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(GalacticEvents::load());
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum EventEffect {
+    /// Halves the production of `hexes` random planets for `rounds` rounds.
+    SolarStorm { hexes: usize, rounds: i32 },
+    /// `ships` join a random player on one of their planets.
+    DerelictShips { ships: Vec<SpaceShipType> },
+    /// Pirates with `ships` attack a random planet of a player and keep it if they win.
+    PirateRaid { ships: Vec<SpaceShipType> },
+    /// The owner of a random planet gets `bonus` once.
+    ResourceBoom { bonus: PlayerResources },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GalacticEvent {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// How often the event is drawn compared to the others.
+    pub weight: u32,
+    pub effect: EventEffect,
+}
+
+/// The event deck and what the drawn events left behind.
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct GalacticEvents {
+    pub deck: Vec<GalacticEvent>,
+    /// Last round of the storm over every hex it hit.
+    pub storms: HashMap<Hex, i32>,
+    /// Name and outcome of the last drawn event, shown until the popup is closed.
+    pub announcement: Option<(String, String)>,
+}
+
+impl GalacticEvents {
+    pub(crate) fn new(deck: Vec<GalacticEvent>) -> Self {
+        Self { deck, storms: HashMap::new(), announcement: None }
+    }
+
+    /// The deck of the data file, nothing drawn yet.
+    pub(crate) fn load() -> Self {
+        Self::new(load_data(EVENTS_FILE))
+    }
+
+    /// Shows `text` in the popup, added below whatever is announced already.
+    pub(crate) fn announce(&mut self, title: String, text: String) {
+        self.announcement = Some(match self.announcement.take() {
+            Some((previous_title, previous_text)) => (previous_title, format!("{}\n\n{}\n{}", previous_text, title, text)),
+            None => (title, text),
+        });
+    }
+
+    /// Hexes whose production is halved during `round`.
+    pub(crate) fn stormy_hexes(&self, round: i32) -> Vec<Hex> {
+        self.storms.iter().filter(|(_, until)| **until >= round).map(|(hex, _)| *hex).collect()
+    }
+}
+
+fn format_hexes(hexes: &[Hex]) -> String {
+    hexes.iter().map(format_hex).collect::<Vec<String>>().join(", ")
+}
+
+/// A card is drawn whenever the first player starts a new round.
+/// The tutorial plays out as written, scenarios can turn events off as well.
+pub(crate) fn are_events_drawn(tutorial: Option<Res<Tutorial>>, scenario: Option<Res<Scenario>>) -> bool {
+    tutorial.is_none() && scenario.map_or(true, |scenario| scenario.events)
+}
+
+/// An event is drawn when the first player still in the game starts their turn, from the second round on.
+pub(crate) fn is_event_due<'a>(player: &Player, round: i32, turn_order: &TurnOrder, stats: impl Iterator<Item=(&'a Player, &'a Stats)>) -> bool {
+    round > 1 && turn_order.active(stats).first() == Some(player)
+}
+
+pub(crate) fn draw_galactic_event(
+    player_query: Query<&Player, With<Movable>>,
+    mut events: ResMut<GalacticEvents>,
+    mut grid: ResMut<HexGrid>,
+    mut game_resources: ResMut<GameResources>,
+    mut rng: ResMut<GameRng>,
+    mut ship_ids: ResMut<ShipIds>,
+    research: Res<Research>,
+    factions: Res<Factions>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    turn_order: Res<TurnOrder>,
+    stats_query: Query<(&Player, &Stats)>,
+    mut event_state: ResMut<NextState<GalacticEventState>>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+    if !is_event_due(player, round.number, &turn_order, stats_query.iter()) { return; }
+    let Some((title, text)) = draw_event(&mut events, &mut grid, &mut game_resources, &mut rng, &mut ship_ids, &research, &factions, &mut game_log, round.number) else { return; };
+    events.announce(title, text);
+    event_state.set(GalacticEventState::Shown);
+}
+
+/// Draws a card and plays it out, returns the name of the event and what is announced about it.
+pub(crate) fn draw_event(
+    events: &mut GalacticEvents,
+    grid: &mut HexGrid,
+    game_resources: &mut GameResources,
+    rng: &mut GameRng,
+    ship_ids: &mut ShipIds,
+    research: &Research,
+    factions: &Factions,
+    game_log: &mut GameLog,
+    round: i32,
+) -> Option<(String, String)> {
+    let event = events.deck.choose_weighted(&mut rng.0, |event| event.weight).ok().cloned()?;
+
+    // Sorted, so that the same dice pick the same planets
+    let mut hexes: Vec<Hex> = grid.planets.keys().copied().collect();
+    hexes.sort_by_key(|hex| (hex.x, hex.y));
+    let colonies: Vec<Hex> = hexes.iter().copied().filter(|hex| grid.planets[hex].owner.id != -1).collect();
+
+    let outcome = match &event.effect {
+        EventEffect::SolarStorm { hexes: count, rounds } => {
+            let productive: Vec<Hex> = hexes.iter().copied().filter(|hex| grid.planets[hex].resource + grid.planets[hex].influence > 0).collect();
+            let hit: Vec<Hex> = productive.choose_multiple(&mut rng.0, *count).copied().collect();
+            for hex in hit.iter() {
+                events.storms.insert(*hex, round + rounds - 1);
+            }
+            format!("production is halved at {} until round {}", format_hexes(&hit), round + rounds - 1)
+        }
+        EventEffect::DerelictShips { ships } => match colonies.choose(&mut rng.0) {
+            Some(hex) => {
+                let planet = grid.planets.get_mut(hex).unwrap();
+                for ship_type in ships.iter() {
+                    planet.owner_army.push(SpaceShip::new(ship_ids.next(), *ship_type, planet.owner, *hex));
+                }
+                format!("{} ships joined {} at {}", ships.len(), factions.name(&planet.owner), format_hex(hex))
+            }
+            None => "the ships drifted away".to_string(),
+        },
+        EventEffect::PirateRaid { ships } => match colonies.choose(&mut rng.0).copied() {
+            Some(hex) => {
+                let pirates = Player { id: -1 };
+                let mut planet = grid.planets.remove(&hex).unwrap();
+                let defender = planet.owner;
+                let army: Vec<SpaceShip> = ships.iter()
+                    .map(|ship_type| SpaceShip::new(ship_ids.next(), *ship_type, pirates, hex))
+                    .collect();
+                let mut defense_platforms = planet.count_finished(BuildingType::DefensePlatform);
+                let modifiers = BattleModifiers::of_planet(&planet, &factions);
+                let (winner, winner_army) = move_army_to_planet(&mut rng.0, &research, pirates, defender, army, planet.owner_army, &mut defense_platforms, modifiers);
+                planet.destroy_buildings(BuildingType::DefensePlatform, defense_platforms);
+                if winner == pirates {
+                    planet.on_captured();
+                }
+                planet.owner = winner;
+                planet.owner_army = winner_army;
+                grid.planets.insert(hex, planet);
+                if winner == pirates {
+                    format!("pirates took {} from {}", format_hex(&hex), factions.name(&defender))
+                } else {
+                    format!("{} fought off pirates at {}", factions.name(&defender), format_hex(&hex))
+                }
+            }
+            None => "the pirates found nothing to raid".to_string(),
+        },
+        EventEffect::ResourceBoom { bonus } => match colonies.choose(&mut rng.0) {
+            Some(hex) => {
+                let owner = grid.planets[hex].owner;
+                *game_resources.resources.entry(owner).or_insert_with(PlayerResources::default) += *bonus;
+                format!("{} gained {} resources and {} influence at {}", factions.name(&owner), bonus.resources, bonus.influence, format_hex(hex))
+            }
+            None => "nobody was there to profit".to_string(),
+        },
+    };
+
+    game_log.record(round, Player { id: -1 }, GameEvent::GalacticEvent { name: event.name.clone(), outcome: outcome.clone() });
+    Some((event.name, format!("{}\n{}", event.description, outcome)))
+}
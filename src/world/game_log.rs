@@ -0,0 +1,218 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::space_ships::SpaceShipType;
+use crate::world::buildings::BuildingType;
+use crate::world::diplomacy::ProposalKind;
+use crate::world::fleets::Fleet;
+use crate::world::fog_of_war::compute_visible_hexes;
+use crate::world::player::{Player, TurnOrder};
+use crate::world::setup_world_grid::HexGrid;
+
+pub struct GameLogPlugin;
+
+impl Plugin for GameLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameLog::default())
+            // Before the next turn starts, so the board is still the one the event happened on
+            .add_systems(PostUpdate, note_witnesses);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum GameEvent {
+    ShipsBought { hex: Hex, ships: Vec<SpaceShipType> },
+    ShipDelivered { hex: Hex, ship: SpaceShipType },
+    PlanetFortified { hex: Hex },
+    Surrendered,
+    PlayerEliminated,
+    OutOfTime,
+    FleetLaunched { from: Hex, to: Hex, ships: usize },
+    Battle { hex: Hex, defender: Player, winner: Player, attacker_ships: usize, defender_ships: usize, survivors: usize },
+    PlanetCaptured { hex: Hex, previous_owner: Player },
+    BuildingStarted { hex: Hex, building: BuildingType },
+    BuildingFinished { hex: Hex, building: BuildingType },
+    ResearchStarted { tech: String },
+    ResearchFinished { tech: String },
+    GalacticEvent { name: String, outcome: String },
+    ProposalMade { to: Player, kind: ProposalKind },
+    ProposalAccepted { from: Player, kind: ProposalKind },
+    ProposalDeclined { from: Player, kind: ProposalKind },
+    TreatyBroken { with: Player, penalty: u32 },
+    RoundSkipped,
+}
+
+/// Something `player` did (or what happened to their fleets) during `round`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct LogEntry {
+    pub round: i32,
+    pub player: Player,
+    pub event: GameEvent,
+    /// Players who could see it happen, `None` until `note_witnesses` looked at the board.
+    #[serde(default)]
+    pub witnesses: Option<Vec<Player>>,
+}
+
+impl LogEntry {
+    pub(crate) fn is_witnessed_by(&self, player: &Player) -> bool {
+        self.player == *player || self.witnesses.as_ref().map_or(false, |witnesses| witnesses.contains(player))
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+pub(crate) struct GameLog {
+    pub entries: Vec<LogEntry>,
+    /// Number of entries each player has already been shown.
+    seen_until: HashMap<Player, usize>,
+}
+
+impl GameLog {
+    pub(crate) fn record(&mut self, round: i32, player: Player, event: GameEvent) {
+        info!("Round {}, {}: {}", round, player, event);
+        self.entries.push(LogEntry { round, player, event, witnesses: None });
+    }
+
+    pub(crate) fn unseen_by(&self, player: &Player) -> &[LogEntry] {
+        let from = self.seen_until.get(player).copied().unwrap_or(0);
+        &self.entries[from.min(self.entries.len())..]
+    }
+
+    pub(crate) fn mark_seen(&mut self, player: &Player) {
+        self.seen_until.insert(*player, self.entries.len());
+    }
+}
+
+impl GameEvent {
+    /// Hex the event can be seen on, `None` for what is announced to everybody.
+    fn location(&self) -> Option<Hex> {
+        match self {
+            GameEvent::ShipsBought { hex, .. }
+            | GameEvent::ShipDelivered { hex, .. }
+            | GameEvent::PlanetFortified { hex }
+            | GameEvent::Battle { hex, .. }
+            | GameEvent::PlanetCaptured { hex, .. }
+            | GameEvent::BuildingStarted { hex, .. }
+            | GameEvent::BuildingFinished { hex, .. } => Some(*hex),
+            GameEvent::FleetLaunched { from, .. } => Some(*from),
+            _ => None,
+        }
+    }
+
+    /// Players the event happens to, they always learn about it.
+    fn concerns(&self, player: &Player) -> bool {
+        match self {
+            GameEvent::Battle { defender, .. } => defender == player,
+            GameEvent::PlanetCaptured { previous_owner, .. } => previous_owner == player,
+            GameEvent::ProposalMade { to, .. } => to == player,
+            GameEvent::ProposalAccepted { from, .. } | GameEvent::ProposalDeclined { from, .. } => from == player,
+            GameEvent::TreatyBroken { with, .. } => with == player,
+            _ => false,
+        }
+    }
+
+    /// Whether an event without a location is known to everybody.
+    fn is_public(&self) -> bool {
+        !matches!(self,
+            GameEvent::ResearchStarted { .. } | GameEvent::ResearchFinished { .. }
+            | GameEvent::ProposalMade { .. } | GameEvent::ProposalAccepted { .. } | GameEvent::ProposalDeclined { .. }
+            | GameEvent::TreatyBroken { .. })
+    }
+}
+
+fn note_witnesses(
+    mut game_log: ResMut<GameLog>,
+    grid: Res<HexGrid>,
+    fleets_query: Query<&Fleet>,
+    turn_order: Res<TurnOrder>,
+) {
+    if game_log.entries.last().map_or(true, |entry| entry.witnesses.is_some()) { return; }
+    game_log.note_witnesses(&turn_order.players, &grid, fleets_query.iter());
+}
+
+impl GameLog {
+    /// Events are only told to the players whose fog of war allowed them to see it at the time.
+    pub(crate) fn note_witnesses<'a>(&mut self, players: &[Player], grid: &HexGrid, fleets: impl Iterator<Item=&'a Fleet>) {
+        let fleets: Vec<&Fleet> = fleets.collect();
+        let visible: HashMap<Player, _> = players.iter()
+            .map(|player| (*player, compute_visible_hexes(player, &grid.planets, fleets.iter().copied())))
+            .collect();
+        for entry in self.entries.iter_mut().rev().take_while(|entry| entry.witnesses.is_none()) {
+            let witnesses = players.iter()
+                .filter(|player| entry.event.concerns(player) || match entry.event.location() {
+                    Some(hex) => visible[*player].contains(&hex),
+                    None => entry.event.is_public(),
+                })
+                .copied()
+                .collect();
+            entry.witnesses = Some(witnesses);
+        }
+    }
+}
+
+pub(crate) fn format_hex(hex: &Hex) -> String {
+    format!("({}, {})", hex.x, hex.y)
+}
+
+fn format_owner(player: &Player) -> String {
+    if player.id == -1 { "neutrals".to_string() } else { player.to_string() }
+}
+
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameEvent::ShipsBought { hex, ships } => {
+                let mut counts: Vec<(String, usize)> = vec![];
+                for ship_type in ships.iter() {
+                    let name = ship_type.to_string();
+                    match counts.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, count)) => *count += 1,
+                        None => counts.push((name, 1)),
+                    }
+                }
+                let bought: Vec<String> = counts.iter().map(|(name, count)| format!("{} {}", count, name)).collect();
+                write!(f, "ordered {} at {}", bought.join(", "), format_hex(hex))
+            }
+            GameEvent::ShipDelivered { hex, ship } => write!(f, "received a {} at {}", ship, format_hex(hex)),
+            GameEvent::PlanetFortified { hex } => write!(f, "fortified {}", format_hex(hex)),
+            GameEvent::Surrendered => write!(f, "surrendered"),
+            GameEvent::PlayerEliminated => write!(f, "was eliminated"),
+            GameEvent::OutOfTime => write!(f, "ran out of time"),
+            GameEvent::FleetLaunched { from, to, ships } => {
+                write!(f, "sent {} ships from {} to {}", ships, format_hex(from), format_hex(to))
+            }
+            GameEvent::Battle { hex, defender, winner, attacker_ships, defender_ships, survivors } => {
+                write!(f, "attacked {} at {} with {} ships against {}, {} won with {} ships left",
+                       format_owner(defender), format_hex(hex), attacker_ships, defender_ships, format_owner(winner), survivors)
+            }
+            GameEvent::PlanetCaptured { hex, previous_owner } => {
+                write!(f, "captured {} from {}", format_hex(hex), format_owner(previous_owner))
+            }
+            GameEvent::BuildingStarted { hex, building } => {
+                write!(f, "started building a {} at {}", building, format_hex(hex))
+            }
+            GameEvent::BuildingFinished { hex, building } => {
+                write!(f, "finished a {} at {}", building, format_hex(hex))
+            }
+            GameEvent::ResearchStarted { tech } => write!(f, "started researching {}", tech),
+            GameEvent::ResearchFinished { tech } => write!(f, "finished researching {}", tech),
+            GameEvent::GalacticEvent { name, outcome } => write!(f, "{}: {}", name, outcome),
+            GameEvent::ProposalMade { to, kind } => write!(f, "proposed {} to {}", kind, format_owner(to)),
+            GameEvent::ProposalAccepted { from, kind } => write!(f, "accepted {} from {}", kind, format_owner(from)),
+            GameEvent::ProposalDeclined { from, kind } => write!(f, "declined {} from {}", kind, format_owner(from)),
+            GameEvent::TreatyBroken { with, penalty } => write!(f, "broke the treaty with {} and lost {} influence", format_owner(with), penalty),
+            GameEvent::RoundSkipped => write!(f, "skipped the rest of the round"),
+        }
+    }
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Galactic events happen to nobody in particular
+        if self.player.id == -1 { return write!(f, "Round {}, {}", self.round, self.event); }
+        write!(f, "Round {}, {} {}", self.round, self.player, self.event)
+    }
+}
@@ -1,17 +1,67 @@
 use bevy::app::{App, Plugin, Startup, Update};
-use bevy::prelude::IntoSystemConfigs;
+use bevy::prelude::{apply_deferred, in_state, IntoSystemConfigs, OnEnter};
 use crate::game_state::UpdateUI;
+use crate::network::client::is_local_game;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::diplomacy::plugin::DiplomacyState;
+use crate::ui::handover::plugin::HandoverState;
+use crate::ui::tech_tree::plugin::TechTreeState;
 
 use crate::world::actions::ActionsPlugin;
+use crate::world::ai::AiPlugin;
+use crate::world::buildings::BuildingsPlugin;
+use crate::world::campaign::CampaignPlugin;
+use crate::world::defense::DefensePlugin;
+use crate::world::diplomacy::DiplomacyPlugin;
+use crate::world::elimination::EliminationPlugin;
 use crate::world::button_click::UIClickPlugin;
+use crate::world::fleets::FleetsPlugin;
+use crate::world::fog_of_war::FogOfWarPlugin;
+use crate::world::galactic_events::GalacticEventsPlugin;
+use crate::world::game_log::GameLogPlugin;
+use crate::world::random::GameRng;
+use crate::world::production::ProductionPlugin;
+use crate::world::research::ResearchPlugin;
+use crate::world::scenario::ScenarioPlugin;
+use crate::world::supply::SupplyPlugin;
+use crate::world::turn_actions::TurnActionsPlugin;
+use crate::world::turn_timer::TurnTimerPlugin;
+use crate::world::tutorial::TutorialPlugin;
+use crate::world::undo::UndoPlugin;
 
 pub mod navigations_systems;
 pub mod player;
 pub mod setup_world_grid;
 pub mod resources;
 pub(crate) mod actions;
+pub(crate) mod ai;
 pub(crate) mod fonts_and_styles;
 pub mod ownership;
+pub(crate) mod buildings;
+pub(crate) mod campaign;
+pub(crate) mod data;
+pub(crate) mod defense;
+pub(crate) mod diplomacy;
+pub(crate) mod elimination;
+pub(crate) mod factions;
+pub(crate) mod fleets;
+pub(crate) mod fog_of_war;
+pub(crate) mod galactic_events;
+pub(crate) mod game_log;
+pub(crate) mod objectives;
+pub(crate) mod pathfinding;
+pub(crate) mod production;
+pub(crate) mod random;
+pub(crate) mod research;
+pub(crate) mod scenario;
+pub(crate) mod snapshot;
+pub(crate) mod supply;
+pub(crate) mod terrain;
+pub(crate) mod turn_actions;
+pub(crate) mod turn_timer;
+pub(crate) mod tutorial;
+pub(crate) mod undo;
+pub(crate) mod victory;
 mod create_map_layout;
 mod button_click;
 pub struct WorldPlugin;
@@ -21,8 +71,43 @@ impl Plugin for WorldPlugin {
         app
             .add_plugins(ActionsPlugin)
             .add_plugins(UIClickPlugin)
+            .add_plugins(FleetsPlugin)
+            .add_plugins(BuildingsPlugin)
+            .add_plugins(ProductionPlugin)
+            .add_plugins(DefensePlugin)
+            .add_plugins(ResearchPlugin)
+            .add_plugins(GalacticEventsPlugin)
+            .add_plugins(DiplomacyPlugin)
+            .add_plugins(EliminationPlugin)
+            .add_plugins(SupplyPlugin)
+            .add_plugins(FogOfWarPlugin)
+            .add_plugins(GameLogPlugin)
+            .add_plugins(UndoPlugin)
+            .add_plugins(TurnActionsPlugin)
+            .add_plugins(TurnTimerPlugin)
+            .add_plugins(TutorialPlugin)
+            .add_plugins(ScenarioPlugin)
+            .add_plugins(CampaignPlugin)
+            .add_plugins(AiPlugin)
+            .init_resource::<GameRng>()
+            // Same order as `ServerGame::start_turn`, so local and online games play a turn start alike
+            .add_systems(OnEnter(TurnSwitchedState::OnDefaultState), (
+                (
+                    diplomacy::expire_pacts,
+                    galactic_events::draw_galactic_event.run_if(galactic_events::are_events_drawn),
+                    fleets::advance_fleets,
+                    // Fleets destroyed or landed are despawned before supply looks at the board
+                    apply_deferred,
+                    supply::update_supply,
+                    production::advance_production,
+                    buildings::advance_construction,
+                    research::advance_research,
+                    ownership::update_player_resources,
+                ).chain().run_if(is_local_game),
+                turn_timer::start_turn,
+            ).chain())
             .add_systems(Startup, (setup_world_grid::setup_camera, setup_world_grid::setup_grid))
             .add_systems(Startup, setup_world_grid::register_selected_hex)
-            .add_systems(Update, (button_click::set_ui_clicked, setup_world_grid::handle_click_on_planet).chain().in_set(UpdateUI::FieldSelect));
+            .add_systems(Update, (button_click::set_ui_clicked, setup_world_grid::handle_click_on_planet.run_if(in_state(HandoverState::Hidden)).run_if(in_state(TechTreeState::Hidden)).run_if(in_state(DiplomacyState::Hidden))).chain().in_set(UpdateUI::FieldSelect));
     }
 }
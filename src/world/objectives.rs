@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::world::player::Player;
+use crate::world::setup_world_grid::HexGrid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Objective {
+    pub hex: Hex,
+    /// Win points its owner gets at the end of every round.
+    pub points: i32,
+}
+
+/// Objective hexes of the map and the points every player has scored on each of them.
+#[derive(Debug, Clone, Default, Resource)]
+pub(crate) struct Objectives {
+    pub list: Vec<Objective>,
+    pub earned: HashMap<Player, HashMap<Hex, i32>>,
+}
+
+impl Objectives {
+    pub(crate) fn new(objectives: HashMap<Hex, i32>) -> Self {
+        let mut list: Vec<Objective> = objectives.into_iter().map(|(hex, points)| Objective { hex, points }).collect();
+        list.sort_by_key(|objective| (-objective.points, objective.hex.x, objective.hex.y));
+        Self { list, earned: HashMap::new() }
+    }
+
+    pub(crate) fn get(&self, hex: &Hex) -> Option<&Objective> {
+        self.list.iter().find(|objective| objective.hex == *hex)
+    }
+
+    /// Gives `player` the points of every objective they hold, returns how many they got.
+    pub(crate) fn award(&mut self, grid: &HexGrid, player: &Player) -> i32 {
+        let mut total = 0;
+        for objective in self.list.iter() {
+            if grid.planets.get(&objective.hex).map_or(false, |planet| planet.owner == *player) {
+                *self.earned.entry(*player).or_insert_with(HashMap::new).entry(objective.hex).or_insert(0) += objective.points;
+                total += objective.points;
+            }
+        }
+        total
+    }
+
+    pub(crate) fn earned_by(&self, player: &Player, hex: &Hex) -> i32 {
+        self.earned.get(player).and_then(|earned| earned.get(hex)).copied().unwrap_or(0)
+    }
+}
+
+/// `0,0:2;1,1:1` - objective hexes with their points, as given on the command line.
+pub(crate) fn parse_objectives(value: &str) -> HashMap<Hex, i32> {
+    value.split(';')
+        .filter_map(|objective| {
+            let (hex, points) = objective.split_once(':').unwrap_or((objective, "1"));
+            let (x, y) = hex.split_once(',')?;
+            let hex = Hex { x: x.trim().parse().ok()?, y: y.trim().parse().ok()? };
+            Some((hex, points.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objectives_are_read_with_their_points() {
+        let objectives = parse_objectives("0,0:2; 1,-1 : 3");
+        assert_eq!(objectives.len(), 2);
+        assert_eq!(objectives[&Hex::ZERO], 2);
+        assert_eq!(objectives[&Hex { x: 1, y: -1 }], 3);
+    }
+
+    #[test]
+    fn objectives_without_points_give_one() {
+        assert_eq!(parse_objectives("-2,1")[&Hex { x: -2, y: 1 }], 1);
+    }
+
+    #[test]
+    fn malformed_objectives_are_skipped() {
+        let objectives = parse_objectives("0,0:2;1;a,b:1;2,2:x");
+        assert_eq!(objectives.len(), 1);
+        assert!(objectives.contains_key(&Hex::ZERO));
+    }
+
+    #[test]
+    fn most_valuable_objectives_come_first() {
+        let objectives = Objectives::new(parse_objectives("1,1:1;0,0:2;-1,-1:1"));
+        let hexes: Vec<Hex> = objectives.list.iter().map(|objective| objective.hex).collect();
+        assert_eq!(hexes, vec![Hex::ZERO, Hex { x: -1, y: -1 }, Hex { x: 1, y: 1 }]);
+    }
+}
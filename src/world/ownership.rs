@@ -1,14 +1,23 @@
 use bevy::app::{App, Update};
-use bevy::prelude::{Color, Component, IntoSystemConfigs, OnEnter,  Plugin, Query, Res, ResMut, Text, TextureAtlasSprite, With};
+use bevy::prelude::{Color, Component, IntoSystemConfigs, OnEnter,  Plugin, Query, Res, ResMut, Text, TextureAtlasSprite, Visibility, With};
 use bevy::sprite::Sprite;
 use hexx::Hex;
 
 use crate::game_state::UpdateUI;
 use crate::space_ships::SpaceShipType;
-use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::stats::resources::Round;
+use crate::space_ships::SpaceShip;
+use crate::world::factions::Factions;
+use crate::world::galactic_events::GalacticEvents;
+use crate::world::fog_of_war::{FogOfWar, grey_out, Intel, update_fog_of_war, Viewer};
 use crate::world::player::{Movable, Player};
 use crate::world::resources::{GameResources};
 use crate::world::setup_world_grid::HexGrid;
+use crate::world::supply::Supply;
+
+const UNKNOWN_OWNER_COLOR: Color = Color::rgb(0.2, 0.2, 0.2);
+const NEUTRAL_GARRISON_COLOR: Color = Color::rgb(0.55, 0.55, 0.5);
+pub(crate) const CHEVRON_COLOR: Color = Color::GOLD;
 
 #[derive(Component, Debug, Clone)]
 pub struct OwnershipInfo {
@@ -21,79 +30,129 @@ pub struct SpaceShipsInfo {
     pub(crate) space_ship_type: SpaceShipType,
 }
 
+/// One of the chevrons drawn over a ship type of the hex, shown while a ship of that type has the rank.
+#[derive(Component, Debug, Clone)]
+pub struct RankChevron {
+    pub(crate) hex: Hex,
+    pub(crate) space_ship_type: SpaceShipType,
+    pub(crate) rank: u32,
+}
 
 pub struct OwnershipPlugin;
 
 impl Plugin for OwnershipPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_ownership_texts.in_set(UpdateUI::RenderStats));
-        app.add_systems(Update, update_spaceships_texture.in_set(UpdateUI::RenderStats));
-        app.add_systems(OnEnter(TurnSwitchedState::OnDefaultState), update_player_resources.in_set(UpdateUI::NewRound));
-        app.add_systems(Update, update_spaceships_text_value.in_set(UpdateUI::RenderStats));
+        app.add_systems(Update, update_ownership_texts.in_set(UpdateUI::RenderStats).after(update_fog_of_war));
+        app.add_systems(Update, update_spaceships_texture.in_set(UpdateUI::RenderStats).after(update_fog_of_war));
+        app.add_systems(Update, update_spaceships_text_value.in_set(UpdateUI::RenderStats).after(update_fog_of_war));
+        app.add_systems(Update, update_rank_chevrons.in_set(UpdateUI::RenderStats).after(update_fog_of_war));
     }
 }
 
 
+/// Owner and army of the hex as the viewing player knows them, `None` if the hex was never seen.
+fn get_known_planet_state(fog: &FogOfWar, player: Option<&Player>, grid: &HexGrid, hex: &Hex) -> Option<(Player, Vec<SpaceShip>, bool)> {
+    let planet = &grid.planets[hex];
+    let Some(player) = player else { return Some((planet.owner, planet.owner_army.clone(), true)); };
+    match fog.get_intel(player, hex) {
+        Intel::Live => Some((planet.owner, planet.owner_army.clone(), true)),
+        Intel::Remembered(intel) => Some((intel.owner, intel.army.clone(), false)),
+        Intel::Unknown => None,
+    }
+}
+
 fn update_ownership_texts(mut text_query: Query<(&mut Sprite, &OwnershipInfo)>,
-                          grid_res: Res<HexGrid>) {
+                          grid_res: Res<HexGrid>,
+                          fog: Res<FogOfWar>,
+                          viewer: Res<Viewer>,
+                          factions: Res<Factions>,
+                          current_player_query: Query<&Player, With<Movable>>) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
     for (mut sprite, owner) in text_query.iter_mut() {
-        let planet = &grid_res.planets[&owner.hex];
-        match planet.owner.id {
-            -1 => {
-                // text.sections[0].value = "Owner: None".to_string();
-                sprite.color = Color::Rgba { red: 0.0, green: 0.0, blue: 0.0, alpha: 1.0 }
-            }
-            1 => {
-                sprite.color = Color::DARK_GREEN
-            }
-            2 => {
-                sprite.color = Color::MAROON
-            }
-            _ => { panic!() }
-        }
+        let Some((planet_owner, army, is_live)) = get_known_planet_state(&fog, player.as_ref(), &grid_res, &owner.hex) else {
+            sprite.color = UNKNOWN_OWNER_COLOR;
+            continue;
+        };
+        let color = if planet_owner.id == -1 && !army.is_empty() { NEUTRAL_GARRISON_COLOR } else { factions.color(&planet_owner) };
+        sprite.color = if is_live { color } else { grey_out(color) };
     }
 }
 
 
 fn update_spaceships_texture(mut image_query: Query<(&mut TextureAtlasSprite, &SpaceShipsInfo)>,
-                             grid_res: Res<HexGrid>) {
+                             grid_res: Res<HexGrid>,
+                             fog: Res<FogOfWar>,
+                             viewer: Res<Viewer>,
+                             current_player_query: Query<&Player, With<Movable>>) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
     for (mut sprite, owner) in image_query.iter_mut() {
-        let planet = &grid_res.planets[&owner.hex];
-        match planet.owner.id {
-            -1 => {
+        let Some((planet_owner, army, is_live)) = get_known_planet_state(&fog, player.as_ref(), &grid_res, &owner.hex) else {
+            sprite.color = Color::Rgba { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0 };
+            continue;
+        };
+        match planet_owner.id {
+            -1 if army.is_empty() => {
                 // text.sections[0].value = "Owner: None".to_string();
                 sprite.color = Color::Rgba { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0 };
             }
-            1 => {
-                sprite.color = Color::WHITE;
-            }
-            2 => {
-                sprite.color = Color::WHITE;
+            _ => {
+                sprite.color = if is_live { Color::WHITE } else { grey_out(Color::WHITE) };
             }
-            _ => { panic!() }
         }
     }
 }
 
-pub(self) fn update_player_resources(player_query: Query<&Player, With<Movable>>,
+pub(crate) fn update_player_resources(player_query: Query<&Player, With<Movable>>,
                            hex_grid: Res<HexGrid>, 
+                           factions: Res<Factions>,
+                           galactic_events: Res<GalacticEvents>,
+                           round: Res<Round>,
+                           supply: Res<Supply>,
                            mut game_resources: ResMut<GameResources>,) {
     if let Err(_) = player_query.get_single() { return; }
     let player = player_query.single();
-    game_resources.update(&hex_grid, player);
+    game_resources.update(&hex_grid, player, factions.planet_bonus(player), &galactic_events.stormy_hexes(round.number), &supply);
 }
 
 fn update_spaceships_text_value(mut text_query: Query<(&mut Text, &SpaceShipsInfo)>,
-                                grid_res: Res<HexGrid>) {
+                                grid_res: Res<HexGrid>,
+                                fog: Res<FogOfWar>,
+                                viewer: Res<Viewer>,
+                                current_player_query: Query<&Player, With<Movable>>) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
     for (mut sprite, owner) in text_query.iter_mut() {
-        let planet = &grid_res.planets[&owner.hex];
-        let count = planet.owner_army.iter().filter(|spaceship| { spaceship.ship_type == owner.space_ship_type && !spaceship.is_selected_for_move && !spaceship.is_selected_for_buy }).count();
-        sprite.sections[0].value = format!("{count} - ").to_string();
-        sprite.sections[0].style.color = match planet.owner.id {
-            -1 => Color::Rgba { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0 },
-            1 => Color::WHITE,
-            2 => Color::WHITE,
-            _ => panic!(),
-        }
+        let Some((planet_owner, army, is_live)) = get_known_planet_state(&fog, player.as_ref(), &grid_res, &owner.hex) else {
+            sprite.sections[0].value = "? - ".to_string();
+            sprite.sections[0].style.color = Color::Rgba { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0 };
+            continue;
+        };
+        let ships: Vec<&SpaceShip> = army.iter().filter(|spaceship| { spaceship.ship_type == owner.space_ship_type && !spaceship.is_selected_for_move }).collect();
+        sprite.sections[0].value = format!("{} - ", ships.len());
+        let color = match planet_owner.id {
+            -1 if army.is_empty() => Color::Rgba { red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0 },
+            _ => Color::WHITE,
+        };
+        sprite.sections[0].style.color = if is_live { color } else { grey_out(color) };
+    }
+}
+/// Chevrons show the rank of the most experienced ship of the type still on the hex.
+fn update_rank_chevrons(mut chevron_query: Query<(&mut Visibility, &mut Sprite, &RankChevron)>,
+                        grid_res: Res<HexGrid>,
+                        fog: Res<FogOfWar>,
+                        viewer: Res<Viewer>,
+                        current_player_query: Query<&Player, With<Movable>>) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
+    for (mut visibility, mut sprite, chevron) in chevron_query.iter_mut() {
+        let Some((_, army, is_live)) = get_known_planet_state(&fog, player.as_ref(), &grid_res, &chevron.hex) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let rank = army.iter()
+            .filter(|spaceship| spaceship.ship_type == chevron.space_ship_type && !spaceship.is_selected_for_move)
+            .map(|spaceship| spaceship.rank())
+            .max()
+            .unwrap_or(0);
+        *visibility = if chevron.rank <= rank { Visibility::Inherited } else { Visibility::Hidden };
+        sprite.color = if is_live { CHEVRON_COLOR } else { grey_out(CHEVRON_COLOR) };
     }
-}
\ No newline at end of file
+}
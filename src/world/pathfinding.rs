@@ -0,0 +1,197 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use bevy::utils::{HashMap, HashSet};
+use hexx::Hex;
+
+use crate::space_ships::SpaceShip;
+use crate::world::diplomacy::Diplomacy;
+use crate::world::player::Player;
+use crate::world::research::Research;
+use crate::world::setup_world_grid::Planet;
+
+/// Fleets can only fly through planets of their owner or allies and through neutral planets nobody guards,
+/// black holes can't be passed at all.
+pub(crate) fn is_passable_for(player: &Player, planet: &Planet, diplomacy: &Diplomacy) -> bool {
+    planet.terrain.is_enterable()
+        && (planet.owner == *player || diplomacy.is_allied(player, &planet.owner) || (planet.owner.id == -1 && planet.owner_army.is_empty()))
+}
+
+/// Hexes of the map a fleet can jump to from `hex`, the far end of a wormhole counts as a neighbour.
+pub(crate) fn movement_neighbours(hex: Hex, planets: &HashMap<Hex, Planet>) -> impl Iterator<Item=Hex> + '_ {
+    let exit = planets.get(&hex).and_then(|planet| planet.terrain.wormhole_exit());
+    hex.all_neighbors().into_iter()
+        .chain(exit)
+        .filter(move |neighbour| planets.get(neighbour).map_or(false, |planet| planet.terrain.is_enterable()))
+}
+
+/// Cheapest cost of flying from `from` to every hex within `max_cost`, with the hex each one is entered from.
+/// Every jump costs what entering its hex does, so nebulae count twice and a wormhole's far end like a neighbour.
+/// Any enterable hex can end the trip, hexes in between have to be passable for `player`.
+fn explore(player: &Player, from: Hex, planets: &HashMap<Hex, Planet>, diplomacy: &Diplomacy, max_cost: u32) -> HashMap<Hex, (u32, Option<Hex>)> {
+    let mut costs: HashMap<Hex, (u32, Option<Hex>)> = HashMap::new();
+    if !planets.contains_key(&from) { return costs; }
+    let mut queue = BinaryHeap::new();
+    costs.insert(from, (0, None));
+    queue.push(Reverse((0, from.x, from.y)));
+    while let Some(Reverse((cost, x, y))) = queue.pop() {
+        let hex = Hex::new(x, y);
+        if cost > costs[&hex].0 { continue; }
+        if hex != from && !is_passable_for(player, &planets[&hex], diplomacy) { continue; }
+        for next in movement_neighbours(hex, planets) {
+            let next_cost = cost.saturating_add(planets[&next].terrain.movement_cost());
+            if next_cost > max_cost { continue; }
+            if costs.get(&next).map_or(true, |(known, _)| next_cost < *known) {
+                costs.insert(next, (next_cost, Some(hex)));
+                queue.push(Reverse((next_cost, next.x, next.y)));
+            }
+        }
+    }
+    costs
+}
+
+/// Cheapest path between two planets, both ends included, see `path_cost`.
+/// The destination itself may belong to anyone, every hex in between has to be passable for `player`.
+pub(crate) fn find_path(player: &Player, from: Hex, to: Hex, planets: &HashMap<Hex, Planet>, diplomacy: &Diplomacy) -> Option<Vec<Hex>> {
+    if from == to { return None; }
+    let costs = explore(player, from, planets, diplomacy, u32::MAX);
+    let mut path = vec![to];
+    while let Some((_, Some(hex))) = costs.get(path.last().unwrap()) {
+        path.push(*hex);
+    }
+    if *path.last().unwrap() != from { return None; }
+    path.reverse();
+    Some(path)
+}
+
+/// Jumps it takes to fly `path`, the same count ship ranges and fleet speeds are measured in.
+pub(crate) fn path_cost(path: &[Hex], planets: &HashMap<Hex, Planet>) -> u32 {
+    path.iter().skip(1)
+        .map(|hex| planets.get(hex).map_or(1, |planet| planet.terrain.movement_cost()))
+        .sum()
+}
+
+pub(crate) fn can_fly_path(ship: &SpaceShip, path: &[Hex], planets: &HashMap<Hex, Planet>, research: &Research) -> bool {
+    !path.is_empty() && research.ship_characteristics(&ship.ship_owner, ship.ship_type).range >= path_cost(path, planets)
+}
+
+pub(crate) fn get_army_range(army: &[SpaceShip], research: &Research) -> u32 {
+    army.iter()
+        .map(|ship| research.ship_characteristics(&ship.ship_owner, ship.ship_type).range)
+        .max()
+        .unwrap_or(0)
+}
+
+/// All hexes which can be attacked or reinforced by ships currently stationed on planets of `player`.
+pub(crate) fn get_reachable_hexes(player: &Player, planets: &HashMap<Hex, Planet>, research: &Research, diplomacy: &Diplomacy) -> HashSet<Hex> {
+    planets.iter()
+        .filter(|(_, planet)| planet.owner == *player)
+        .flat_map(|(hex, planet)| explore(player, *hex, planets, diplomacy, get_army_range(&planet.owner_army, research)).into_keys())
+        .collect()
+}
+
+pub(crate) fn is_hex_reachable(player: &Player, hex: &Hex, planets: &HashMap<Hex, Planet>, research: &Research, diplomacy: &Diplomacy) -> bool {
+    get_reachable_hexes(player, planets, research, diplomacy).contains(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use hexx::shapes;
+
+    use crate::space_ships::SpaceShipType;
+    use crate::world::terrain::Terrain;
+
+    use super::*;
+
+    const PLAYER: Player = Player { id: 1 };
+
+    /// Empty neutral planets on a board of radius 2, with `terrain` placed on top.
+    fn board(terrain: &[(Hex, Terrain)]) -> HashMap<Hex, Planet> {
+        let mut planets: HashMap<Hex, Planet> = shapes::hexagon(Hex::ZERO, 2)
+            .map(|hex| (hex, Planet::default(hex, 0, 1, 1)))
+            .collect();
+        for (hex, hex_terrain) in terrain {
+            planets.get_mut(hex).unwrap().terrain = *hex_terrain;
+        }
+        planets
+    }
+
+    fn with_carrier(mut planets: HashMap<Hex, Planet>, hex: Hex) -> HashMap<Hex, Planet> {
+        let planet = planets.get_mut(&hex).unwrap();
+        planet.owner = PLAYER;
+        planet.owner_army.push(SpaceShip::new(1, SpaceShipType::Carrier, PLAYER, hex));
+        planets
+    }
+
+    fn wormhole(from: Hex, to: Hex) -> [(Hex, Terrain); 2] {
+        [(from, Terrain::Wormhole { exit: to }), (to, Terrain::Wormhole { exit: from })]
+    }
+
+    #[test]
+    fn open_space_costs_a_jump_per_hex() {
+        let planets = board(&[]);
+        let path = find_path(&PLAYER, Hex::ZERO, Hex::new(2, 0), &planets, &Diplomacy::default()).unwrap();
+        assert_eq!(path, vec![Hex::ZERO, Hex::new(1, 0), Hex::new(2, 0)]);
+        assert_eq!(path_cost(&path, &planets), 2);
+    }
+
+    #[test]
+    fn entering_a_nebula_costs_two_jumps() {
+        let planets = board(&[(Hex::new(1, 0), Terrain::Nebula)]);
+        let path = find_path(&PLAYER, Hex::ZERO, Hex::new(1, 0), &planets, &Diplomacy::default()).unwrap();
+        assert_eq!(path_cost(&path, &planets), 2);
+        let path = find_path(&PLAYER, Hex::ZERO, Hex::new(2, 0), &planets, &Diplomacy::default()).unwrap();
+        assert_eq!(path_cost(&path, &planets), 3);
+    }
+
+    #[test]
+    fn black_holes_are_flown_around() {
+        let planets = board(&[(Hex::new(1, 0), Terrain::BlackHole)]);
+        assert_eq!(find_path(&PLAYER, Hex::ZERO, Hex::new(1, 0), &planets, &Diplomacy::default()), None);
+        let path = find_path(&PLAYER, Hex::ZERO, Hex::new(2, 0), &planets, &Diplomacy::default()).unwrap();
+        assert!(!path.contains(&Hex::new(1, 0)));
+        assert_eq!(path_cost(&path, &planets), 3);
+    }
+
+    #[test]
+    fn wormholes_lead_to_their_exit_in_one_jump() {
+        let planets = board(&wormhole(Hex::new(0, 2), Hex::new(0, -2)));
+        let path = find_path(&PLAYER, Hex::new(0, 1), Hex::new(0, -2), &planets, &Diplomacy::default()).unwrap();
+        assert_eq!(path, vec![Hex::new(0, 1), Hex::new(0, 2), Hex::new(0, -2)]);
+    }
+
+    #[test]
+    fn guarded_planets_end_a_path_but_are_not_passed() {
+        let mut planets = board(&[]);
+        for hex in Hex::ZERO.all_neighbors() {
+            planets.get_mut(&hex).unwrap().owner_army.push(SpaceShip::new(2, SpaceShipType::Frigate, Player { id: -1 }, hex));
+        }
+        assert!(find_path(&PLAYER, Hex::ZERO, Hex::new(1, 0), &planets, &Diplomacy::default()).is_some());
+        assert_eq!(find_path(&PLAYER, Hex::ZERO, Hex::new(2, 0), &planets, &Diplomacy::default()), None);
+    }
+
+    #[test]
+    fn reachable_hexes_use_the_same_costs_as_paths() {
+        let research = Research::new(vec![]);
+        let diplomacy = Diplomacy::default();
+        let reachable = get_reachable_hexes(&PLAYER, &with_carrier(board(&[]), Hex::ZERO), &research, &diplomacy);
+        assert!(reachable.contains(&Hex::new(2, 0)));
+
+        let planets = with_carrier(board(&[(Hex::new(1, 0), Terrain::Nebula)]), Hex::ZERO);
+        let reachable = get_reachable_hexes(&PLAYER, &planets, &research, &diplomacy);
+        assert!(reachable.contains(&Hex::new(1, 0)));
+        assert!(reachable.contains(&Hex::new(2, -1)));
+        assert!(!reachable.contains(&Hex::new(2, 0)));
+
+        let path = find_path(&PLAYER, Hex::ZERO, Hex::new(2, 0), &planets, &diplomacy).unwrap();
+        assert!(!can_fly_path(&planets[&Hex::ZERO].owner_army[0], &path, &planets, &research));
+    }
+
+    #[test]
+    fn reachable_hexes_include_the_far_end_of_a_wormhole() {
+        let planets = with_carrier(board(&wormhole(Hex::new(0, 2), Hex::new(0, -2))), Hex::new(0, 1));
+        let reachable = get_reachable_hexes(&PLAYER, &planets, &Research::new(vec![]), &Diplomacy::default());
+        assert!(reachable.contains(&Hex::new(0, -2)));
+        assert!(!reachable.contains(&Hex::new(1, -2)));
+    }
+}
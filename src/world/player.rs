@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use std::ops::DerefMut;
 
 use bevy::app::{Plugin, Update};
 use bevy::ecs::{
@@ -6,14 +7,20 @@ use bevy::ecs::{
     component::Component,
     system::Commands,
 };
-use bevy::prelude::{Entity, IntoSystemConfigs, NextState, Query, Res, ResMut, State, With, Without};
+use bevy::prelude::{Entity, IntoSystemConfigs, NextState, Query, Res, ResMut, Resource, State, With, Without};
+use serde::{Deserialize, Serialize};
 
 use crate::game_state::UpdateUI;
+use crate::network::client::is_local_game;
 use crate::ui::action_panel::plugin::TurnSwitchedState;
 
 pub const INITIAL_MOVES: i32 = 1;
 
-#[derive(Debug, Component, Copy, Clone, Eq, Hash, PartialEq)]
+/// Every seat a game can have, in turn order.
+pub const PLAYERS: [Player; 4] = [Player { id: 1 }, Player { id: 2 }, Player { id: 3 }, Player { id: 4 }];
+const DEFAULT_PLAYERS_COUNT: usize = 2;
+
+#[derive(Debug, Component, Copy, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     pub id: i32,
 }
@@ -24,10 +31,48 @@ impl Display for Player {
     }
 }
 
+/// Players taking part in the current game, in turn order.
+#[derive(Resource, Debug, Clone)]
+pub struct TurnOrder {
+    pub players: Vec<Player>,
+}
+
+impl TurnOrder {
+    /// `--players 3` sets how many players take part, from 2 up to the number of seats.
+    pub fn from_args(args: impl Iterator<Item=String>) -> Self {
+        let args: Vec<String> = args.collect();
+        let count = args.iter()
+            .position(|arg| arg == "--players")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_PLAYERS_COUNT)
+            .clamp(DEFAULT_PLAYERS_COUNT, PLAYERS.len());
+        Self { players: PLAYERS[..count].to_vec() }
+    }
+
+    pub fn first(&self) -> Player {
+        self.players[0]
+    }
+
+    /// Players still in the game, in turn order.
+    pub fn active<'a>(&self, stats: impl Iterator<Item=(&'a Player, &'a Stats)>) -> Vec<Player> {
+        let eliminated: Vec<Player> = stats.filter(|(_, stats)| stats.eliminated).map(|(player, _)| *player).collect();
+        self.players.iter().filter(|player| !eliminated.contains(player)).copied().collect()
+    }
+
+    /// Players coming after `player`, wrapping around and ending with `player` itself.
+    pub fn after(&self, player: &Player) -> Vec<Player> {
+        let index = self.players.iter().position(|other| other == player).unwrap_or(0);
+        self.players.iter().cycle().skip(index + 1).take(self.players.len()).copied().collect()
+    }
+}
+
 #[derive(Component)]
 pub struct Stats {
     pub moves_left: i32,
     pub win_points: i32,
+    /// Set once the player has lost everything or surrendered, they get no more turns.
+    pub eliminated: bool,
 }
 
 
@@ -48,54 +93,154 @@ impl Default for PlayerBundle {
             stats: Stats {
                 moves_left: INITIAL_MOVES,
                 win_points: 0,
+                eliminated: false,
             },
         }
     }
 }
 
-pub fn spawn_players(mut commands: Commands) {
-    commands
-        .spawn(PlayerBundle {
-            player: Player { id: 1 },
+pub fn spawn_players(mut commands: Commands, turn_order: Res<TurnOrder>) {
+    for player in turn_order.players.iter() {
+        let entity = commands.spawn(PlayerBundle {
+            player: *player,
             ..Default::default()
-        })
-        .insert(Movable);
-    commands.spawn(PlayerBundle {
-        player: Player { id: 2 },
-        ..Default::default()
-    });
+        }).id();
+        if *player == turn_order.first() {
+            commands.entity(entity).insert(Movable);
+        }
+    }
 }
 
 pub struct TurnPlugin;
 
 impl Plugin for TurnPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_systems(Update, determine_turn.in_set(UpdateUI::FlipTurn));
+        app.add_systems(Update, determine_turn.run_if(is_local_game).in_set(UpdateUI::FlipTurn));
+    }
+}
+
+/// Who moves once the current player has finished a turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NextTurn {
+    Player(Player),
+    /// Nobody else has moves left, the current player moves again.
+    Again,
+    RoundOver,
+}
+
+/// The move goes to the next player in turn order who still has moves left,
+/// once nobody has any the round is over. The finished move has to be taken off `current` already.
+pub(crate) fn next_turn<'a>(current: &Player, turn_order: &TurnOrder, stats: impl Iterator<Item=(&'a Player, &'a Stats)>) -> NextTurn {
+    let moves: Vec<(Player, i32)> = stats.map(|(player, stats)| (*player, stats.moves_left)).collect();
+    let moves_left = |player: &Player| moves.iter().find(|(other, _)| other == player).map_or(0, |(_, moves_left)| *moves_left);
+    let next = turn_order.after(current).into_iter()
+        .filter(|player| player != current)
+        .find(|player| moves_left(player) > 0);
+    match next {
+        Some(next) => NextTurn::Player(next),
+        None if moves_left(current) > 0 => NextTurn::Again,
+        None => NextTurn::RoundOver,
     }
 }
 
+/// Everyone gets their moves back, eliminated players sit the new round out. Returns who moves first.
+pub(crate) fn start_round<'a, S: DerefMut<Target=Stats>>(turn_order: &TurnOrder, stats: impl Iterator<Item=(&'a Player, S)>) -> Option<Player> {
+    let mut stats: Vec<(&Player, S)> = stats.collect();
+    for (_, stats) in stats.iter_mut() {
+        stats.moves_left = if stats.eliminated { 0 } else { INITIAL_MOVES };
+    }
+    turn_order.active(stats.iter().map(|(player, stats)| (*player, &**stats))).first().copied()
+}
+
 fn determine_turn(
     mut commands: Commands,
-    mut current_player_query: Query<(Entity, &mut Stats), (With<Player>, With<Movable>)>,
-    opposite_player_query: Query<(Entity, &Stats), (With<Player>, Without<Movable>)>,
+    mut current_player_query: Query<(Entity, &Player, &mut Stats), With<Movable>>,
+    other_players_query: Query<(Entity, &Player, &Stats), Without<Movable>>,
+    turn_order: Res<TurnOrder>,
     turn_switch_state: Res<State<TurnSwitchedState>>,
     mut turn_switch_state_mutable: ResMut<NextState<TurnSwitchedState>>,
 ) {
     match turn_switch_state.get() {
         TurnSwitchedState::OnTurnSwitched => {
-            let (cur_id, mut cur_stats) = current_player_query.single_mut();
-            let (op_id, op_stats) = opposite_player_query.single();
+            let Ok((cur_id, cur_player, mut cur_stats)) = current_player_query.get_single_mut() else { return; };
             cur_stats.moves_left -= 1;
-            if op_stats.moves_left > 0 {
-                commands.entity(cur_id).remove::<Movable>();
-                commands.entity(op_id).insert(Movable);
-                turn_switch_state_mutable.set(TurnSwitchedState::OnDefaultState);
-            } else if op_stats.moves_left == 0 && cur_stats.moves_left > 0 {} else if cur_stats.moves_left == 0 && cur_stats.moves_left == 0 {
-                commands.entity(cur_id).remove::<Movable>();
-            } else {
-                panic!("Should never happen");
+            let stats = other_players_query.iter().map(|(_, player, stats)| (player, stats)).chain(std::iter::once((cur_player, &*cur_stats)));
+            match next_turn(cur_player, &turn_order, stats) {
+                NextTurn::Player(next) => {
+                    let Some((next_id, _, _)) = other_players_query.iter().find(|(_, other, _)| **other == next) else { return; };
+                    commands.entity(cur_id).remove::<Movable>();
+                    commands.entity(next_id).insert(Movable);
+                    turn_switch_state_mutable.set(TurnSwitchedState::OnDefaultState);
+                }
+                NextTurn::Again => turn_switch_state_mutable.set(TurnSwitchedState::OnDefaultState),
+                NextTurn::RoundOver => {
+                    commands.entity(cur_id).remove::<Movable>();
+                }
             }
         }
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn_order(count: usize) -> TurnOrder {
+        TurnOrder { players: PLAYERS[..count].to_vec() }
+    }
+
+    #[test]
+    fn player_count_is_kept_within_the_seats() {
+        let args = |count: &str| vec!["--players".to_string(), count.to_string()].into_iter();
+        assert_eq!(TurnOrder::from_args(std::iter::empty()).players.len(), DEFAULT_PLAYERS_COUNT);
+        assert_eq!(TurnOrder::from_args(args("3")).players, PLAYERS[..3].to_vec());
+        assert_eq!(TurnOrder::from_args(args("9")).players.len(), PLAYERS.len());
+        assert_eq!(TurnOrder::from_args(args("1")).players.len(), DEFAULT_PLAYERS_COUNT);
+    }
+
+    #[test]
+    fn players_after_wrap_around_to_the_player_itself() {
+        let turn_order = turn_order(3);
+        assert_eq!(turn_order.after(&PLAYERS[0]), vec![PLAYERS[1], PLAYERS[2], PLAYERS[0]]);
+        assert_eq!(turn_order.after(&PLAYERS[2]), vec![PLAYERS[0], PLAYERS[1], PLAYERS[2]]);
+    }
+
+    #[test]
+    fn eliminated_players_are_not_active() {
+        let turn_order = turn_order(4);
+        let stats: Vec<(Player, Stats)> = PLAYERS.iter()
+            .map(|player| (*player, Stats { moves_left: INITIAL_MOVES, win_points: 0, eliminated: player.id % 2 == 0 }))
+            .collect();
+        let active = turn_order.active(stats.iter().map(|(player, stats)| (player, stats)));
+        assert_eq!(active, vec![PLAYERS[0], PLAYERS[2]]);
+    }
+
+    #[test]
+    fn turn_passes_to_the_next_player_with_moves_left() {
+        let turn_order = turn_order(3);
+        let mut stats: Vec<(Player, Stats)> = turn_order.players.iter()
+            .map(|player| (*player, Stats { moves_left: INITIAL_MOVES, win_points: 0, eliminated: false }))
+            .collect();
+        stats[0].1.moves_left = 0;
+        stats[1].1.moves_left = 0;
+        let next = |stats: &[(Player, Stats)]| next_turn(&PLAYERS[0], &turn_order, stats.iter().map(|(player, stats)| (player, stats)));
+        assert_eq!(next(&stats), NextTurn::Player(PLAYERS[2]));
+        stats[2].1.moves_left = 0;
+        assert_eq!(next(&stats), NextTurn::RoundOver);
+        stats[0].1.moves_left = 1;
+        assert_eq!(next(&stats), NextTurn::Again);
+    }
+
+    #[test]
+    fn new_round_starts_with_the_first_player_left() {
+        let turn_order = turn_order(3);
+        let mut stats: Vec<(Player, Stats)> = turn_order.players.iter()
+            .map(|player| (*player, Stats { moves_left: 0, win_points: 0, eliminated: *player == PLAYERS[0] }))
+            .collect();
+        let first = start_round(&turn_order, stats.iter_mut().map(|(player, stats)| (&*player, stats)));
+        assert_eq!(first, Some(PLAYERS[1]));
+        let moves: Vec<i32> = stats.iter().map(|(_, stats)| stats.moves_left).collect();
+        assert_eq!(moves, vec![0, INITIAL_MOVES, INITIAL_MOVES]);
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::UpdateUI;
+use crate::space_ships::{ShipIds, SpaceShip, SpaceShipCharacteristics, SpaceShipType};
+use crate::ui::stats::resources::Round;
+use crate::world::fog_of_war::{FogOfWar, Viewer};
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Movable, Player};
+use crate::world::research::Research;
+use crate::world::resources::{GameResources, PlayerResources};
+use crate::world::setup_world_grid::{HexGrid, Planet};
+use crate::world::supply::Supply;
+
+pub struct ProductionPlugin;
+
+impl Plugin for ProductionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, update_production_labels.in_set(UpdateUI::RenderStats));
+    }
+}
+
+/// A ship paid for and waiting in the production queue of a planet.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct QueuedShip {
+    pub ship_type: SpaceShipType,
+    /// Turns of the owner left until the ship is delivered, only the first ship of the queue is worked on.
+    pub turns_left: u32,
+    /// Given back when the order is cancelled.
+    pub paid: PlayerResources,
+}
+
+impl QueuedShip {
+    pub(crate) fn new(ship_type: SpaceShipType, paid: PlayerResources) -> Self {
+        Self { ship_type, turns_left: SpaceShipCharacteristics::get_by_spaceship_type(ship_type).build_time, paid }
+    }
+}
+
+/// One edit of a production queue, the spawn menu records them so that they can be replayed elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum ProductionChange {
+    Queue(SpaceShipType),
+    MoveUp(usize),
+    Cancel(usize),
+}
+
+/// Marks the label showing the production progress of the planet at `hex`.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct ProductionInfo {
+    pub(crate) hex: Hex,
+}
+
+impl Planet {
+    pub(crate) fn queue_ship(&mut self, ship_type: SpaceShipType, paid: PlayerResources) {
+        self.production.push(QueuedShip::new(ship_type, paid));
+    }
+
+    /// Swaps the order at `index` with the one before it.
+    pub(crate) fn move_up_in_queue(&mut self, index: usize) {
+        if index > 0 && index < self.production.len() {
+            self.production.swap(index - 1, index);
+        }
+    }
+
+    /// Removes the order at `index`, the caller refunds what was paid for it.
+    pub(crate) fn cancel_queued(&mut self, index: usize) -> Option<QueuedShip> {
+        if index < self.production.len() { Some(self.production.remove(index)) } else { None }
+    }
+}
+
+/// Whether `player` can afford and has the technology to order a `ship_type` on `planet`.
+pub(crate) fn can_queue(planet: &Planet, player: &Player, player_resources: &PlayerResources, research: &Research, ship_type: SpaceShipType) -> bool {
+    let price = research.ship_characteristics(player, ship_type).price;
+    planet.owner == *player
+        && planet.has_shipyard()
+        && research.is_ship_unlocked(player, ship_type)
+        && player_resources.resources >= price.resources
+        && player_resources.influence >= price.influence
+}
+
+/// Applies `change` to the queue of `planet`, ships are paid when queued and refunded in full when cancelled.
+/// Returns whether anything changed.
+pub(crate) fn change_production(planet: &mut Planet, player: &Player, resources: &mut GameResources, research: &Research, change: ProductionChange) -> bool {
+    match change {
+        ProductionChange::Queue(ship_type) => {
+            let player_resources = resources.resources.get(player).copied().unwrap_or_default();
+            if !can_queue(planet, player, &player_resources, research, ship_type) { return false; }
+            let price = research.ship_characteristics(player, ship_type).price;
+            planet.queue_ship(ship_type, price);
+            resources.resources.insert(*player, player_resources - price);
+            true
+        }
+        ProductionChange::MoveUp(index) => {
+            if planet.owner != *player || index == 0 || index >= planet.production.len() { return false; }
+            planet.move_up_in_queue(index);
+            true
+        }
+        ProductionChange::Cancel(index) => {
+            if planet.owner != *player { return false; }
+            let Some(cancelled) = planet.cancel_queued(index) else { return false; };
+            *resources.resources.entry(planet.owner).or_default() += cancelled.paid;
+            true
+        }
+    }
+}
+
+/// The queue of a planet and the resources of its owner when the spawn menu was opened,
+/// put back when the changes are cancelled.
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct ProductionDraft {
+    pub hex: Hex,
+    pub queue: Vec<QueuedShip>,
+    pub resources: PlayerResources,
+    /// Edits made since, in order.
+    pub changes: Vec<ProductionChange>,
+}
+
+impl ProductionDraft {
+    pub(crate) fn new(planet: &Planet, resources: &GameResources) -> Self {
+        Self { hex: planet.hex, queue: planet.production.clone(), resources: resources.resources.get(&planet.owner).copied().unwrap_or_default(), changes: vec![] }
+    }
+
+    pub(crate) fn restore(&self, grid: &mut HexGrid, resources: &mut GameResources) {
+        let Some(planet) = grid.planets.get_mut(&self.hex) else { return; };
+        planet.production = self.queue.clone();
+        resources.resources.insert(planet.owner, self.resources);
+    }
+
+    /// Ship types in the queue of `planet` which were not there when the draft was taken.
+    pub(crate) fn ordered(&self, planet: &Planet) -> Vec<SpaceShipType> {
+        let mut previous: Vec<SpaceShipType> = self.queue.iter().map(|queued| queued.ship_type).collect();
+        let mut ordered = vec![];
+        for queued in planet.production.iter() {
+            match previous.iter().position(|ship_type| *ship_type == queued.ship_type) {
+                Some(index) => { previous.remove(index); }
+                None => ordered.push(queued.ship_type),
+            }
+        }
+        ordered
+    }
+}
+
+/// The first order of every planet of the player starting their turn gets one turn closer to delivery.
+pub(crate) fn advance_production(
+    player_query: Query<&Player, With<Movable>>,
+    mut grid: ResMut<HexGrid>,
+    mut ship_ids: ResMut<ShipIds>,
+    supply: Res<Supply>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+    deliver_ships(player, &mut grid, &mut ship_ids, &supply, &mut game_log, round.number);
+}
+
+/// Planets without a shipyard or cut off from home don't produce.
+pub(crate) fn deliver_ships(player: &Player, grid: &mut HexGrid, ship_ids: &mut ShipIds, supply: &Supply, game_log: &mut GameLog, round: i32) {
+    for (hex, planet) in grid.planets.iter_mut().filter(|(_, planet)| planet.owner == *player && !planet.production.is_empty()) {
+        if !planet.has_shipyard() || supply.is_cut_off(hex) { continue; }
+        let queued = &mut planet.production[0];
+        queued.turns_left = queued.turns_left.saturating_sub(1);
+        if queued.turns_left > 0 { continue; }
+        let ship_type = planet.production.remove(0).ship_type;
+        planet.owner_army.push(SpaceShip::new(ship_ids.next(), ship_type, *player, *hex));
+        game_log.record(round, *player, GameEvent::ShipDelivered { hex: *hex, ship: ship_type });
+    }
+}
+
+/// Only the owner sees what their planets are building.
+fn update_production_labels(
+    grid: Res<HexGrid>,
+    fog: Res<FogOfWar>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut label_query: Query<(&mut Text, &ProductionInfo)>,
+) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
+    for (mut text, info) in label_query.iter_mut() {
+        let planet = &grid.planets[&info.hex];
+        let is_visible = player.map_or(true, |player| planet.owner == player);
+        let value = match planet.production.first().filter(|_| is_visible) {
+            Some(queued) => {
+                let build_time = SpaceShipCharacteristics::get_by_spaceship_type(queued.ship_type).build_time;
+                let waiting = if planet.production.len() > 1 { format!(" +{}", planet.production.len() - 1) } else { String::new() };
+                format!("{} {}/{}{}", queued.ship_type, build_time - queued.turns_left, build_time, waiting)
+            }
+            None => String::new(),
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::utils::HashMap;
+
+    use super::*;
+
+    const PLAYER: Player = Player { id: 1 };
+    const PRICE: PlayerResources = PlayerResources { influence: 0, resources: 3 };
+
+    fn planet(queue: &[SpaceShipType]) -> Planet {
+        let mut planet = Planet::default(Hex::ZERO, 0, 1, 1);
+        planet.owner = PLAYER;
+        for ship_type in queue {
+            planet.queue_ship(*ship_type, PRICE);
+        }
+        planet
+    }
+
+    fn resources(amount: u32) -> GameResources {
+        GameResources { resources: HashMap::from_iter([(PLAYER, PlayerResources { influence: 0, resources: amount })]) }
+    }
+
+    #[test]
+    fn ordered_ships_are_the_ones_added_since_the_draft() {
+        let mut planet = planet(&[SpaceShipType::Carrier, SpaceShipType::Fighter]);
+        let draft = ProductionDraft::new(&planet, &resources(10));
+        planet.queue_ship(SpaceShipType::Carrier, PRICE);
+        planet.queue_ship(SpaceShipType::Frigate, PRICE);
+        planet.move_up_in_queue(2);
+        assert_eq!(draft.ordered(&planet), vec![SpaceShipType::Carrier, SpaceShipType::Frigate]);
+    }
+
+    #[test]
+    fn cancelled_orders_are_not_counted_as_ordered() {
+        let mut planet = planet(&[SpaceShipType::Carrier, SpaceShipType::Fighter]);
+        let draft = ProductionDraft::new(&planet, &resources(10));
+        planet.cancel_queued(0);
+        planet.queue_ship(SpaceShipType::Destroyer, PRICE);
+        assert_eq!(draft.ordered(&planet), vec![SpaceShipType::Destroyer]);
+        planet.cancel_queued(1);
+        assert!(draft.ordered(&planet).is_empty());
+    }
+
+    #[test]
+    fn cancelled_orders_are_refunded_in_full() {
+        let mut planet = planet(&[SpaceShipType::Carrier]);
+        let mut game_resources = resources(1);
+        let research = Research::new(vec![]);
+        assert!(change_production(&mut planet, &PLAYER, &mut game_resources, &research, ProductionChange::Cancel(0)));
+        assert_eq!(game_resources.resources[&PLAYER].resources, 1 + PRICE.resources);
+        assert!(planet.production.is_empty());
+        assert!(!change_production(&mut planet, &PLAYER, &mut game_resources, &research, ProductionChange::Cancel(0)));
+    }
+
+    #[test]
+    fn only_later_orders_move_up() {
+        let mut planet = planet(&[SpaceShipType::Carrier, SpaceShipType::Fighter]);
+        let mut game_resources = resources(0);
+        let research = Research::new(vec![]);
+        assert!(!change_production(&mut planet, &PLAYER, &mut game_resources, &research, ProductionChange::MoveUp(0)));
+        assert!(change_production(&mut planet, &PLAYER, &mut game_resources, &research, ProductionChange::MoveUp(1)));
+        let queue: Vec<SpaceShipType> = planet.production.iter().map(|queued| queued.ship_type).collect();
+        assert_eq!(queue, vec![SpaceShipType::Fighter, SpaceShipType::Carrier]);
+    }
+}
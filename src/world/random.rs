@@ -0,0 +1,13 @@
+use bevy::prelude::Resource;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Dice of the game. Kept as a resource so that a restored state rolls the same battles again.
+#[derive(Resource, Clone)]
+pub(crate) struct GameRng(pub StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng(StdRng::from_entropy())
+    }
+}
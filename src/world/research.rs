@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::space_ships::{SpaceShipCharacteristics, SpaceShipType};
+use crate::ui::stats::resources::Round;
+use crate::world::data::load_data;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Movable, Player};
+use crate::world::resources::{GameResources, PlayerResources};
+
+/// Technologies are described in a data file, so they can be balanced without rebuilding the game.
+const TECHS_FILE: &str = "data/techs.json";
+
+pub struct ResearchPlugin;
+
+impl Plugin for ResearchPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Research::load());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TechEffect {
+    /// Added to the power of every ship of the type in battles.
+    ShipPower { ship: SpaceShipType, bonus: u32 },
+    /// Subtracted from the resource price of the ship type.
+    ShipDiscount { ship: SpaceShipType, resources: u32 },
+    /// Added to how many hexes the ship type can fly in a single move.
+    ShipRange { ship: SpaceShipType, bonus: u32 },
+    /// Allows buying a ship type which is not available from the start.
+    UnlockShip { ship: SpaceShipType },
+    /// Forbids buying a ship type, even when it gets unlocked. Used by faction rosters.
+    LockShip { ship: SpaceShipType },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Tech {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Paid when the research starts.
+    pub cost: PlayerResources,
+    /// Turns of the player until the research is finished.
+    pub turns: u32,
+    /// Ids of the techs which have to be researched first.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    pub effects: Vec<TechEffect>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ResearchProgress {
+    pub tech: String,
+    pub turns_left: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PlayerResearch {
+    pub researched: Vec<String>,
+    pub current: Option<ResearchProgress>,
+}
+
+/// The tech tree and how far every player got in it.
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct Research {
+    pub techs: Vec<Tech>,
+    pub players: HashMap<Player, PlayerResearch>,
+    /// Effects players have without researching anything, granted by their faction.
+    pub innate: HashMap<Player, Vec<TechEffect>>,
+}
+
+impl Research {
+    pub(crate) fn new(techs: Vec<Tech>) -> Self {
+        Self { techs, players: HashMap::new(), innate: HashMap::new() }
+    }
+
+    /// The tech tree of the data file, nobody researched anything yet.
+    pub(crate) fn load() -> Self {
+        Self::new(load_data(TECHS_FILE))
+    }
+
+    pub(crate) fn tech(&self, id: &str) -> Option<&Tech> {
+        self.techs.iter().find(|tech| tech.id == id)
+    }
+
+    pub(crate) fn of(&self, player: &Player) -> PlayerResearch {
+        self.players.get(player).cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn has_researched(&self, player: &Player, id: &str) -> bool {
+        self.players.get(player).map_or(false, |research| research.researched.iter().any(|tech| tech == id))
+    }
+
+    pub(crate) fn is_available(&self, player: &Player, tech: &Tech) -> bool {
+        !self.has_researched(player, &tech.id) && tech.requires.iter().all(|id| self.has_researched(player, id))
+    }
+
+    pub(crate) fn can_start(&self, player: &Player, tech: &Tech, player_resources: &PlayerResources) -> bool {
+        self.of(player).current.is_none()
+            && self.is_available(player, tech)
+            && player_resources.resources >= tech.cost.resources
+            && player_resources.influence >= tech.cost.influence
+    }
+
+    /// The cost has to be paid by the caller.
+    pub(crate) fn start(&mut self, player: &Player, tech: &Tech) {
+        let research = self.players.entry(*player).or_insert_with(PlayerResearch::default);
+        research.current = Some(ResearchProgress { tech: tech.id.clone(), turns_left: tech.turns });
+    }
+
+    pub(crate) fn grant(&mut self, player: &Player, effects: Vec<TechEffect>) {
+        self.innate.entry(*player).or_insert_with(Vec::new).extend(effects);
+    }
+
+    fn effects<'a>(&'a self, player: &'a Player) -> impl Iterator<Item=&'a TechEffect> + 'a {
+        self.techs.iter()
+            .filter(move |tech| self.has_researched(player, &tech.id))
+            .flat_map(|tech| tech.effects.iter())
+            .chain(self.innate.get(player).into_iter().flatten())
+    }
+
+    pub(crate) fn is_ship_unlocked(&self, player: &Player, ship_type: SpaceShipType) -> bool {
+        if self.effects(player).any(|effect| *effect == TechEffect::LockShip { ship: ship_type }) { return false; }
+        match ship_type {
+            SpaceShipType::Carrier | SpaceShipType::Destroyer | SpaceShipType::Frigate => true,
+            SpaceShipType::Battleship | SpaceShipType::Fighter => self.effects(player)
+                .any(|effect| *effect == TechEffect::UnlockShip { ship: ship_type }),
+        }
+    }
+
+    /// Characteristics of the ship type with every tech of `player` applied.
+    pub(crate) fn ship_characteristics(&self, player: &Player, ship_type: SpaceShipType) -> SpaceShipCharacteristics {
+        let mut characteristics = SpaceShipCharacteristics::get_by_spaceship_type(ship_type);
+        for effect in self.effects(player) {
+            match effect {
+                TechEffect::ShipPower { ship, bonus } if *ship == ship_type => characteristics.power += bonus,
+                TechEffect::ShipDiscount { ship, resources } if *ship == ship_type => {
+                    characteristics.price.resources = characteristics.price.resources.saturating_sub(*resources).max(1);
+                }
+                TechEffect::ShipRange { ship, bonus } if *ship == ship_type => characteristics.range += bonus,
+                _ => {}
+            }
+        }
+        characteristics
+    }
+}
+
+/// Pays for `tech_id` and starts researching it, returns whether `player` could.
+pub(crate) fn start_research(research: &mut Research, resources: &mut GameResources, player: &Player, tech_id: &str, game_log: &mut GameLog, round: i32) -> bool {
+    let Some(tech) = research.tech(tech_id).cloned() else { return false; };
+    let Some(player_resources) = resources.resources.get_mut(player) else { return false; };
+    if !research.can_start(player, &tech, player_resources) { return false; }
+    *player_resources -= tech.cost;
+    research.start(player, &tech);
+    game_log.record(round, *player, GameEvent::ResearchStarted { tech: tech.name });
+    true
+}
+
+pub(crate) fn advance_research(
+    player_query: Query<&Player, With<Movable>>,
+    mut research: ResMut<Research>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+    research.advance(player, &mut game_log, round.number);
+}
+
+impl Research {
+    /// Research of the player who starts their turn gets one turn closer to being finished.
+    pub(crate) fn advance(&mut self, player: &Player, game_log: &mut GameLog, round: i32) {
+        let Some(player_research) = self.players.get_mut(player) else { return; };
+        let Some(progress) = player_research.current.as_mut() else { return; };
+        progress.turns_left = progress.turns_left.saturating_sub(1);
+        if progress.turns_left > 0 { return; }
+
+        let tech = progress.tech.clone();
+        player_research.current = None;
+        player_research.researched.push(tech.clone());
+        let name = self.tech(&tech).map_or(tech.clone(), |tech| tech.name.clone());
+        game_log.record(round, *player, GameEvent::ResearchFinished { tech: name });
+    }
+}
@@ -1,14 +1,22 @@
+use std::fmt;
 use std::fmt::Write;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 use hexx::Hex;
-
-use crate::world::player::Player;
-use crate::world::setup_world_grid::{HexGrid};
-
-#[derive(Debug, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+use crate::space_ships::{ShipIds, SpaceShip};
+use crate::world::buildings::{Building, BuildingType};
+use crate::world::factions::Factions;
+use crate::world::player::{Player, TurnOrder};
+use crate::world::research::Research;
+use crate::world::setup_world_grid::{HexGrid, Planet};
+use crate::world::supply::{Supply, SupplyStatus};
+use crate::world::terrain::GameMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub(crate) struct PlayerResources {
     pub influence: u32,
     pub resources: u32,
@@ -49,6 +57,17 @@ impl SubAssign for PlayerResources {
     }
 }
 
+impl PlayerResources {
+    pub(crate) fn halved(self) -> Self {
+        PlayerResources { influence: self.influence / 2, resources: self.resources / 2 }
+    }
+}
+
+impl fmt::Display for PlayerResources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} resources and {} influence", self.resources, self.influence)
+    }
+}
 
 impl Default for PlayerResources {
     fn default() -> Self {
@@ -65,22 +84,34 @@ pub(crate) struct GameResources {
 }
 
 impl GameResources {
-    pub(crate) fn to_string(&self) -> String {
+    pub(crate) fn to_string(&self, factions: &Factions) -> String {
         let mut resource_text = String::new();
-        for (player, player_resources) in &self.resources {
-            write!(&mut resource_text, "Player {:?} : {:?}\n", player.id, (player_resources.resources, player_resources.influence)).unwrap();
+        let mut players: Vec<&Player> = self.resources.keys().collect();
+        players.sort_by_key(|player| player.id);
+        for player in players {
+            let player_resources = &self.resources[player];
+            write!(&mut resource_text, "{} : {:?}\n", factions.name(player), (player_resources.resources, player_resources.influence)).unwrap();
         }
         return resource_text;
     }
 }
 
 impl GameResources {
-    pub fn update(&mut self, value: &HexGrid, player : &Player) {
+    /// `planet_bonus` is added for every planet of the player on top of its own income,
+    /// planets on `stormy_hexes` or cut off from home produce only half of it, blockaded ones nothing.
+    pub fn update(&mut self, value: &HexGrid, player : &Player, planet_bonus: PlayerResources, stormy_hexes: &[Hex], supply: &Supply) {
         let mut current_resources = self.resources.remove(player).unwrap();
-        for (_, planet) in &value.planets {
+        for (hex, planet) in &value.planets {
             if planet.owner.id == player.id {
-                current_resources.influence += planet.influence;
-                current_resources.resources += planet.resource;
+                let mut income = planet.income() + planet_bonus;
+                if stormy_hexes.contains(hex) {
+                    income = income.halved();
+                }
+                current_resources += match supply.status(hex) {
+                    SupplyStatus::Supplied => income,
+                    SupplyStatus::CutOff => income.halved(),
+                    SupplyStatus::Blockaded => PlayerResources::default(),
+                };
             }
          }
         self.resources.insert(player.clone(), current_resources);
@@ -91,26 +122,29 @@ impl GameResources {
 const INITIAL_RESOURCES: u32 = 15;
 const INITIAL_INFLUENCE: u32 = 5;
 
-pub fn setup_resources(mut commands: &mut Commands, grid: &mut HexGrid) {
-    let planets = &mut grid.planets;
-    let player1 = Player { id: 1 };
-    let player2 = Player { id: 2 };
-    let player1_home_hex = Hex { x: -2, y: 2 };
-    let player2_home_hex = Hex { x: 2, y: -2 };
-
-    let mut planet1 = planets.remove(&player1_home_hex).unwrap();
-    planet1.owner = player1;
-    planets.insert(player1_home_hex, planet1);
-
-
-    let mut planet2 = planets.remove(&player2_home_hex).unwrap();
-    planet2.owner = player2;
-    planets.insert(player2_home_hex, planet2);
-
-    let player1_res = PlayerResources { influence: INITIAL_INFLUENCE, resources: INITIAL_RESOURCES };
-    let player2_res = PlayerResources { influence: INITIAL_INFLUENCE, resources: INITIAL_RESOURCES };
+/// Gives every player their home planet from the map and what their faction starts with.
+pub(crate) fn setup_resources(planets: &mut HashMap<Hex, Planet>, turn_order: &TurnOrder, factions: &Factions, game_map: &GameMap, research: &mut Research, ship_ids: &mut ShipIds) -> GameResources {
     let mut resources = HashMap::new();
-    resources.insert(player1, player1_res);
-    resources.insert(player2, player2_res);
-    commands.insert_resource(GameResources { resources });
+    for player in turn_order.players.iter().copied() {
+        let faction = factions.of(&player);
+        let home_planet = game_map.home_of(&player).and_then(|home_hex| planets.remove(&home_hex));
+        if let Some(mut planet) = home_planet {
+            let home_hex = planet.hex;
+            planet.owner = player;
+            planet.home_of = Some(player);
+            planet.owner_army.clear();
+            planet.buildings.push(Building::finished(BuildingType::Shipyard));
+            for ship_type in faction.map_or(vec![], |faction| faction.starting_ships.clone()) {
+                planet.owner_army.push(SpaceShip::new(ship_ids.next(), ship_type, player, home_hex));
+            }
+            planets.insert(home_hex, planet);
+        } else {
+            warn!("{} has no home planet on the map", player);
+        }
+
+        let starting_bonus = faction.map_or(PlayerResources::default(), |faction| faction.starting_bonus);
+        resources.insert(player, PlayerResources { influence: INITIAL_INFLUENCE, resources: INITIAL_RESOURCES } + starting_bonus);
+        research.grant(&player, faction.map_or(vec![], |faction| faction.effects.clone()));
+    }
+    GameResources { resources }
 }
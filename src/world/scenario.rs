@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::UpdateUI;
+use crate::network::client::is_local_game;
+use crate::space_ships::{ShipIds, SpaceShip, SpaceShipType};
+use crate::ui::galactic_event::plugin::GalacticEventState;
+use crate::ui::stats::resources::Round;
+use crate::world::ai::AiPlayers;
+use crate::world::buildings::{Building, BuildingType};
+use crate::world::data::load_data;
+use crate::world::elimination::surrender;
+use crate::world::factions::Factions;
+use crate::world::fleets::Fleet;
+use crate::world::fog_of_war::Viewer;
+use crate::world::galactic_events::GalacticEvents;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Player, Stats, TurnOrder};
+use crate::world::resources::{GameResources, PlayerResources};
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::terrain::GameMap;
+use crate::world::victory::{GameOutcome, VictoryConditions};
+
+/// Scenarios are described in data files, `--scenario <id>` plays `assets/data/scenarios/<id>.json`.
+const SCENARIOS_DIR: &str = "data/scenarios";
+const DEFAULT_MAP: &str = "default";
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // The grid, the players and their resources are all set up by then
+            .add_systems(PostStartup, setup_scenario.run_if(resource_exists::<Scenario>()))
+            // Clients get the outcome of triggers with the state from the server
+            .add_systems(Update, (fire_scenario_triggers, check_scenario_defeats)
+                .chain()
+                .run_if(resource_exists::<Scenario>())
+                .run_if(is_local_game)
+                .in_set(UpdateUI::RenderStats));
+    }
+}
+
+/// A planet a side starts with, replacing its garrison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ScenarioPlanet {
+    pub hex: Hex,
+    #[serde(default)]
+    pub ships: Vec<SpaceShipType>,
+    #[serde(default)]
+    pub shipyard: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ScenarioSide {
+    pub player: Player,
+    /// Played by the computer, see `AiPlayers`.
+    #[serde(default)]
+    pub ai: bool,
+    #[serde(default)]
+    pub faction: Option<String>,
+    /// Replaces the resources the side would start with.
+    #[serde(default)]
+    pub resources: Option<PlayerResources>,
+    /// Replaces the home planet the map gives the side.
+    #[serde(default)]
+    pub home: Option<Hex>,
+    /// Given on top of the home planet.
+    #[serde(default)]
+    pub planets: Vec<ScenarioPlanet>,
+}
+
+/// Knocks `player` out of the game, as if they surrendered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum DefeatCondition {
+    /// The planet at `hex` is lost.
+    PlanetLost { player: Player, hex: Hex },
+    /// The game is still going when `round` starts.
+    RoundReached { player: Player, round: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TriggerEffect {
+    /// Ships landing on the planet at `hex`, lost if `player` doesn't hold it anymore.
+    Reinforcements { player: Player, hex: Hex, ships: Vec<SpaceShipType> },
+    Resources { player: Player, resources: PlayerResources },
+    /// Only the announcement.
+    Message,
+}
+
+/// Fires when `round` starts, the announcement is shown like a galactic event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ScenarioTrigger {
+    pub round: i32,
+    pub title: String,
+    pub text: String,
+    pub effect: TriggerEffect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub(crate) struct Scenario {
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Map file the scenario is played on, see `GameMap`.
+    #[serde(default = "default_map")]
+    pub map: String,
+    /// In turn order.
+    pub sides: Vec<ScenarioSide>,
+    /// Written like in `--victory`, the usual conditions apply when empty.
+    #[serde(default)]
+    pub victory: Vec<String>,
+    #[serde(default)]
+    pub defeat: Vec<DefeatCondition>,
+    #[serde(default)]
+    pub triggers: Vec<ScenarioTrigger>,
+    /// Whether galactic events are drawn, off for scenarios whose story shouldn't be disturbed.
+    #[serde(default = "default_events")]
+    pub events: bool,
+}
+
+fn default_map() -> String {
+    DEFAULT_MAP.to_string()
+}
+
+fn default_events() -> bool {
+    true
+}
+
+impl Scenario {
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Option<Self> {
+        let args: Vec<String> = args.collect();
+        let id = args.iter()
+            .position(|arg| arg == "--scenario")
+            .and_then(|index| args.get(index + 1))?;
+        Some(Self::named(id))
+    }
+
+    pub(crate) fn named(id: &str) -> Self {
+        let mut scenario: Scenario = load_data(&format!("{}/{}.json", SCENARIOS_DIR, id));
+        assert!(scenario.sides.len() >= 2, "Scenario {} needs at least two sides", id);
+        scenario.id = id.to_string();
+        scenario
+    }
+
+    pub(crate) fn turn_order(&self) -> TurnOrder {
+        TurnOrder { players: self.sides.iter().map(|side| side.player).collect() }
+    }
+
+    pub(crate) fn factions(&self) -> Factions {
+        let choices: Vec<String> = self.sides.iter().map(|side| side.faction.clone().unwrap_or_default()).collect();
+        Factions::from_choices(&choices, &self.turn_order().players)
+    }
+
+    pub(crate) fn ai_players(&self) -> AiPlayers {
+        AiPlayers { players: self.sides.iter().filter(|side| side.ai).map(|side| side.player).collect() }
+    }
+
+    /// Sides played by people, they are the ones a campaign is won for.
+    pub(crate) fn human_players(&self) -> Vec<Player> {
+        self.sides.iter().filter(|side| !side.ai).map(|side| side.player).collect()
+    }
+
+    pub(crate) fn apply_homes(&self, game_map: &mut GameMap) {
+        for side in self.sides.iter() {
+            if let Some(home) = side.home {
+                game_map.set_home(side.player, home);
+            }
+        }
+    }
+
+    pub(crate) fn apply_victory_conditions(&self, victory_conditions: &mut VictoryConditions) {
+        if !self.victory.is_empty() {
+            victory_conditions.set_conditions(self.victory.iter().map(String::as_str));
+        }
+    }
+}
+
+fn setup_scenario(
+    mut commands: Commands,
+    scenario: Res<Scenario>,
+    mut grid: ResMut<HexGrid>,
+    mut resources: ResMut<GameResources>,
+    mut ship_ids: ResMut<ShipIds>,
+) {
+    info!("Starting scenario {}: {}", scenario.id, scenario.name);
+    // A lone player against the computer keeps their own view during its turns,
+    // a game of computers only is watched from above
+    match scenario.human_players()[..] {
+        [human] => commands.insert_resource(Viewer::Seat(human)),
+        [] => commands.insert_resource(Viewer::FullMap),
+        _ => {}
+    }
+    for side in scenario.sides.iter() {
+        if let Some(side_resources) = side.resources {
+            resources.resources.insert(side.player, side_resources);
+        }
+        for placement in side.planets.iter() {
+            let Some(planet) = grid.planets.get_mut(&placement.hex) else {
+                warn!("Scenario {} places a planet outside of the map at {:?}", scenario.id, placement.hex);
+                continue;
+            };
+            planet.owner = side.player;
+            planet.owner_army = placement.ships.iter()
+                .map(|ship_type| SpaceShip::new(ship_ids.next(), *ship_type, side.player, placement.hex))
+                .collect();
+            if placement.shipyard && !planet.has_shipyard() {
+                planet.buildings.push(Building::finished(BuildingType::Shipyard));
+            }
+        }
+    }
+}
+
+/// Triggers of every round the game enters, rounds taken back by undo fire again once replayed.
+fn fire_scenario_triggers(
+    scenario: Res<Scenario>,
+    round: Res<Round>,
+    mut last_round: Local<i32>,
+    mut grid: ResMut<HexGrid>,
+    mut resources: ResMut<GameResources>,
+    mut ship_ids: ResMut<ShipIds>,
+    turn_order: Res<TurnOrder>,
+    factions: Res<Factions>,
+    mut game_log: ResMut<GameLog>,
+    mut galactic_events: ResMut<GalacticEvents>,
+    mut event_state: ResMut<NextState<GalacticEventState>>,
+) {
+    if round.number <= *last_round {
+        *last_round = round.number;
+        return;
+    }
+    *last_round = round.number;
+
+    for trigger in scenario.triggers.iter().filter(|trigger| trigger.round == round.number) {
+        let mut text = trigger.text.clone();
+        let player = match &trigger.effect {
+            TriggerEffect::Reinforcements { player, hex, ships } => {
+                match grid.planets.get_mut(hex).filter(|planet| planet.owner == *player) {
+                    Some(planet) => {
+                        planet.owner_army.extend(ships.iter().map(|ship_type| SpaceShip::new(ship_ids.next(), *ship_type, *player, *hex)));
+                    }
+                    None => text = format!("{}\n{} lost the planet, the ships turned back", text, factions.name(player)),
+                }
+                *player
+            }
+            TriggerEffect::Resources { player, resources: bonus } => {
+                *resources.resources.entry(*player).or_default() += *bonus;
+                *player
+            }
+            TriggerEffect::Message => turn_order.first(),
+        };
+        game_log.record(round.number, player, GameEvent::GalacticEvent { name: trigger.title.clone(), outcome: text.clone() });
+        galactic_events.announce(trigger.title.clone(), text);
+        event_state.set(GalacticEventState::Shown);
+    }
+}
+
+fn check_scenario_defeats(
+    mut commands: Commands,
+    scenario: Res<Scenario>,
+    round: Res<Round>,
+    outcome: Option<Res<GameOutcome>>,
+    stats_query: Query<(&Player, &Stats)>,
+    fleets_query: Query<(Entity, &Fleet)>,
+    mut grid: ResMut<HexGrid>,
+    factions: Res<Factions>,
+    mut game_log: ResMut<GameLog>,
+    mut galactic_events: ResMut<GalacticEvents>,
+    mut event_state: ResMut<NextState<GalacticEventState>>,
+) {
+    if outcome.is_some() { return; }
+    for condition in scenario.defeat.iter() {
+        let (player, is_defeated) = match condition {
+            DefeatCondition::PlanetLost { player, hex } => (player, grid.planets.get(hex).map_or(false, |planet| planet.owner != *player)),
+            DefeatCondition::RoundReached { player, round: last_round } => (player, round.number >= *last_round),
+        };
+        let is_in_game = stats_query.iter().any(|(other, stats)| other == player && !stats.eliminated);
+        if !is_defeated || !is_in_game { continue; }
+        // Whatever is left goes to the neutrals, the player is then eliminated like anybody else
+        if grid.planets.values().any(|planet| planet.owner == *player) || fleets_query.iter().any(|(_, fleet)| fleet.owner == *player) {
+            surrender(player, &mut grid, fleets_query.iter(), &mut commands, &mut game_log, round.number);
+            galactic_events.announce(format!("{} defeated", factions.name(player)), "The scenario is lost for them".to_string());
+            event_state.set(GalacticEventState::Shown);
+        }
+    }
+}
@@ -10,14 +10,24 @@ use glam::{vec2, Vec2};
 use hexx::{Hex, HexLayout, HexOrientation, shapes};
 use rand::Rng;
 
-use crate::space_ships::{get_spaceship_atlas, get_spaceship_sprite_bundle_by_type, SpaceShip, SpaceSipTextureAtlas};
-use crate::space_ships::SpaceShipType::{Carrier, Destroyer, Frigate};
+use crate::space_ships::{ALL_SPACE_SHIPS, CHEVRON_ICON, get_spaceship_atlas, get_spaceship_sprite_bundle_by_type, MAX_RANK, ShipIds, SpaceShip, SpaceShipType, SpaceSipTextureAtlas};
 use crate::world::actions::ActionsState;
+use crate::world::buildings::Building;
 use crate::world::button_click::IsButtonClicked;
 use crate::world::create_map_layout;
-use crate::world::ownership::{OwnershipInfo, SpaceShipsInfo};
-use crate::world::player::{Movable, Player};
+use crate::world::diplomacy::Diplomacy;
+use crate::world::factions::Factions;
+use crate::world::objectives::Objectives;
+use crate::world::ownership::{CHEVRON_COLOR, OwnershipInfo, RankChevron, SpaceShipsInfo};
+use crate::world::pathfinding::{find_path, get_army_range, path_cost};
+use crate::world::defense::FortificationInfo;
+use crate::world::production::{ProductionInfo, QueuedShip};
+use crate::world::terrain::{GameMap, Terrain};
+use crate::world::player::{Movable, Player, TurnOrder};
+use crate::world::research::Research;
 use crate::world::resources::setup_resources;
+use crate::world::supply::SupplyInfo;
+use crate::world::victory::VictoryConditions;
 
 const HEX_SIZE: Vec2 = Vec2::splat(75.0);
 const FILE_GRID_HEIGHT_IN_FILE: usize = 1;
@@ -29,6 +39,8 @@ const GRID_WEIGHT_IN_FILE: usize = 6;
 
 const RESOURCE_COLOR: bevy::prelude::Color = Color::ORANGE;
 const INFLUENCE_COLOR: bevy::prelude::Color = Color::CYAN;
+const MOVE_PATH_COLOR: bevy::prelude::Color = Color::YELLOW;
+const OBJECTIVE_COLOR: bevy::prelude::Color = Color::GOLD;
 
 /// 3D Orthogrpahic camera setup
 pub(crate) fn setup_camera(mut commands: Commands) {
@@ -39,10 +51,20 @@ pub(crate) fn setup_camera(mut commands: Commands) {
 #[derive(Debug, Clone)]
 pub struct Planet {
     pub hex: Hex,
+    /// Index of the map atlas tile the planet is drawn with.
+    pub tile: usize,
+    pub terrain: Terrain,
     pub resource: u32,
     pub influence: u32,
     pub owner: Player,
     pub owner_army: Vec<SpaceShip>,
+    pub buildings: Vec<Building>,
+    /// Ships ordered at the shipyard of the planet, delivered first to last.
+    pub production: Vec<QueuedShip>,
+    /// Set when the owner spent a turn fortifying, see `BattleModifiers`.
+    pub fortified: bool,
+    /// Player who started the game on the planet.
+    pub home_of: Option<Player>,
     pub planet_locked_entity: Option<Entity>,
 }
 
@@ -58,33 +80,61 @@ pub(crate) fn get_planet_resource_and_influence(id: usize) -> (u32, u32) {
     };
 }
 
+/// Planet value needed for every garrison ship of each type.
+const GARRISON_CARRIER_VALUE: u32 = 4;
+const GARRISON_DESTROYER_VALUE: u32 = 8;
+
+/// Pirates guarding an unclaimed planet, the richer the planet the stronger they are.
+pub(crate) fn create_garrison(hex: Hex, resource: u32, influence: u32, ship_ids: &mut ShipIds) -> Vec<SpaceShip> {
+    let value = resource + influence;
+    let carriers = (0..value / GARRISON_CARRIER_VALUE).map(|_| SpaceShipType::Carrier);
+    let destroyers = (0..value / GARRISON_DESTROYER_VALUE).map(|_| SpaceShipType::Destroyer);
+    carriers.chain(destroyers)
+        .map(|ship_type| SpaceShip::new(ship_ids.next(), ship_type, Player { id: -1 }, hex))
+        .collect()
+}
+
 impl Planet {
     pub(crate) fn new(
         hex: Hex,
+        tile: usize,
         resource: u32,
         influence: u32,
         owner: Player,
         owner_army: Vec<SpaceShip>) -> Self {
         Self {
             hex,
+            tile,
+            terrain: Terrain::Open,
             resource,
             influence,
             owner,
             owner_army,
+            buildings: vec![],
+            production: vec![],
+            fortified: false,
+            home_of: None,
             planet_locked_entity: None,
         }
     }
 
     pub(crate) fn default(
         hex: Hex,
+        tile: usize,
         resource: u32,
         influence: u32) -> Self {
         Self {
             hex,
+            tile,
+            terrain: Terrain::Open,
             resource,
             influence,
             owner: Player { id: -1 },
             owner_army: vec![],
+            buildings: vec![],
+            production: vec![],
+            fortified: false,
+            home_of: None,
             planet_locked_entity: None,
         }
     }
@@ -108,6 +158,11 @@ pub(crate) fn setup_grid(
     mut commands: Commands,
     mut atlases: ResMut<Assets<TextureAtlas>>,
     asset_server: Res<AssetServer>,
+    victory_conditions: Res<VictoryConditions>,
+    turn_order: Res<TurnOrder>,
+    factions: Res<Factions>,
+    game_map: Res<GameMap>,
+    mut research: ResMut<Research>,
 ) {
     let texture = asset_server.load("images/Simple grid.png");
     let atlas = TextureAtlas::from_grid(
@@ -126,9 +181,9 @@ pub(crate) fn setup_grid(
         ..default()
     };
     let sprite_size = layout.rect_size();
-    let mut planets: HashMap<Hex, Planet> = HashMap::new();
-    let radius = 3;
-    let map = create_map_layout::create_setup_field_map_for_radius(radius);
+    let mut ship_ids = ShipIds::default();
+    let mut planets = create_planets(&game_map, &mut ship_ids);
+    let objectives = create_objectives(&game_map, &victory_conditions);
 
     let spaceship_grid_atlas = get_spaceship_atlas(&asset_server);
     let spaceship_grid_texture: Handle<TextureAtlas> = atlases.add(spaceship_grid_atlas);
@@ -136,19 +191,16 @@ pub(crate) fn setup_grid(
         spaceship_grid_atlas: spaceship_grid_texture.clone()
     });
 
-    let entities = shapes::hexagon(Hex::ZERO, radius)
-        .enumerate()
-        .map(|(i, coord)| {
+    let entities = shapes::hexagon(Hex::ZERO, game_map.radius)
+        .map(|coord| {
             let pos = layout.hex_to_world_pos(coord);
-            let index = map[&i]; // i % (FILE_GRID_HEIGHT_IN_FILE * GRID_WEIGHT_IN_FILE);
-            let (planet_resource_value, planet_influence_value) = get_planet_resource_and_influence(index);
-            let planet = Planet::default(coord, planet_resource_value, planet_influence_value);
+            let planet = &planets[&coord];
 
             let entity = commands
                 .spawn(SpriteSheetBundle {
                     sprite: TextureAtlasSprite {
-                        color: Color::WHITE,
-                        index,
+                        color: planet.terrain.tint(),
+                        index: planet.tile,
                         custom_size: Some(sprite_size),
                         ..default()
                     },
@@ -173,30 +225,64 @@ pub(crate) fn setup_grid(
                         parent.spawn(create_influence_sprite_bundle(&asset_server));
                         parent.spawn(create_influence_text_bundle(font.clone(), planet.influence));
                     }
+                    if let Some(icon) = planet.terrain.icon() {
+                        parent.spawn(create_terrain_sprite_bundle(&asset_server, icon));
+                    }
+                    if let Some(objective) = objectives.get(&coord) {
+                        parent.spawn(create_objective_sprite_bundle(&asset_server));
+                        parent.spawn(create_objective_text_bundle(font.clone(), objective.points));
+                    }
+                    parent.spawn((create_supply_sprite_bundle(&asset_server), SupplyInfo { hex: coord.clone() }));
+                    parent.spawn((create_production_text_bundle(font.clone()), ProductionInfo { hex: coord.clone() }));
+                    parent.spawn((create_fortification_sprite_bundle(&asset_server), FortificationInfo { hex: coord.clone() }));
                     parent.spawn((get_ownership_frame(&asset_server, sprite_size), OwnershipInfo { hex: coord.clone() }));
-                    spawn_space_ship_info_grid(parent, &spaceship_grid_texture, coord.clone(), font.clone())
+                    spawn_space_ship_info_grid(parent, &spaceship_grid_texture, &asset_server, coord.clone(), font.clone())
                 })
                 .id();
-            planets.insert(coord, planet);
             (coord, entity)
         })
         .collect();
 
-    let mut grid = HexGrid { entities, layout, planets };
-    setup_resources(&mut commands, &mut grid);
-    commands.insert_resource(grid);
+    let game_resources = setup_resources(&mut planets, &turn_order, &factions, &game_map, &mut research, &mut ship_ids);
+    commands.insert_resource(HexGrid { entities, layout, planets });
+    commands.insert_resource(game_resources);
+    commands.insert_resource(ship_ids);
+    commands.insert_resource(objectives);
+}
+
+/// Planets of the whole board with their terrain and pirate garrisons, nobody owns anything yet.
+pub(crate) fn create_planets(game_map: &GameMap, ship_ids: &mut ShipIds) -> HashMap<Hex, Planet> {
+    let map = create_map_layout::create_setup_field_map_for_radius(game_map.radius);
+    let terrain = game_map.placements(&game_map.home_hexes());
+    shapes::hexagon(Hex::ZERO, game_map.radius)
+        .enumerate()
+        .map(|(i, coord)| {
+            let hex_terrain = terrain.get(&coord).copied();
+            let index = hex_terrain.and_then(|terrain| terrain.tile()).unwrap_or(map[&i]); // i % (FILE_GRID_HEIGHT_IN_FILE * GRID_WEIGHT_IN_FILE);
+            let (planet_resource_value, planet_influence_value) = get_planet_resource_and_influence(index);
+            let mut planet = Planet::default(coord, index, planet_resource_value, planet_influence_value);
+            if let Some(hex_terrain) = hex_terrain {
+                planet.terrain = hex_terrain;
+            }
+            planet.owner_army = create_garrison(coord, planet_resource_value, planet_influence_value, ship_ids);
+            (coord, planet)
+        })
+        .collect()
 }
 
-fn spawn_space_ship_info_grid(parent: &mut ChildBuilder, spaceship_grid_texture: &Handle<TextureAtlas>, hex: Hex, font: Handle<Font>) {
-    let all_space_ships = vec![Carrier, Destroyer, Frigate /*Battleship, Fighter*/];
+/// Objectives given on the command line or by the scenario, the ones of the map otherwise.
+pub(crate) fn create_objectives(game_map: &GameMap, victory_conditions: &VictoryConditions) -> Objectives {
+    Objectives::new(victory_conditions.objectives.clone().unwrap_or_else(|| game_map.objective_points()))
+}
 
+fn spawn_space_ship_info_grid(parent: &mut ChildBuilder, spaceship_grid_texture: &Handle<TextureAtlas>, asset_server: &Res<AssetServer>, hex: Hex, font: Handle<Font>) {
     let spaceships_info_text_style: TextStyle = TextStyle {
         font,
         font_size: 42.0,
         color: Color::WHITE,
     };
 
-    for (id, space_ship_type) in all_space_ships.into_iter().enumerate() {
+    for (id, space_ship_type) in ALL_SPACE_SHIPS.into_iter().enumerate() {
         let transform = Transform {
             translation: Vec3 { x: 60.0, y: (30.0 - 15.0 * id as f32), z: 1.0 },
             scale: Vec3::splat(0.4),
@@ -221,9 +307,24 @@ fn spawn_space_ship_info_grid(parent: &mut ChildBuilder, spaceship_grid_texture:
 
         parent.spawn(image_sprite);
         parent.spawn(text_sprite);
+        for rank in 1..=MAX_RANK {
+            parent.spawn((create_chevron_sprite_bundle(asset_server, id, rank), RankChevron { hex, space_ship_type, rank }));
+        }
     }
 }
 
+/// Chevrons are stacked down from the top of the ship sprite of the row, hidden until a ship reaches their rank.
+fn create_chevron_sprite_bundle(asset_server: &Res<AssetServer>, row: usize, rank: u32) -> SpriteBundle {
+    let transform = Transform {
+        translation: Vec3::new(53., (30.0 - 15.0 * row as f32) + 7.0 - 3.0 * (rank - 1) as f32, 1.1),
+        scale: Vec3::splat(0.05),
+        ..Default::default()
+    };
+    let mut bundle = create_sprite_bundle_with_image(asset_server, CHEVRON_ICON.to_string(), transform, CHEVRON_COLOR);
+    bundle.visibility = Visibility::Hidden;
+    bundle
+}
+
 fn get_ownership_frame(asset_server: &Res<AssetServer>, sprite_size: Vec2) -> SpriteBundle {
     let image_path = "kenney - Simpe Icons/my_hex.png".to_string();
     let transform = Transform {
@@ -266,6 +367,51 @@ fn create_influence_sprite_bundle(asset_server: &Res<AssetServer>) -> SpriteBund
     create_sprite_bundle_with_image(asset_server, image_path, transform, INFLUENCE_COLOR)
 }
 
+fn create_objective_sprite_bundle(asset_server: &Res<AssetServer>) -> SpriteBundle {
+    let image_path = "kenney - Simpe Icons/award.png".to_string();
+    let transform = Transform {
+        translation: Vec3::new(-59., 10., 1.0),
+        scale: Vec3::splat(0.1),
+        ..Default::default()
+    };
+    create_sprite_bundle_with_image(asset_server, image_path, transform, OBJECTIVE_COLOR)
+}
+
+/// Marks the terrain of the hex, drawn under the planet's numbers.
+fn create_terrain_sprite_bundle(asset_server: &Res<AssetServer>, image_path: &str) -> SpriteBundle {
+    let transform = Transform {
+        translation: Vec3::new(0., 40., 0.5),
+        scale: Vec3::splat(0.8),
+        ..Default::default()
+    };
+    create_sprite_bundle_with_image(asset_server, image_path.to_string(), transform, Color::WHITE)
+}
+
+/// Shown by the supply overlay on planets cut off from home or blockaded.
+fn create_supply_sprite_bundle(asset_server: &Res<AssetServer>) -> SpriteBundle {
+    let image_path = "kenney - Simpe Icons/arrow_cross_divided.png".to_string();
+    let transform = Transform {
+        translation: Vec3::new(-59., -5., 1.0),
+        scale: Vec3::splat(0.1),
+        ..Default::default()
+    };
+    let mut bundle = create_sprite_bundle_with_image(asset_server, image_path, transform, Color::WHITE);
+    bundle.visibility = Visibility::Hidden;
+    bundle
+}
+
+fn create_fortification_sprite_bundle(asset_server: &Res<AssetServer>) -> SpriteBundle {
+    let image_path = "kenney - Simpe Icons/shield.png".to_string();
+    let transform = Transform {
+        translation: Vec3::new(59., -5., 1.0),
+        scale: Vec3::splat(0.1),
+        ..Default::default()
+    };
+    let mut bundle = create_sprite_bundle_with_image(asset_server, image_path, transform, Color::WHITE);
+    bundle.visibility = Visibility::Hidden;
+    bundle
+}
+
 pub(crate) fn create_sprite_bundle_with_image(asset_server: &Res<AssetServer>, image_path: String, transform: Transform, color: Color) -> SpriteBundle {
     (SpriteBundle {
         texture: asset_server.load(image_path).clone().into(),
@@ -343,6 +489,41 @@ fn create_influence_text_bundle(font: Handle<Font>, value: u32) -> Text2dBundle
     )
 }
 
+fn create_objective_text_bundle(font: Handle<Font>, points: i32) -> Text2dBundle {
+    let objective_text_style: TextStyle = TextStyle {
+        font,
+        font_size: 42.0,
+        color: OBJECTIVE_COLOR,
+    };
+
+    let objective_transform = Transform {
+        translation: Vec3::new(-45., 11., 0.5),
+        scale: Vec3::splat(0.35),
+        ..Default::default()
+    };
+
+    create_text_bundle(
+        format!("+{}", points),
+        objective_text_style,
+        objective_transform,
+    )
+}
+
+/// Progress of the ship the planet is building, see `update_production_labels`.
+fn create_production_text_bundle(font: Handle<Font>) -> Text2dBundle {
+    let production_text_style: TextStyle = TextStyle {
+        font,
+        font_size: 42.0,
+        color: Color::WHITE,
+    };
+    let transform = Transform {
+        translation: Vec3::new(0., -55., 1.0),
+        scale: Vec3::splat(0.3),
+        ..Default::default()
+    };
+    create_text_bundle(String::new(), production_text_style, transform)
+}
+
 fn create_ownership_text_bundle(font: Handle<Font>) -> Text2dBundle {
     let resource_text_style: TextStyle = TextStyle {
         font,
@@ -383,6 +564,8 @@ pub(crate) struct SelectedHex {
     pub is_selected_for_move: bool,
     pub in_entity: Option<Entity>,
     pub out_entity: Option<Entity>,
+    /// Route from `hex_selected_for_move` to `hex`, both ends included.
+    pub move_path: Vec<Hex>,
 }
 
 pub fn clear_selected(
@@ -397,6 +580,7 @@ pub fn clear_selected(
     // set_color_to_hex(&grid, &mut tiles, &selecred_hex.hex_selected_for_move, &DEFAULT_COLOR);
     selecred_hex.hex = HEX_NOWHERE;
     selecred_hex.hex_selected_for_move = HEX_NOWHERE;
+    selecred_hex.move_path.clear();
     despawn_in_entity(&mut selecred_hex, &mut commands);
     despawn_out_entity(&mut selecred_hex, &mut commands);
 }
@@ -459,7 +643,7 @@ fn spawn_bundle_on_hex(
 
 
 pub(crate) fn register_selected_hex(mut commands: Commands) {
-    let hex = SelectedHex { hex: HEX_NOWHERE, is_selected: false, hex_selected_for_move: HEX_NOWHERE, is_selected_for_move: false, in_entity: None, out_entity: None };
+    let hex = SelectedHex { hex: HEX_NOWHERE, is_selected: false, hex_selected_for_move: HEX_NOWHERE, is_selected_for_move: false, in_entity: None, out_entity: None, move_path: vec![] };
     commands.insert_resource(hex);
 }
 
@@ -475,6 +659,7 @@ pub(crate) fn clear_move_selected(
     selecred_hex.is_selected_for_move = false;
     // set_color_to_hex(&grid, &mut tiles, &selecred_hex.hex_selected_for_move, &DEFAULT_COLOR);
     selecred_hex.hex_selected_for_move = HEX_NOWHERE;
+    selecred_hex.move_path.clear();
     despawn_out_entity(&mut selecred_hex, &mut commands);
 }
 
@@ -503,6 +688,8 @@ pub(crate) fn handle_click_on_planet(
     is_button_clicked: Res<IsButtonClicked>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    research: Res<Research>,
+    diplomacy: Res<Diplomacy>,
 ) {
     if is_button_clicked.value { return; }
     let window = windows.single();
@@ -541,6 +728,7 @@ pub(crate) fn handle_click_on_planet(
                 }
             }
             ActionsState::SpawningSpaceShips => {}
+            ActionsState::ConstructingBuildings => {}
             ActionsState::MovingSpaceShips => {
                 if !selected_hex.is_selected || selected_hex.hex == cur_pos { return; }
 
@@ -550,12 +738,16 @@ pub(crate) fn handle_click_on_planet(
                     return;
                 }
 
-                if current_player_query.get_single().unwrap().clone() != grid.planets.get(&cur_pos).unwrap().owner {
+                let player = current_player_query.get_single().unwrap().clone();
+                let source_planet = grid.planets.get(&cur_pos).unwrap();
+                if player != source_planet.owner {
                     return;
                 };
 
-
-                if !are_hex_neighbours(&selected_hex.hex, &cur_pos) {
+                let Some(path) = find_path(&player, cur_pos, selected_hex.hex, &grid.planets, &diplomacy) else {
+                    return;
+                };
+                if path_cost(&path, &grid.planets) > get_army_range(&source_planet.owner_army, &research) {
                     return;
                 }
 
@@ -564,10 +756,12 @@ pub(crate) fn handle_click_on_planet(
                         // set_color_to_hex(&grid, &mut tiles, &cur_pos, &DEFAULT_COLOR);
                         selected_hex.is_selected_for_move = false;
                         selected_hex.hex_selected_for_move = HEX_NOWHERE;
+                        selected_hex.move_path.clear();
                         despawn_out_entity(&mut selected_hex, &mut commands);
                     } else {
                         // set_color_to_hex(&grid, &mut tiles, &cur_pos, &SELECTED_FOR_MOVE_COLOR);
                         selected_hex.is_selected_for_move = true;
+                        selected_hex.move_path = path;
                         println!("456");
                         selected_hex.out_entity = spawn_bundle_on_hex(&grid, &cur_pos, &mut commands, create_move_out_sprite_bundle(&asset_server))
                     }
@@ -575,6 +769,7 @@ pub(crate) fn handle_click_on_planet(
                     let prv_pos_copy = selected_hex.hex_selected_for_move.clone();
                     selected_hex.hex_selected_for_move = cur_pos;
                     selected_hex.is_selected_for_move = true;
+                    selected_hex.move_path = path;
                     // set_color_to_hex(&grid, &mut tiles, &prv_pos_copy, &DEFAULT_COLOR);
                     despawn_out_entity(&mut selected_hex, &mut commands);
                     // set_color_to_hex(&grid, &mut tiles, &cur_pos, &SELECTED_FOR_MOVE_COLOR);
@@ -587,9 +782,18 @@ pub(crate) fn handle_click_on_planet(
     }
 }
 
-fn are_hex_neighbours(first: &Hex, second: &Hex) -> bool {
-    let delta = *first - *second;
-    return Hex::NEIGHBORS_COORDS.contains(&delta);
+/// Draws the route of the ships selected for move on top of the grid
+pub(crate) fn draw_move_path_preview(
+    selected_hex: Res<SelectedHex>,
+    grid: Res<HexGrid>,
+    mut gizmos: Gizmos,
+) {
+    if !selected_hex.is_selected_for_move || selected_hex.move_path.is_empty() { return; }
+    let points: Vec<Vec2> = selected_hex.move_path.iter().map(|hex| grid.layout.hex_to_world_pos(*hex)).collect();
+    gizmos.linestrip_2d(points.clone(), MOVE_PATH_COLOR);
+    for point in points.iter().skip(1).take(points.len().saturating_sub(2)) {
+        gizmos.circle_2d(*point, 6.0, MOVE_PATH_COLOR);
+    }
 }
 
 
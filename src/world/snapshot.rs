@@ -0,0 +1,180 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::AppState;
+use crate::space_ships::{ShipIds, SpaceShip, SpaceSipTextureAtlas};
+use crate::ui::stats::resources::Round;
+use crate::world::buildings::Building;
+use crate::world::diplomacy::Diplomacy;
+use crate::world::fleets::{Fleet, spawn_fleet};
+use crate::world::galactic_events::GalacticEvents;
+use crate::world::game_log::{GameLog, LogEntry};
+use crate::world::objectives::Objectives;
+use crate::world::player::{Movable, Player, Stats};
+use crate::world::production::QueuedShip;
+use crate::world::research::{PlayerResearch, Research};
+use crate::world::resources::{GameResources, PlayerResources};
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::victory::GameOutcome;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PlanetSnapshot {
+    pub hex: Hex,
+    pub owner: Player,
+    pub army: Vec<SpaceShip>,
+    pub buildings: Vec<Building>,
+    pub production: Vec<QueuedShip>,
+    pub fortified: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PlayerSnapshot {
+    pub player: Player,
+    pub moves_left: i32,
+    pub win_points: i32,
+    pub eliminated: bool,
+    pub resources: PlayerResources,
+    /// Win points scored so far on every objective hex.
+    pub objective_points: Vec<(Hex, i32)>,
+    pub research: PlayerResearch,
+}
+
+/// Everything that changes during a game, detached from entities and rendering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GameSnapshot {
+    pub round: i32,
+    pub current_player: Option<Player>,
+    pub players: Vec<PlayerSnapshot>,
+    pub planets: Vec<PlanetSnapshot>,
+    pub fleets: Vec<Fleet>,
+    pub log: Vec<LogEntry>,
+    /// Last round of every solar storm still raging.
+    pub storms: Vec<(Hex, i32)>,
+    pub diplomacy: Diplomacy,
+    pub outcome: Option<GameOutcome>,
+}
+
+/// Access to the whole game state, used to take and restore snapshots.
+#[derive(SystemParam)]
+pub(crate) struct WorldState<'w, 's> {
+    commands: Commands<'w, 's>,
+    grid: ResMut<'w, HexGrid>,
+    resources: ResMut<'w, GameResources>,
+    round: ResMut<'w, Round>,
+    game_log: ResMut<'w, GameLog>,
+    objectives: ResMut<'w, Objectives>,
+    research: ResMut<'w, Research>,
+    galactic_events: ResMut<'w, GalacticEvents>,
+    diplomacy: ResMut<'w, Diplomacy>,
+    ship_ids: ResMut<'w, ShipIds>,
+    players: Query<'w, 's, (Entity, &'static Player, &'static mut Stats, Option<&'static Movable>)>,
+    fleets: Query<'w, 's, (Entity, &'static Fleet)>,
+    handle: Res<'w, SpaceSipTextureAtlas>,
+    asset_server: Res<'w, AssetServer>,
+    outcome: Option<Res<'w, GameOutcome>>,
+    app_state: ResMut<'w, NextState<AppState>>,
+}
+
+impl<'w, 's> WorldState<'w, 's> {
+    pub(crate) fn round(&self) -> i32 {
+        self.round.number
+    }
+
+    pub(crate) fn current_player(&self) -> Option<Player> {
+        self.players.iter().find(|(_, _, _, movable)| movable.is_some()).map(|(_, player, _, _)| *player)
+    }
+
+    pub(crate) fn capture(&self) -> GameSnapshot {
+        let mut players: Vec<PlayerSnapshot> = self.players.iter()
+            .map(|(_, player, stats, _)| capture_player(player, stats, &self.resources, &self.objectives, &self.research))
+            .collect();
+        players.sort_by_key(|snapshot| snapshot.player.id);
+        GameSnapshot {
+            round: self.round.number,
+            current_player: self.current_player(),
+            players,
+            planets: capture_planets(&self.grid),
+            fleets: self.fleets.iter().map(|(_, fleet)| fleet.clone()).collect(),
+            log: self.game_log.entries.clone(),
+            storms: capture_storms(&self.galactic_events),
+            diplomacy: self.diplomacy.clone(),
+            outcome: self.outcome.as_deref().cloned(),
+        }
+    }
+
+    pub(crate) fn apply(&mut self, snapshot: &GameSnapshot) {
+        for planet_snapshot in snapshot.planets.iter() {
+            let Some(planet) = self.grid.planets.get_mut(&planet_snapshot.hex) else { continue; };
+            planet.owner = planet_snapshot.owner;
+            planet.owner_army = planet_snapshot.army.clone();
+            planet.buildings = planet_snapshot.buildings.clone();
+            planet.production = planet_snapshot.production.clone();
+            planet.fortified = planet_snapshot.fortified;
+        }
+        for (entity, player, mut stats, movable) in self.players.iter_mut() {
+            let Some(player_snapshot) = snapshot.players.iter().find(|snapshot| snapshot.player == *player) else { continue; };
+            stats.moves_left = player_snapshot.moves_left;
+            stats.win_points = player_snapshot.win_points;
+            stats.eliminated = player_snapshot.eliminated;
+            self.resources.resources.insert(*player, player_snapshot.resources);
+            self.objectives.earned.insert(*player, player_snapshot.objective_points.iter().copied().collect());
+            self.research.players.insert(*player, player_snapshot.research.clone());
+            let should_move = snapshot.current_player == Some(*player);
+            if should_move && movable.is_none() {
+                self.commands.entity(entity).insert(Movable);
+            } else if !should_move && movable.is_some() {
+                self.commands.entity(entity).remove::<Movable>();
+            }
+        }
+        if self.round.number != snapshot.round {
+            self.round.number = snapshot.round;
+        }
+        for (entity, _) in self.fleets.iter() {
+            self.commands.entity(entity).despawn_recursive();
+        }
+        let ship_ids = snapshot.planets.iter().flat_map(|planet| planet.army.iter())
+            .chain(snapshot.fleets.iter().flat_map(|fleet| fleet.ships.iter()))
+            .map(|ship| ship.id);
+        self.ship_ids.skip_past(ship_ids.max().unwrap_or(0));
+        for fleet in snapshot.fleets.iter() {
+            spawn_fleet(fleet.clone(), &self.grid.layout, &self.handle, &self.asset_server, &mut self.commands);
+        }
+        self.game_log.entries = snapshot.log.clone();
+        self.galactic_events.storms = snapshot.storms.iter().copied().collect();
+        *self.diplomacy = snapshot.diplomacy.clone();
+        if let Some(outcome) = snapshot.outcome.as_ref().filter(|_| self.outcome.is_none()) {
+            self.commands.insert_resource(outcome.clone());
+            self.app_state.set(AppState::GameOver);
+        }
+    }
+}
+
+pub(crate) fn capture_planets(grid: &HexGrid) -> Vec<PlanetSnapshot> {
+    let mut planets: Vec<PlanetSnapshot> = grid.planets.values()
+        .map(|planet| PlanetSnapshot { hex: planet.hex, owner: planet.owner, army: planet.owner_army.clone(), buildings: planet.buildings.clone(), production: planet.production.clone(), fortified: planet.fortified })
+        .collect();
+    planets.sort_by_key(|planet| (planet.hex.x, planet.hex.y));
+    planets
+}
+
+pub(crate) fn capture_player(player: &Player, stats: &Stats, resources: &GameResources, objectives: &Objectives, research: &Research) -> PlayerSnapshot {
+    PlayerSnapshot {
+        player: *player,
+        moves_left: stats.moves_left,
+        win_points: stats.win_points,
+        eliminated: stats.eliminated,
+        resources: resources.resources.get(player).copied().unwrap_or_default(),
+        objective_points: objectives.list.iter()
+            .map(|objective| (objective.hex, objectives.earned_by(player, &objective.hex)))
+            .collect(),
+        research: research.of(player),
+    }
+}
+
+pub(crate) fn capture_storms(galactic_events: &GalacticEvents) -> Vec<(Hex, i32)> {
+    let mut storms: Vec<(Hex, i32)> = galactic_events.storms.iter().map(|(hex, until)| (*hex, *until)).collect();
+    storms.sort_by_key(|(hex, _)| (hex.x, hex.y));
+    storms
+}
@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use hexx::Hex;
+
+use crate::game_state::UpdateUI;
+use crate::ui::action_panel::systems::interaction::neighbours_in_planets;
+use crate::world::diplomacy::Diplomacy;
+use crate::world::fleets::Fleet;
+use crate::world::fog_of_war::{FogOfWar, Viewer};
+use crate::world::player::{Movable, Player};
+use crate::world::setup_world_grid::{HexGrid, Planet};
+
+const CUT_OFF_COLOR: Color = Color::rgb(1.0, 0.6, 0.0);
+const BLOCKADED_COLOR: Color = Color::rgb(0.9, 0.1, 0.1);
+
+pub struct SupplyPlugin;
+
+impl Plugin for SupplyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Supply::default())
+            .add_systems(Update, (update_supply, update_supply_overlay).chain().in_set(UpdateUI::RenderStats));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SupplyStatus {
+    #[default]
+    Supplied,
+    /// No chain of own or allied planets leads home, income is halved and no ships can be bought.
+    CutOff,
+    /// An enemy fleet sits next to the planet, it produces nothing.
+    Blockaded,
+}
+
+/// Supply status of every owned planet, planets missing here are supplied.
+#[derive(Debug, Clone, Default, PartialEq, Resource)]
+pub(crate) struct Supply {
+    statuses: HashMap<Hex, SupplyStatus>,
+}
+
+impl Supply {
+    pub(crate) fn status(&self, hex: &Hex) -> SupplyStatus {
+        self.statuses.get(hex).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn is_cut_off(&self, hex: &Hex) -> bool {
+        self.status(hex) == SupplyStatus::CutOff
+    }
+}
+
+/// Marks the icon showing the supply status of the planet at `hex`.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct SupplyInfo {
+    pub(crate) hex: Hex,
+}
+
+/// Planets of `player` linked to their home planet through own or allied planets.
+fn connected_planets(player: &Player, planets: &HashMap<Hex, Planet>, diplomacy: &Diplomacy) -> HashSet<Hex> {
+    let mut connected = HashSet::new();
+    let Some(home) = planets.values().find(|planet| planet.owner == *player && planet.is_home()).map(|planet| planet.hex) else { return connected; };
+    let mut queue = VecDeque::from([home]);
+    connected.insert(home);
+    while let Some(hex) = queue.pop_front() {
+        for (neighbour, planet) in neighbours_in_planets(&hex, planets) {
+            let is_friendly = planet.owner == *player || diplomacy.is_allied(player, &planet.owner);
+            if is_friendly && connected.insert(neighbour) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    connected
+}
+
+pub(crate) fn compute_supply<'a>(planets: &HashMap<Hex, Planet>, fleets: impl Iterator<Item=&'a Fleet>, diplomacy: &Diplomacy) -> Supply {
+    let fleets: Vec<&Fleet> = fleets.collect();
+    let mut connected: HashMap<Player, HashSet<Hex>> = HashMap::new();
+    let mut statuses = HashMap::new();
+    for (hex, planet) in planets.iter().filter(|(_, planet)| planet.owner.id != -1) {
+        let owner = planet.owner;
+        let is_blockaded = fleets.iter().any(|fleet| {
+            fleet.owner != owner
+                && !diplomacy.is_at_peace(&fleet.owner, &owner)
+                && Hex::NEIGHBORS_COORDS.iter().any(|delta| *delta + *hex == fleet.current_hex())
+        });
+        let is_connected = connected.entry(owner)
+            .or_insert_with(|| connected_planets(&owner, planets, diplomacy))
+            .contains(hex);
+        if is_blockaded {
+            statuses.insert(*hex, SupplyStatus::Blockaded);
+        } else if !is_connected {
+            statuses.insert(*hex, SupplyStatus::CutOff);
+        }
+    }
+    Supply { statuses }
+}
+
+pub(crate) fn update_supply(
+    grid: Res<HexGrid>,
+    fleets_query: Query<&Fleet>,
+    diplomacy: Res<Diplomacy>,
+    mut supply: ResMut<Supply>,
+) {
+    let new_supply = compute_supply(&grid.planets, fleets_query.iter(), &diplomacy);
+    if *supply != new_supply {
+        *supply = new_supply;
+    }
+}
+
+/// Players only see the supply of their own planets.
+fn update_supply_overlay(
+    supply: Res<Supply>,
+    grid: Res<HexGrid>,
+    fog: Res<FogOfWar>,
+    viewer: Res<Viewer>,
+    current_player_query: Query<&Player, With<Movable>>,
+    mut icon_query: Query<(&mut Sprite, &mut Visibility, &SupplyInfo)>,
+) {
+    let player = viewer.resolve(current_player_query.get_single().ok(), &fog);
+    for (mut sprite, mut visibility, info) in icon_query.iter_mut() {
+        let is_visible = player.map_or(true, |player| grid.planets[&info.hex].owner == player);
+        let color = match supply.status(&info.hex) {
+            SupplyStatus::Supplied => None,
+            SupplyStatus::CutOff => Some(CUT_OFF_COLOR),
+            SupplyStatus::Blockaded => Some(BLOCKADED_COLOR),
+        };
+        let new_visibility = match color {
+            Some(color) if is_visible => {
+                if sprite.color != color { sprite.color = color; }
+                Visibility::Inherited
+            }
+            _ => Visibility::Hidden,
+        };
+        if *visibility != new_visibility {
+            *visibility = new_visibility;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hexx::shapes;
+
+    use crate::world::diplomacy::Treaty;
+
+    use super::*;
+
+    const PLAYER: Player = Player { id: 1 };
+    const ALLY: Player = Player { id: 2 };
+    const ENEMY: Player = Player { id: 3 };
+    const HOME: Hex = Hex { x: -2, y: 0 };
+
+    /// Neutral planets on a board of radius 2 with `owned` ones given to their players and `PLAYER` at `HOME`.
+    fn planets(owned: &[(Hex, Player)]) -> HashMap<Hex, Planet> {
+        let mut planets: HashMap<Hex, Planet> = shapes::hexagon(Hex::ZERO, 2)
+            .map(|hex| (hex, Planet::default(hex, 0, 1, 1)))
+            .collect();
+        let home = planets.get_mut(&HOME).unwrap();
+        home.owner = PLAYER;
+        home.home_of = Some(PLAYER);
+        for (hex, owner) in owned {
+            planets.get_mut(hex).unwrap().owner = *owner;
+        }
+        planets
+    }
+
+    #[test]
+    fn planets_linked_to_home_are_supplied() {
+        let planets = planets(&[(Hex::new(-1, 0), PLAYER), (Hex::ZERO, PLAYER)]);
+        let supply = compute_supply(&planets, std::iter::empty(), &Diplomacy::default());
+        assert_eq!(supply.status(&HOME), SupplyStatus::Supplied);
+        assert_eq!(supply.status(&Hex::ZERO), SupplyStatus::Supplied);
+    }
+
+    #[test]
+    fn planets_away_from_home_are_cut_off() {
+        let planets = planets(&[(Hex::new(2, 0), PLAYER)]);
+        let supply = compute_supply(&planets, std::iter::empty(), &Diplomacy::default());
+        assert!(supply.is_cut_off(&Hex::new(2, 0)));
+        assert!(!supply.is_cut_off(&HOME));
+    }
+
+    #[test]
+    fn allied_planets_carry_supply() {
+        let planets = planets(&[(Hex::new(-1, 0), ALLY), (Hex::ZERO, PLAYER)]);
+        let mut diplomacy = Diplomacy::default();
+        assert!(compute_supply(&planets, std::iter::empty(), &diplomacy).is_cut_off(&Hex::ZERO));
+        diplomacy.treaties.push((PLAYER, ALLY, Treaty::Alliance));
+        assert!(!compute_supply(&planets, std::iter::empty(), &diplomacy).is_cut_off(&Hex::ZERO));
+    }
+
+    #[test]
+    fn lost_home_cuts_every_planet_off() {
+        let mut planets = planets(&[(Hex::new(-1, 0), PLAYER)]);
+        planets.get_mut(&HOME).unwrap().owner = ENEMY;
+        let supply = compute_supply(&planets, std::iter::empty(), &Diplomacy::default());
+        assert!(supply.is_cut_off(&Hex::new(-1, 0)));
+        assert_eq!(supply.status(&HOME), SupplyStatus::CutOff);
+    }
+
+    #[test]
+    fn enemy_fleets_next_to_a_planet_blockade_it() {
+        let planets = planets(&[(Hex::new(-1, 0), PLAYER)]);
+        let fleet = Fleet::new(ENEMY, vec![], vec![Hex::new(0, 0)]);
+        let supply = compute_supply(&planets, [&fleet].into_iter(), &Diplomacy::default());
+        assert_eq!(supply.status(&Hex::new(-1, 0)), SupplyStatus::Blockaded);
+        assert_eq!(supply.status(&HOME), SupplyStatus::Supplied);
+
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.treaties.push((PLAYER, ENEMY, Treaty::Pact { until_round: 5 }));
+        assert_eq!(compute_supply(&planets, [&fleet].into_iter(), &diplomacy).status(&Hex::new(-1, 0)), SupplyStatus::Supplied);
+    }
+}
@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::world::create_map_layout::create_objectives_for_radius;
+use crate::world::data::try_load_data;
+use crate::world::objectives::Objective;
+use crate::world::player::{Player, PLAYERS};
+
+/// Maps are described in data files, `--map <name>` picks `assets/data/maps/<name>.json`.
+const MAPS_DIR: &str = "data/maps";
+const DEFAULT_MAP: &str = "default";
+const DEFAULT_RADIUS: u32 = 3;
+
+/// Tile of the map atlas showing empty space, placed terrain is drawn on it with its tint and icon.
+const EMPTY_SPACE_TILE: usize = 5;
+const ASTEROID_ICON: &str = "kenney_simple-space/PNG/Default/meteor_detailedLarge.png";
+const WORMHOLE_ICON: &str = "kenney_simple-space/PNG/Default/effect_yellow.png";
+
+const NEBULA_COLOR: Color = Color::rgb(0.75, 0.55, 1.0);
+const BLACK_HOLE_COLOR: Color = Color::rgb(0.15, 0.1, 0.2);
+const WORMHOLE_COLOR: Color = Color::rgb(0.4, 1.0, 1.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum Terrain {
+    /// Planets and empty space.
+    #[default]
+    Open,
+    /// Fleets inside can't be seen by other players and entering it takes two jumps.
+    Nebula,
+    /// Attackers fight at a penalty, see `BattleModifiers`.
+    AsteroidField,
+    /// Nothing can enter or pass it.
+    BlackHole,
+    /// A jump to `exit` counts like one to a neighbour.
+    Wormhole { exit: Hex },
+}
+
+impl Terrain {
+    /// Jumps it takes a fleet to enter the hex.
+    pub(crate) fn movement_cost(&self) -> u32 {
+        match self {
+            Terrain::Nebula => 2,
+            _ => 1,
+        }
+    }
+
+    pub(crate) fn is_enterable(&self) -> bool {
+        *self != Terrain::BlackHole
+    }
+
+    pub(crate) fn hides_fleets(&self) -> bool {
+        *self == Terrain::Nebula
+    }
+
+    pub(crate) fn wormhole_exit(&self) -> Option<Hex> {
+        match self {
+            Terrain::Wormhole { exit } => Some(*exit),
+            _ => None,
+        }
+    }
+
+    /// Atlas tile drawn instead of the one from the map layout, `None` keeps the layout's tile.
+    pub(crate) fn tile(&self) -> Option<usize> {
+        match self {
+            Terrain::Open => None,
+            _ => Some(EMPTY_SPACE_TILE),
+        }
+    }
+
+    /// Image drawn over the tile, nebulae and black holes are told apart by their tint alone.
+    pub(crate) fn icon(&self) -> Option<&'static str> {
+        match self {
+            Terrain::AsteroidField => Some(ASTEROID_ICON),
+            Terrain::Wormhole { .. } => Some(WORMHOLE_ICON),
+            _ => None,
+        }
+    }
+
+    /// Color the tile is tinted with while it is visible.
+    pub(crate) fn tint(&self) -> Color {
+        match self {
+            Terrain::Open | Terrain::AsteroidField => Color::WHITE,
+            Terrain::Nebula => NEBULA_COLOR,
+            Terrain::BlackHole => BLACK_HOLE_COLOR,
+            Terrain::Wormhole { .. } => WORMHOLE_COLOR,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TerrainPlacement {
+    pub hex: Hex,
+    pub terrain: Terrain,
+}
+
+/// Planet a player starts on, it keeps counting as their home while they hold it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PlayerHome {
+    pub player: Player,
+    pub hex: Hex,
+}
+
+/// Terrain placed on top of the planet layout, hexes it lands on lose their resources.
+/// Wormholes are listed as pairs, both ends lead to each other.
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub(crate) struct GameMap {
+    /// Name of the file the map was loaded from, network players have to play the same one.
+    #[serde(skip)]
+    pub name: String,
+    /// Size of the hexagonal board, the planet layouts exist for radius 2 and 3.
+    #[serde(default = "default_radius")]
+    pub radius: u32,
+    #[serde(default)]
+    pub terrain: Vec<TerrainPlacement>,
+    #[serde(default)]
+    pub wormholes: Vec<(Hex, Hex)>,
+    /// Players missing here have no home planet, the corners of the board are used when the map lists none.
+    #[serde(default = "default_homes")]
+    pub homes: Vec<PlayerHome>,
+    /// Hexes giving win points, maps listing none get the default ones of their size.
+    #[serde(default)]
+    pub objectives: Vec<Objective>,
+}
+
+impl Default for GameMap {
+    fn default() -> Self {
+        GameMap {
+            name: DEFAULT_MAP.to_string(),
+            radius: default_radius(),
+            terrain: vec![],
+            wormholes: vec![],
+            homes: default_homes(),
+            objectives: vec![],
+        }
+    }
+}
+
+fn default_radius() -> u32 {
+    DEFAULT_RADIUS
+}
+
+fn default_homes() -> Vec<PlayerHome> {
+    vec![
+        PlayerHome { player: PLAYERS[0], hex: Hex { x: -2, y: 2 } },
+        PlayerHome { player: PLAYERS[1], hex: Hex { x: 2, y: -2 } },
+        PlayerHome { player: PLAYERS[2], hex: Hex { x: 2, y: 0 } },
+        PlayerHome { player: PLAYERS[3], hex: Hex { x: -2, y: 0 } },
+    ]
+}
+
+impl GameMap {
+    /// `--map nebulae` loads `assets/data/maps/nebulae.json`.
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Self {
+        let args: Vec<String> = args.collect();
+        let name = args.iter()
+            .position(|arg| arg == "--map")
+            .and_then(|index| args.get(index + 1))
+            .map_or(DEFAULT_MAP, |name| name.as_str());
+        Self::named(name)
+    }
+
+    /// A missing or broken map falls back to the default one, and that one to an empty board of the default size.
+    pub(crate) fn named(name: &str) -> Self {
+        match try_load_data::<GameMap>(&format!("{}/{}.json", MAPS_DIR, name)) {
+            Ok(map) => GameMap { name: name.to_string(), ..map },
+            Err(error) if name != DEFAULT_MAP => {
+                warn!("{}, using the {} map", error, DEFAULT_MAP);
+                Self::named(DEFAULT_MAP)
+            }
+            Err(error) => {
+                error!("{}, using an empty board", error);
+                GameMap::default()
+            }
+        }
+    }
+
+    pub(crate) fn home_of(&self, player: &Player) -> Option<Hex> {
+        self.homes.iter().find(|home| home.player == *player).map(|home| home.hex)
+    }
+
+    /// Moves the home of `player` to `hex`, used by scenarios.
+    pub(crate) fn set_home(&mut self, player: Player, hex: Hex) {
+        self.homes.retain(|home| home.player != player);
+        self.homes.push(PlayerHome { player, hex });
+    }
+
+    /// Win points of every objective hex of the map.
+    pub(crate) fn objective_points(&self) -> HashMap<Hex, i32> {
+        if self.objectives.is_empty() {
+            return create_objectives_for_radius(self.radius);
+        }
+        self.objectives.iter().map(|objective| (objective.hex, objective.points)).collect()
+    }
+
+    pub(crate) fn home_hexes(&self) -> Vec<Hex> {
+        self.homes.iter().map(|home| home.hex).collect()
+    }
+
+    /// Terrain of every hex the map places something on, hexes in `reserved` are left alone.
+    pub(crate) fn placements(&self, reserved: &[Hex]) -> HashMap<Hex, Terrain> {
+        let mut placements = HashMap::new();
+        for placement in self.terrain.iter() {
+            if let Terrain::Wormhole { .. } = placement.terrain {
+                warn!("Wormhole at {:?} has to be listed in pairs, ignoring it", placement.hex);
+                continue;
+            }
+            placements.insert(placement.hex, placement.terrain);
+        }
+        for (entrance, exit) in self.wormholes.iter() {
+            placements.insert(*entrance, Terrain::Wormhole { exit: *exit });
+            placements.insert(*exit, Terrain::Wormhole { exit: *entrance });
+        }
+        placements.retain(|hex, _| {
+            let is_reserved = reserved.contains(hex);
+            if is_reserved { warn!("Home planet at {:?} can't hold terrain, ignoring it", hex); }
+            !is_reserved
+        });
+        placements
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::world::buildings::BuildingType;
+use crate::world::diplomacy::Proposal;
+use crate::world::production::ProductionChange;
+
+pub struct TurnActionsPlugin;
+
+impl Plugin for TurnActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TurnAction>();
+    }
+}
+
+/// What the current player did, sent by the UI once it applied the action locally.
+/// Network games replay these on the server, which runs the rules and sends back the resulting state.
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum TurnAction {
+    /// Edits of the production queue at `hex`, made in the spawn menu.
+    ChangeProduction { hex: Hex, changes: Vec<ProductionChange> },
+    Build { hex: Hex, building: BuildingType },
+    /// Ids of the ships sent towards `destination`.
+    MoveShips { ships: Vec<u32>, destination: Hex },
+    Fortify { hex: Hex },
+    StartResearch { tech: String },
+    Propose { proposal: Proposal },
+    Answer { proposal: Proposal, accept: bool },
+    SkipRound,
+    Surrender,
+    /// The turn timer ran out, `skip_round` is set once the bank is empty too.
+    OutOfTime { skip_round: bool },
+}
+
+impl TurnAction {
+    /// Whether the turn passes on to the next player afterwards.
+    pub(crate) fn ends_turn(&self) -> bool {
+        !matches!(self, TurnAction::StartResearch { .. } | TurnAction::Propose { .. } | TurnAction::Answer { .. } | TurnAction::Surrender)
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::game_state::UpdateUI;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::handover::plugin::HandoverState;
+use crate::ui::stats::resources::Round;
+use crate::world::actions::cancel_running_action;
+use crate::world::game_log::{GameEvent, GameLog};
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+use crate::world::production::ProductionDraft;
+use crate::world::resources::GameResources;
+use crate::world::setup_world_grid::HexGrid;
+use crate::world::turn_actions::TurnAction;
+use crate::world::victory::GameOutcome;
+
+pub struct TurnTimerPlugin;
+
+impl Plugin for TurnTimerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TurnClock>()
+            .add_systems(Startup, fill_time_banks)
+            .add_systems(Update, tick_turn_clock.run_if(in_state(HandoverState::Hidden)).in_set(UpdateUI::RenderStats))
+            // Input sets only run for the local seat online, so each client ends only its own turns
+            .add_systems(Update, end_expired_turn.run_if(in_state(HandoverState::Hidden)).in_set(UpdateUI::UserInput));
+    }
+}
+
+/// Time limits of the game in seconds, `--turn-limit 60` caps every turn and
+/// `--time-bank 900` gives every player a chess clock for the whole game. Both are off by default.
+#[derive(Debug, Clone, Copy, Default, Resource)]
+pub(crate) struct TimeControl {
+    pub turn_limit: Option<f32>,
+    pub time_bank: Option<f32>,
+}
+
+impl TimeControl {
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Self {
+        let args: Vec<String> = args.collect();
+        let seconds_after = |flag: &str| args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+            .and_then(|value| {
+                let seconds = value.parse::<f32>().ok().filter(|seconds| *seconds > 0.0);
+                if seconds.is_none() { warn!("Ignoring {} '{}', expected a positive number of seconds", flag, value); }
+                seconds
+            });
+        TimeControl {
+            turn_limit: seconds_after("--turn-limit"),
+            time_bank: seconds_after("--time-bank"),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.turn_limit.is_some() || self.time_bank.is_some()
+    }
+}
+
+/// Time spent on the running turn and what is left in every player's bank.
+/// The clocks stop while the handover screen hides the board.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct TurnClock {
+    pub turn_elapsed: f32,
+    pub banks: HashMap<Player, f32>,
+}
+
+impl TurnClock {
+    /// Seconds left on the running turn for `player`, `None` without time control.
+    pub(crate) fn time_left(&self, player: &Player, time_control: &TimeControl) -> Option<f32> {
+        let turn_left = time_control.turn_limit.map(|limit| limit - self.turn_elapsed);
+        let bank_left = self.banks.get(player).copied();
+        match (turn_left, bank_left) {
+            (Some(turn), Some(bank)) => Some(turn.min(bank)),
+            (turn, bank) => turn.or(bank),
+        }.map(|seconds| seconds.max(0.0))
+    }
+
+    pub(crate) fn is_bank_empty(&self, player: &Player) -> bool {
+        self.banks.get(player).map_or(false, |bank| *bank <= 0.0)
+    }
+}
+
+/// Clock label as `m:ss`.
+pub(crate) fn format_seconds(seconds: f32) -> String {
+    let seconds = seconds.ceil() as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn fill_time_banks(mut clock: ResMut<TurnClock>, time_control: Res<TimeControl>, turn_order: Res<TurnOrder>) {
+    let Some(bank) = time_control.time_bank else { return; };
+    clock.banks = turn_order.players.iter().map(|player| (*player, bank)).collect();
+}
+
+pub(crate) fn start_turn(mut clock: ResMut<TurnClock>) {
+    clock.turn_elapsed = 0.0;
+}
+
+fn tick_turn_clock(
+    time: Res<Time>,
+    time_control: Res<TimeControl>,
+    mut clock: ResMut<TurnClock>,
+    outcome: Option<Res<GameOutcome>>,
+    current_player_query: Query<&Player, With<Movable>>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+) {
+    if !time_control.is_enabled() || outcome.is_some() { return; }
+    if *turn_switched_state.get() != TurnSwitchedState::OnDefaultState { return; }
+    let Ok(player) = current_player_query.get_single() else { return; };
+    let delta = time.delta_seconds();
+    clock.turn_elapsed += delta;
+    if let Some(bank) = clock.banks.get_mut(player) {
+        *bank = (*bank - delta).max(0.0);
+    }
+}
+
+/// Cancels whatever the player was doing, like the `C` key, and passes the turn on.
+/// A player whose bank is empty passes the rest of the round too.
+fn end_expired_turn(
+    mut commands: Commands,
+    time_control: Res<TimeControl>,
+    clock: Res<TurnClock>,
+    mut current_player_query: Query<(&Player, &mut Stats), With<Movable>>,
+    mut grid: ResMut<HexGrid>,
+    mut player_resources: ResMut<GameResources>,
+    draft: Option<Res<ProductionDraft>>,
+    mut game_log: ResMut<GameLog>,
+    round: Res<Round>,
+    outcome: Option<Res<GameOutcome>>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    mut next_turn_switched_state: ResMut<NextState<TurnSwitchedState>>,
+    mut turn_actions: EventWriter<TurnAction>,
+) {
+    if !time_control.is_enabled() || outcome.is_some() { return; }
+    if *turn_switched_state.get() != TurnSwitchedState::OnDefaultState { return; }
+    let Ok((player, mut stats)) = current_player_query.get_single_mut() else { return; };
+    if clock.time_left(player, &time_control) != Some(0.0) { return; }
+
+    cancel_running_action(&mut commands, &mut grid, &mut player_resources, draft.as_deref());
+    let skip_round = clock.is_bank_empty(player);
+    if skip_round {
+        stats.moves_left = 1;
+    }
+    game_log.record(round.number, *player, GameEvent::OutOfTime);
+    turn_actions.send(TurnAction::OutOfTime { skip_round });
+    next_turn_switched_state.set(TurnSwitchedState::OnTurnSwitched);
+}
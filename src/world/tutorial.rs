@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::UpdateUI;
+use crate::space_ships::SpaceShipType;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::stats::resources::Round;
+use crate::world::actions::ActionsState;
+use crate::world::data::load_data;
+use crate::world::game_log::{GameEvent, GameLog, LogEntry};
+use crate::world::player::{Movable, Player, Stats, TurnOrder};
+use crate::world::setup_world_grid::SelectedHex;
+use crate::world::victory::GameOutcome;
+
+/// The guided first game is described in a data file, `--tutorial` starts it.
+const TUTORIAL_FILE: &str = "data/tutorial.json";
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, advance_tutorial.run_if(resource_exists::<Tutorial>()).in_set(UpdateUI::RenderStats))
+            .add_systems(Update, pass_opponent_turns.run_if(resource_exists::<Tutorial>()).in_set(UpdateUI::UserInput));
+    }
+}
+
+/// What the student has to do to get to the next step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TutorialGoal {
+    SelectHex { hex: Hex },
+    StartAction { action: TutorialAction },
+    OrderShips { ship_type: SpaceShipType, count: usize },
+    ShipsDelivered { ship_type: SpaceShipType, count: usize },
+    CapturePlanet { hex: Hex },
+    /// Closing words, the tutorial stays on this step.
+    Finish,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TutorialAction {
+    Spawn,
+    Move,
+    Build,
+}
+
+impl TutorialAction {
+    fn state(&self) -> ActionsState {
+        match self {
+            TutorialAction::Spawn => ActionsState::SpawningSpaceShips,
+            TutorialAction::Move => ActionsState::MovingSpaceShips,
+            TutorialAction::Build => ActionsState::ConstructingBuildings,
+        }
+    }
+}
+
+/// Part of the screen the current step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TutorialHighlight {
+    Hex { hex: Hex },
+    Button { button: TutorialButton },
+}
+
+/// Buttons of the action panel a step can point at.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TutorialButton {
+    Spawn,
+    Move,
+    Build,
+    Fortify,
+    SkipRound,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TutorialStep {
+    pub title: String,
+    pub text: String,
+    pub goal: TutorialGoal,
+    #[serde(default)]
+    pub highlights: Vec<TutorialHighlight>,
+}
+
+/// A scripted game on a small fixed map, the first player is taught step by step
+/// while every other seat passes its turns.
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub(crate) struct Tutorial {
+    /// Map file the tutorial is played on, see `GameMap`.
+    pub map: String,
+    pub steps: Vec<TutorialStep>,
+    #[serde(skip)]
+    pub current: usize,
+    /// Length of the game log when the current step started, only later entries count for it.
+    #[serde(skip)]
+    step_started_at: usize,
+}
+
+impl Tutorial {
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Option<Self> {
+        let mut args = args;
+        if !args.any(|arg| arg == "--tutorial") { return None; }
+        let tutorial: Tutorial = load_data(TUTORIAL_FILE);
+        if tutorial.steps.is_empty() {
+            panic!("Tutorial in {} has no steps", TUTORIAL_FILE);
+        }
+        Some(tutorial)
+    }
+
+    pub(crate) fn step(&self) -> &TutorialStep {
+        &self.steps[self.current]
+    }
+
+    pub(crate) fn highlights(&self) -> &[TutorialHighlight] {
+        &self.step().highlights
+    }
+
+    fn is_done(&self, student: &Player, log: &[LogEntry], selected_hex: &SelectedHex, action: &ActionsState) -> bool {
+        let events = log.iter()
+            .filter(|entry| entry.player == *student)
+            .map(|entry| &entry.event);
+        match &self.step().goal {
+            TutorialGoal::SelectHex { hex } => selected_hex.is_selected && selected_hex.hex == *hex,
+            TutorialGoal::StartAction { action: expected } => *action == expected.state(),
+            TutorialGoal::OrderShips { ship_type, count } => events
+                .filter_map(|event| match event {
+                    GameEvent::ShipsBought { ships, .. } => Some(ships.iter().filter(|ship| *ship == ship_type).count()),
+                    _ => None,
+                })
+                .sum::<usize>() >= *count,
+            TutorialGoal::ShipsDelivered { ship_type, count } => events
+                .filter(|event| matches!(event, GameEvent::ShipDelivered { ship, .. } if ship == ship_type))
+                .count() >= *count,
+            TutorialGoal::CapturePlanet { hex } => events
+                .any(|event| matches!(event, GameEvent::PlanetCaptured { hex: captured, .. } if captured == hex)),
+            TutorialGoal::Finish => false,
+        }
+    }
+}
+
+fn advance_tutorial(
+    mut tutorial: ResMut<Tutorial>,
+    game_log: Res<GameLog>,
+    selected_hex: Res<SelectedHex>,
+    action: Res<State<ActionsState>>,
+    turn_order: Res<TurnOrder>,
+) {
+    // Undo can take back log entries the step already counted
+    let from = tutorial.step_started_at.min(game_log.entries.len());
+    if !tutorial.is_done(&turn_order.first(), &game_log.entries[from..], &selected_hex, action.get()) { return; }
+    if tutorial.current + 1 < tutorial.steps.len() {
+        tutorial.current += 1;
+        tutorial.step_started_at = game_log.entries.len();
+    }
+}
+
+/// Opponents of the student skip their rounds, like the skip round button.
+fn pass_opponent_turns(
+    mut current_player_query: Query<(&Player, &mut Stats), With<Movable>>,
+    turn_order: Res<TurnOrder>,
+    outcome: Option<Res<GameOutcome>>,
+    round: Res<Round>,
+    mut game_log: ResMut<GameLog>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    mut next_turn_switched_state: ResMut<NextState<TurnSwitchedState>>,
+) {
+    if outcome.is_some() { return; }
+    if *turn_switched_state.get() != TurnSwitchedState::OnDefaultState { return; }
+    let Ok((player, mut stats)) = current_player_query.get_single_mut() else { return; };
+    if *player == turn_order.first() { return; }
+    stats.moves_left = 1;
+    game_log.record(round.number, *player, GameEvent::RoundSkipped);
+    next_turn_switched_state.set(TurnSwitchedState::OnTurnSwitched);
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::game_state::UpdateUI;
+use crate::network::client::is_local_game;
+use crate::ui::action_panel::plugin::TurnSwitchedState;
+use crate::ui::handover::plugin::HandoverState;
+use crate::world::actions::ActionsState;
+use crate::world::ai::AiPlayers;
+use crate::world::fog_of_war::FogOfWar;
+use crate::world::player::Player;
+use crate::world::random::GameRng;
+use crate::world::snapshot::{GameSnapshot, WorldState};
+use crate::world::turn_actions::TurnAction;
+use crate::world::victory::GameOutcome;
+
+pub struct UndoPlugin;
+
+impl Plugin for UndoPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(UndoHistory::default())
+            .add_systems(Update, record_checkpoint.run_if(is_local_game).in_set(UpdateUI::RenderStats))
+            .add_systems(Update, note_committed_actions.run_if(is_local_game).in_set(UpdateUI::FlipTurn))
+            .add_systems(Update, handle_undo_keys
+                .run_if(is_local_game)
+                .run_if(in_state(ActionsState::NoActionRunning))
+                .run_if(not(resource_exists::<GameOutcome>()))
+                .in_set(UpdateUI::UserInput));
+    }
+}
+
+/// State of the game when a player was about to act, together with the dice and what everyone had seen at that moment.
+#[derive(Clone)]
+struct Checkpoint {
+    snapshot: GameSnapshot,
+    rng: GameRng,
+    fog: FogOfWar,
+}
+
+/// A checkpoint is taken at the start of every turn and after every committed action,
+/// undoing an action goes back to the checkpoint before it.
+#[derive(Default, Resource)]
+pub(crate) struct UndoHistory {
+    checkpoints: Vec<Checkpoint>,
+    redo: Vec<Checkpoint>,
+    /// Round and player to move of the top checkpoint.
+    position: Option<(i32, Player)>,
+    /// An action was committed since the top checkpoint.
+    is_action_pending: bool,
+}
+
+fn note_committed_actions(mut history: ResMut<UndoHistory>, mut turn_actions: EventReader<TurnAction>) {
+    if turn_actions.read().count() > 0 {
+        history.is_action_pending = true;
+    }
+}
+
+/// Actions ending the turn are recorded once the next turn has started.
+fn record_checkpoint(
+    mut history: ResMut<UndoHistory>,
+    world_state: WorldState,
+    rng: Res<GameRng>,
+    fog: Res<FogOfWar>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+) {
+    if *turn_switched_state.get() != TurnSwitchedState::OnDefaultState { return; }
+    let Some(current_player) = world_state.current_player() else { return; };
+    let position = Some((world_state.round(), current_player));
+    if history.position == position && !history.is_action_pending { return; }
+
+    history.checkpoints.push(Checkpoint { snapshot: world_state.capture(), rng: rng.clone(), fog: fog.clone() });
+    history.redo.clear();
+    history.position = position;
+    history.is_action_pending = false;
+}
+
+/// Turns of the computer are stepped over, it would only play them again.
+fn handle_undo_keys(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut history: ResMut<UndoHistory>,
+    mut world_state: WorldState,
+    mut rng: ResMut<GameRng>,
+    mut fog: ResMut<FogOfWar>,
+    ai_players: Res<AiPlayers>,
+    turn_switched_state: Res<State<TurnSwitchedState>>,
+    handover_state: Res<State<HandoverState>>,
+    mut next_handover_state: ResMut<NextState<HandoverState>>,
+) {
+    if *turn_switched_state.get() != TurnSwitchedState::OnDefaultState { return; }
+    let is_ctrl_pressed = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    if !is_ctrl_pressed || !keyboard_input.just_pressed(KeyCode::Z) { return; }
+    let is_redo = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let is_computer_to_move = |checkpoint: &Checkpoint| checkpoint.snapshot.current_player.map_or(false, |player| ai_players.contains(&player));
+
+    if is_redo {
+        let Some(checkpoint) = history.redo.pop() else { return; };
+        history.checkpoints.push(checkpoint);
+        while history.checkpoints.last().map_or(false, is_computer_to_move) {
+            let Some(checkpoint) = history.redo.pop() else { break; };
+            history.checkpoints.push(checkpoint);
+        }
+    } else {
+        if history.checkpoints.len() < 2 { return; }
+        let undone = history.checkpoints.pop().unwrap();
+        history.redo.push(undone);
+        while history.checkpoints.len() > 1 && history.checkpoints.last().map_or(false, is_computer_to_move) {
+            let undone = history.checkpoints.pop().unwrap();
+            history.redo.push(undone);
+        }
+    }
+    let checkpoint = history.checkpoints.last().unwrap().clone();
+
+    info!("{} to round {}", if is_redo { "Redo" } else { "Undo" }, checkpoint.snapshot.round);
+    world_state.apply(&checkpoint.snapshot);
+    *rng = checkpoint.rng;
+    *fog = checkpoint.fog;
+    history.position = checkpoint.snapshot.current_player.map(|player| (checkpoint.snapshot.round, player));
+    history.is_action_pending = false;
+    if *handover_state.get() == HandoverState::Shown {
+        next_handover_state.set(HandoverState::Hidden);
+    }
+}
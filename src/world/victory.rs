@@ -0,0 +1,258 @@
+use std::fmt;
+use std::ops::DerefMut;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::world::diplomacy::Diplomacy;
+use crate::world::fleets::Fleet;
+use crate::world::objectives::{Objectives, parse_objectives};
+use crate::world::player::{Player, Stats};
+use crate::world::resources::GameResources;
+use crate::world::setup_world_grid::HexGrid;
+
+pub(crate) const MAX_WIN_POINTS: i32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum VictoryCondition {
+    /// Collect `points` win points by holding objective hexes at the end of rounds.
+    KingOfTheHill { points: i32 },
+    /// Own every planet of the map.
+    TotalDomination,
+    /// Stockpile `resources` resources.
+    Economic { resources: u32 },
+    /// Have the most win points once `rounds` rounds are played.
+    MostPoints { rounds: i32 },
+    /// Be the last player owning planets or fleets.
+    Elimination,
+}
+
+impl fmt::Display for VictoryCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VictoryCondition::KingOfTheHill { points } => write!(f, "King of the hill ({} points)", points),
+            VictoryCondition::TotalDomination => write!(f, "Total domination"),
+            VictoryCondition::Economic { resources } => write!(f, "Economic victory ({} resources)", resources),
+            VictoryCondition::MostPoints { rounds } => write!(f, "Most points after {} rounds", rounds),
+            VictoryCondition::Elimination => write!(f, "Elimination of all enemies"),
+        }
+    }
+}
+
+/// Win conditions of the current game, any of them ends it.
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct VictoryConditions {
+    /// Objective hexes replacing the ones declared by the map.
+    pub objectives: Option<HashMap<Hex, i32>>,
+    pub conditions: Vec<VictoryCondition>,
+}
+
+impl Default for VictoryConditions {
+    fn default() -> Self {
+        Self {
+            objectives: None,
+            conditions: vec![VictoryCondition::KingOfTheHill { points: MAX_WIN_POINTS }],
+        }
+    }
+}
+
+impl VictoryConditions {
+    /// `--victory hill:5,domination,economic:100,rounds:20,elimination` picks the conditions,
+    /// `--objectives 0,0:2;1,-1:1` the hexes giving win points and how many.
+    pub(crate) fn from_args(args: impl Iterator<Item=String>) -> Self {
+        let args: Vec<String> = args.collect();
+        let value_after = |flag: &str| args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+            .cloned();
+
+        let mut victory_conditions = VictoryConditions::default();
+        if let Some(value) = value_after("--victory") {
+            victory_conditions.set_conditions(value.split(','));
+        }
+        if let Some(value) = value_after("--objectives") {
+            let objectives = parse_objectives(&value);
+            if !objectives.is_empty() {
+                victory_conditions.objectives = Some(objectives);
+            }
+        }
+        victory_conditions
+    }
+
+    /// Replaces the conditions with the named ones, written like in `--victory`.
+    pub(crate) fn set_conditions<'a>(&mut self, names: impl Iterator<Item=&'a str>) {
+        let names: Vec<&str> = names.collect();
+        let conditions: Vec<VictoryCondition> = names.iter().copied().filter_map(parse_condition).collect();
+        if conditions.is_empty() {
+            warn!("No known victory condition in '{}', using the default ones", names.join(","));
+        } else {
+            self.conditions = conditions;
+        }
+    }
+}
+
+fn parse_condition(value: &str) -> Option<VictoryCondition> {
+    let (name, parameter) = value.split_once(':').unwrap_or((value, ""));
+    match name.trim() {
+        "hill" => Some(VictoryCondition::KingOfTheHill { points: parameter.parse().unwrap_or(MAX_WIN_POINTS) }),
+        "domination" => Some(VictoryCondition::TotalDomination),
+        "economic" => parameter.parse().ok().map(|resources| VictoryCondition::Economic { resources }),
+        "rounds" => parameter.parse().ok().map(|rounds| VictoryCondition::MostPoints { rounds }),
+        "elimination" => Some(VictoryCondition::Elimination),
+        _ => {
+            warn!("Unknown victory condition '{}'", value);
+            None
+        }
+    }
+}
+
+/// How the game ended, `winner` is `None` on a draw.
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
+pub(crate) struct GameOutcome {
+    pub winner: Option<Player>,
+    /// Allies of the winner, who share the victory.
+    pub allies: Vec<Player>,
+    pub condition: VictoryCondition,
+}
+
+/// The player left in the game once everyone else is gone or allied with them.
+pub(crate) fn last_standing(alive: &[Player], diplomacy: &Diplomacy) -> Option<Player> {
+    let first = alive.first()?;
+    alive.iter().all(|player| player == first || diplomacy.is_allied(player, first)).then_some(*first)
+}
+
+/// Checked at the end of every round, the first fulfilled condition decides the game.
+pub(crate) fn check_victory<'a>(
+    victory_conditions: &VictoryConditions,
+    round: i32,
+    grid: &HexGrid,
+    game_resources: &GameResources,
+    players: &[(Player, &Stats)],
+    fleets: impl Iterator<Item=&'a Fleet>,
+    diplomacy: &Diplomacy,
+) -> Option<GameOutcome> {
+    let mut presence: HashMap<Player, usize> = HashMap::new();
+    for planet in grid.planets.values() {
+        *presence.entry(planet.owner).or_insert(0) += 1;
+    }
+    for fleet in fleets {
+        presence.entry(fleet.owner).or_insert(0);
+    }
+
+    for condition in victory_conditions.conditions.iter() {
+        let winner = match condition {
+            VictoryCondition::KingOfTheHill { points } => players.iter()
+                .filter(|(_, stats)| stats.win_points >= *points)
+                .max_by_key(|(_, stats)| stats.win_points)
+                .map(|(player, _)| Some(*player)),
+            VictoryCondition::TotalDomination => players.iter()
+                .find(|(player, _)| grid.planets.values()
+                    .filter(|planet| planet.terrain.is_enterable())
+                    .all(|planet| planet.owner == *player || diplomacy.is_allied(player, &planet.owner)))
+                .map(|(player, _)| Some(*player)),
+            VictoryCondition::Economic { resources } => players.iter()
+                .filter(|(player, _)| game_resources.resources.get(player).map_or(false, |stock| stock.resources >= *resources))
+                .max_by_key(|(player, _)| game_resources.resources[player].resources)
+                .map(|(player, _)| Some(*player)),
+            VictoryCondition::MostPoints { rounds } if round >= *rounds => {
+                let best = players.iter().map(|(_, stats)| stats.win_points).max().unwrap_or(0);
+                let leaders: Vec<Player> = players.iter().filter(|(_, stats)| stats.win_points == best).map(|(player, _)| *player).collect();
+                Some(if leaders.len() == 1 { Some(leaders[0]) } else { None })
+            }
+            VictoryCondition::MostPoints { .. } => None,
+            VictoryCondition::Elimination => {
+                let alive: Vec<Player> = players.iter().filter(|(player, _)| presence.contains_key(player)).map(|(player, _)| *player).collect();
+                last_standing(&alive, diplomacy).map(Some)
+            }
+        };
+        if let Some(winner) = winner {
+            let allies = winner.map_or(vec![], |winner| diplomacy.allies_of(&winner));
+            return Some(GameOutcome { winner, allies, condition: condition.clone() });
+        }
+    }
+    None
+}
+
+/// Objectives held at the end of the round give their points, then the victory conditions are checked.
+/// Played out the same way by `update_win_points_number` and the server.
+pub(crate) fn end_round<'a, 'b, S: DerefMut<Target=Stats>>(
+    players: impl Iterator<Item=(&'a Player, S)>,
+    objectives: &mut Objectives,
+    victory_conditions: &VictoryConditions,
+    round: i32,
+    grid: &HexGrid,
+    game_resources: &GameResources,
+    fleets: impl Iterator<Item=&'b Fleet>,
+    diplomacy: &Diplomacy,
+) -> Option<GameOutcome> {
+    let mut players: Vec<(&Player, S)> = players.collect();
+    for (player, stats) in players.iter_mut() {
+        stats.win_points += objectives.award(grid, player);
+    }
+    let players: Vec<(Player, &Stats)> = players.iter()
+        .filter(|(_, stats)| !stats.eliminated)
+        .map(|(player, stats)| (**player, &**stats))
+        .collect();
+    check_victory(victory_conditions, round, grid, game_resources, &players, fleets, diplomacy)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::diplomacy::Treaty;
+
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item=String> + '_ {
+        line.split(' ').map(String::from)
+    }
+
+    #[test]
+    fn king_of_the_hill_is_played_without_arguments() {
+        let victory_conditions = VictoryConditions::from_args(std::iter::empty());
+        assert_eq!(victory_conditions.conditions, vec![VictoryCondition::KingOfTheHill { points: MAX_WIN_POINTS }]);
+        assert_eq!(victory_conditions.objectives, None);
+    }
+
+    #[test]
+    fn conditions_are_read_with_their_parameters() {
+        let victory_conditions = VictoryConditions::from_args(args("--victory hill:7,domination,economic:100,rounds:20,elimination"));
+        assert_eq!(victory_conditions.conditions, vec![
+            VictoryCondition::KingOfTheHill { points: 7 },
+            VictoryCondition::TotalDomination,
+            VictoryCondition::Economic { resources: 100 },
+            VictoryCondition::MostPoints { rounds: 20 },
+            VictoryCondition::Elimination,
+        ]);
+    }
+
+    #[test]
+    fn unknown_conditions_are_skipped() {
+        let victory_conditions = VictoryConditions::from_args(args("--victory conquest,economic,domination"));
+        assert_eq!(victory_conditions.conditions, vec![VictoryCondition::TotalDomination]);
+    }
+
+    #[test]
+    fn defaults_stay_when_no_condition_is_known() {
+        let victory_conditions = VictoryConditions::from_args(args("--victory conquest"));
+        assert_eq!(victory_conditions.conditions, VictoryConditions::default().conditions);
+    }
+
+    #[test]
+    fn objectives_replace_the_ones_of_the_map() {
+        let victory_conditions = VictoryConditions::from_args(args("--objectives 0,0:2;1,-1"));
+        assert_eq!(victory_conditions.objectives, Some([(Hex::ZERO, 2), (Hex { x: 1, y: -1 }, 1)].into_iter().collect()));
+    }
+
+    #[test]
+    fn last_standing_player_may_have_allies_left() {
+        let (first, second) = (Player { id: 1 }, Player { id: 2 });
+        let mut diplomacy = Diplomacy::default();
+        assert_eq!(last_standing(&[first], &diplomacy), Some(first));
+        assert_eq!(last_standing(&[first, second], &diplomacy), None);
+        diplomacy.treaties.push((first, second, Treaty::Alliance));
+        assert_eq!(last_standing(&[first, second], &diplomacy), Some(first));
+        assert_eq!(last_standing(&[], &diplomacy), None);
+    }
+}